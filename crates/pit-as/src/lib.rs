@@ -0,0 +1,284 @@
+//! # PIT AssemblyScript Code Generator
+//!
+//! Generates AssemblyScript guest bindings from PIT interface definitions.
+//!
+//! This crate emits an AssemblyScript file per interface, for implementing and using
+//! PIT resources from WebAssembly modules compiled with `asc --enable reference-types`.
+//!
+//! ## Generated Code
+//!
+//! For each PIT interface, this crate generates, in `R{rid}.ts`:
+//! - An interface `R{rid}` with all interface methods
+//! - `R{rid}_{method}` functions, the `pit/{rid}.{method}` imports calling methods of
+//!   resources
+//! - `{prefix}_of`, which exports an implementation of `R{rid}` as a new resource
+//!   through the `pit/{rid}.~{ctor}` import, keeping it in an object table
+//! - The `pit/{rid}/~{ctor}/...` exports, dispatching to the objects in that table
+//!
+//! where the prefix is `R{rid}`, or `R{rid}_{name}` for named implementations. Files
+//! generated for named implementations only contain the last three, and import the
+//! interface from `./R{rid}`.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use pit_core::parse_interface;
+//!
+//! let (_, interface) = parse_interface("{ method(I32) -> (I64) }").unwrap();
+//! let opts = pit_as::Opts { salt: vec![], name: None };
+//! let ts = pit_as::asify(&opts, &interface).unwrap();
+//! ```
+//!
+//! AssemblyScript only exports what the entry file exports, under the names of its
+//! functions, so the entry file must `export * from "./R{rid}"`, and the exports are
+//! named as by [`pit_patch_core::names::mangled_method_export_name`]. Compiled modules
+//! must be passed through `pit unmangle` to rename them to their PIT names.
+//!
+//! ## Resources
+//!
+//! Resources are `externref`s, which AssemblyScript cannot store in memory: they may
+//! be held in locals and globals, but not in arrays or objects. Resources a guest owns
+//! are dropped with `drop` from the runtime returned by [`runtime`]. AssemblyScript
+//! functions return at most one value, so interfaces with methods returning more than
+//! one are rejected with [`Unsupported`].
+
+use itertools::Itertools;
+use pit_core::{Arg, Interface, Sig};
+use pit_patch_core::names;
+use sha3::Digest;
+use std::fmt;
+use std::iter::once;
+
+/// Options for AssemblyScript code generation.
+pub struct Opts {
+    /// Additional bytes to include in the constructor ID hash for disambiguation.
+    pub salt: Vec<u8>,
+    /// Names the implementation, prefixing its symbols with `R{rid}_{name}` instead of `R{rid}`.
+    pub name: Option<String>,
+}
+
+/// An interface AssemblyScript code cannot be generated for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    /// The method returning more than one value.
+    pub method: String,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} returns more than one value, which AssemblyScript cannot express",
+            self.method
+        )
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// Generates AssemblyScript code from a PIT interface definition.
+///
+/// # Arguments
+///
+/// * `opts` - Code generation options
+/// * `i` - The PIT interface to generate bindings for
+///
+/// # Returns
+///
+/// A string containing the AssemblyScript source, to be saved as `R{rid}.ts`, or
+/// [`Unsupported`] if a method returns more than one value.
+pub fn asify(opts: &Opts, i: &Interface) -> Result<String, Unsupported> {
+    if let Some((a, _)) = i.methods.iter().find(|(_, b)| b.rets.len() > 1) {
+        return Err(Unsupported { method: a.clone() });
+    }
+    let rid = i.rid_str();
+    let p = match &opts.name {
+        Some(n) => format!("R{rid}_{n}"),
+        None => format!("R{rid}"),
+    };
+    let mut ha = sha3::Sha3_256::default();
+    ha.update(format!("~{rid}"));
+    ha.update(&opts.salt);
+    let ha = hex::encode(ha.finalize());
+    let decls = i
+        .methods
+        .iter()
+        .map(|(a, b)| format!("\n  {a}({}): {};", params(b), ret(b)))
+        .join("");
+    let imports = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            format!(
+                r#"
+
+@external("{}", "{a}")
+export declare function R{rid}_{a}({}): {};"#,
+                names::pit_module_name(&rid),
+                once("self: externref".to_owned())
+                    .chain(once(params(b)).filter(|p| !p.is_empty()))
+                    .join(", "),
+                ret(b)
+            )
+        })
+        .join("");
+    let exports = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let r = if b.rets.is_empty() { "" } else { "return " };
+            format!(
+                r#"
+
+export function {}({}): {} {{
+  {r}{p}_table[index]!.{a}({});
+}}"#,
+                names::mangled_method_export_name(&rid, &ha, a),
+                once("index: i32".to_owned())
+                    .chain(once(params(b)).filter(|p| !p.is_empty()))
+                    .join(", "),
+                ret(b),
+                (0..b.params.len()).map(|idx| format!("p{idx}")).join(", ")
+            )
+        })
+        .join("");
+    let head = match &opts.name {
+        None => format!(
+            r#"/** Methods of resources implementing the interface. */
+export interface R{rid} {{{decls}
+}}{imports}"#
+        ),
+        Some(_) => format!(r#"import {{ R{rid} }} from "./R{rid}";"#),
+    };
+    Ok(format!(
+        r#"{head}
+
+@external("{}", "{}")
+declare function {p}_ctor(index: i32): externref;
+
+const {p}_table = new Array<R{rid} | null>();
+const {p}_free = new Array<i32>();
+
+/** Exports an implementation of the interface as a new resource. */
+export function {p}_of(x: R{rid}): externref {{
+  let index: i32;
+  if ({p}_free.length > 0) {{
+    index = {p}_free.pop();
+    {p}_table[index] = x;
+  }} else {{
+    index = {p}_table.length;
+    {p}_table.push(x);
+  }}
+  return {p}_ctor(index);
+}}{exports}
+
+export function {}(index: i32): void {{
+  {p}_table[index] = null;
+  {p}_free.push(index);
+}}
+"#,
+        names::pit_module_name(&rid),
+        names::constructor_import_name(&ha),
+        names::mangled_drop_export_name(&rid, &ha),
+    ))
+}
+
+/// Renders the parameters of a method, without the leading resource.
+fn params(b: &Sig) -> String {
+    b.params
+        .iter()
+        .enumerate()
+        .map(|(idx, a)| format!("p{idx}: {}", as_ty(a)))
+        .join(", ")
+}
+
+/// Renders the result type of a method, which [`asify`] ensures has at most one.
+fn ret(b: &Sig) -> &'static str {
+    b.rets.first().map(as_ty).unwrap_or("void")
+}
+
+/// Returns the AssemblyScript type of a PIT argument.
+pub fn as_ty(a: &Arg) -> &'static str {
+    match a {
+        Arg::I32 => "u32",
+        Arg::I64 => "u64",
+        Arg::F32 => "f32",
+        Arg::F64 => "f64",
+        Arg::Resource { .. } => "externref",
+        _ => todo!(),
+    }
+}
+
+/// Returns the runtime the generated code may use, as `(file name, contents)` pairs.
+///
+/// The runtime provides `pit.ts`, which imports `pit.drop` as `drop`. Place it next to
+/// the generated files.
+pub fn runtime() -> Vec<(&'static str, &'static str)> {
+    vec![("pit.ts", include_str!("runtime/pit.ts"))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pit_golden::{golden, iface, single_result_iface};
+    use std::process::Command;
+
+    #[test]
+    fn bindings() {
+        let opts = Opts {
+            salt: vec![],
+            name: None,
+        };
+        golden!("iface.ts", &asify(&opts, &single_result_iface()).unwrap());
+    }
+
+    #[test]
+    fn named_bindings() {
+        let opts = Opts {
+            salt: b"second".to_vec(),
+            name: Some("second".to_owned()),
+        };
+        golden!("iface_named.ts", &asify(&opts, &single_result_iface()).unwrap());
+    }
+
+    #[test]
+    fn rejects_multiple_results() {
+        let opts = Opts {
+            salt: vec![],
+            name: None,
+        };
+        assert_eq!(
+            asify(&opts, &iface()),
+            Err(Unsupported {
+                method: "seek".to_owned()
+            })
+        );
+    }
+
+    /// Compiles the unnamed and named bindings, with the runtime, with asc.
+    #[test]
+    fn compiles_with_asc() {
+        let i = single_result_iface();
+        let rid = i.rid_str();
+        let dir = pit_golden::scratch("as");
+        let mut opts = Opts {
+            salt: vec![],
+            name: None,
+        };
+        std::fs::write(dir.join(format!("R{rid}.ts")), asify(&opts, &i).unwrap()).unwrap();
+        opts.salt = b"second".to_vec();
+        opts.name = Some("second".to_owned());
+        std::fs::write(dir.join(format!("R{rid}_second.ts")), asify(&opts, &i).unwrap()).unwrap();
+        std::fs::write(
+            dir.join("main.ts"),
+            format!("export * from \"./R{rid}\";\nexport * from \"./R{rid}_second\";\n"),
+        )
+        .unwrap();
+        pit_golden::write_all(&dir, runtime());
+        pit_golden::run(
+            Command::new("asc")
+                .current_dir(&dir)
+                .args(["main.ts", "--noEmit", "--enable", "reference-types"]),
+        );
+    }
+}
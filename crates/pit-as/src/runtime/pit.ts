@@ -0,0 +1,10 @@
+/*
+ * Runtime for AssemblyScript guest code generated by pit-as.
+ *
+ * Resources are externrefs, which AssemblyScript can hold in locals and globals but
+ * not in memory, so they cannot be stored in arrays, maps or class fields.
+ */
+
+/** Drops a resource the guest owns. */
+@external("pit", "drop")
+export declare function drop(resource: externref): void;
@@ -0,0 +1,59 @@
+/** Methods of resources implementing the interface. */
+export interface R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 {
+  give(p0: externref): void;
+  read(p0: u32): externref;
+  split(): externref;
+  write(p0: externref): void;
+}
+
+@external("pit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526", "give")
+export declare function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_give(self: externref, p0: externref): void;
+
+@external("pit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526", "read")
+export declare function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_read(self: externref, p0: u32): externref;
+
+@external("pit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526", "split")
+export declare function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_split(self: externref): externref;
+
+@external("pit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526", "write")
+export declare function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_write(self: externref, p0: externref): void;
+
+@external("pit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526", "~c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9")
+declare function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_ctor(index: i32): externref;
+
+const R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table = new Array<R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 | null>();
+const R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_free = new Array<i32>();
+
+/** Exports an implementation of the interface as a new resource. */
+export function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_of(x: R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526): externref {
+  let index: i32;
+  if (R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_free.length > 0) {
+    index = R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_free.pop();
+    R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table[index] = x;
+  } else {
+    index = R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table.length;
+    R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table.push(x);
+  }
+  return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_ctor(index);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9_give(index: i32, p0: externref): void {
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table[index]!.give(p0);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9_read(index: i32, p0: u32): externref {
+  return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table[index]!.read(p0);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9_split(index: i32): externref {
+  return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table[index]!.split();
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9_write(index: i32, p0: externref): void {
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table[index]!.write(p0);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9$drop(index: i32): void {
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_table[index] = null;
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_free.push(index);
+}
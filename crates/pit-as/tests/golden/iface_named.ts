@@ -0,0 +1,41 @@
+import { R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 } from "./R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526";
+
+@external("pit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526", "~b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b")
+declare function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_ctor(index: i32): externref;
+
+const R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table = new Array<R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 | null>();
+const R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_free = new Array<i32>();
+
+/** Exports an implementation of the interface as a new resource. */
+export function R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_of(x: R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526): externref {
+  let index: i32;
+  if (R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_free.length > 0) {
+    index = R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_free.pop();
+    R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table[index] = x;
+  } else {
+    index = R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table.length;
+    R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table.push(x);
+  }
+  return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_ctor(index);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b_give(index: i32, p0: externref): void {
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table[index]!.give(p0);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b_read(index: i32, p0: u32): externref {
+  return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table[index]!.read(p0);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b_split(index: i32): externref {
+  return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table[index]!.split();
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b_write(index: i32, p0: externref): void {
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table[index]!.write(p0);
+}
+
+export function pit_2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b$drop(index: i32): void {
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_table[index] = null;
+  R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_free.push(index);
+}
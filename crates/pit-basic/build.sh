@@ -1,4 +1,12 @@
 set -e
 cd $(dirname $0)
 cargo run -p pit-rust-generator ../../common/buffer.pit src/buffer/ffi.rs
-cargo run -p pit-rust-generator ../../common/buffer64.pit src/buffer64/ffi.rs
\ No newline at end of file
+cargo run -p pit-rust-generator ../../common/buffer64.pit src/buffer64/ffi.rs
+cargo run -p pit-rust-generator ../../common/buffer.pit --preserve-docs --host src/buffer/host.rs
+cargo run -p pit-rust-generator ../../common/buffer64.pit --preserve-docs --host src/buffer64/host.rs
+cargo run -p pit-rust-generator ../../common/bulk.pit --preserve-docs src/bulk/ffi.rs
+cargo run -p pit-rust-generator ../../common/bulk64.pit --preserve-docs src/bulk64/ffi.rs
+cargo run -p pit-rust-generator ../../common/reader.pit --preserve-docs src/reader/ffi.rs
+cargo run -p pit-rust-generator ../../common/writer.pit --preserve-docs src/writer/ffi.rs
+cargo run -p pit-rust-generator ../../common/growable.pit --preserve-docs src/growable/ffi.rs
+cargo run -p pit-rust-generator ../../common/growable64.pit --preserve-docs src/growable64/ffi.rs
@@ -6,6 +6,7 @@
 //! - The `R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5` trait (32-bit buffer)
 //! - Implementations for common Rust types (`Vec<u8>`, `Box<[u8]>`, slices)
 //! - Helper functions for working with buffers
+//! - With the `host` feature, host bindings in [`host`]
 //!
 //! ## Buffer Interface
 //!
@@ -19,6 +20,10 @@
 //! ```
 
 pub mod ffi;
+
+/// Host bindings for `buffer.pit`.
+#[cfg(feature = "host")]
+pub mod host;
 use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d;
 pub use ffi::*;
 use std::sync::Arc;
@@ -26,13 +31,15 @@ macro_rules! buffer_slice_impl {
     ($t:ty) => {
         impl R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 for $t {
             fn read8(&mut self, p0: u32) -> (u32) {
-                return self[p0 as usize].into();
+                return self.get(p0 as usize).map_or(0, |a| (*a).into());
             }
             fn write8(&mut self, p0: u32, p1: u32) -> () {
-                self[p0 as usize] = (p1 & 0xff) as u8;
+                if let Some(a) = self.get_mut(p0 as usize) {
+                    *a = (p1 & 0xff) as u8;
+                }
             }
             fn size(&mut self) -> (u32) {
-                return self.len().try_into().unwrap();
+                return self.len().try_into().unwrap_or(u32::MAX);
             }
         }
     };
@@ -40,21 +47,67 @@ macro_rules! buffer_slice_impl {
 buffer_slice_impl!(Vec<u8>);
 buffer_slice_impl!(Box<[u8]>);
 buffer_slice_impl!(&'static mut [u8]);
+#[cfg(feature = "host")]
+buffer_slice_impl!(memmap2::MmapMut);
 macro_rules! buffer_ro_slice_impl {
     ($t:ty) => {
         impl R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 for $t {
             fn read8(&mut self, p0: u32) -> (u32) {
-                return self[p0 as usize].into();
+                return self.get(p0 as usize).map_or(0, |a| (*a).into());
             }
             fn write8(&mut self, p0: u32, p1: u32) -> () {}
             fn size(&mut self) -> (u32) {
-                return self.len().try_into().unwrap();
+                return self.len().try_into().unwrap_or(u32::MAX);
             }
         }
     };
 }
 buffer_ro_slice_impl!(Arc<[u8]>);
 buffer_ro_slice_impl!(&'static [u8]);
+#[cfg(feature = "host")]
+buffer_ro_slice_impl!(memmap2::Mmap);
+
+/// Serves any buffer to guests from the host, one call at a time.
+///
+/// Register it with `pit_rust_host_lib::register` as an
+/// `Arc<dyn host::B867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5<U, E> + Send + Sync>`.
+#[cfg(feature = "host")]
+impl<
+        U: 'static,
+        E: pit_rust_host_lib::wasm_runtime_layer::backend::WasmEngine,
+        T: R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5,
+    > host::B867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5<U, E>
+    for std::sync::Mutex<T>
+{
+    fn read8(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        p0: u32,
+    ) -> pit_rust_host_lib::anyhow::Result<u32> {
+        Ok(crate::lock(self).read8(p0))
+    }
+    fn size(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+    ) -> pit_rust_host_lib::anyhow::Result<u32> {
+        Ok(crate::lock(self).size())
+    }
+    fn write8(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        p0: u32,
+        p1: u32,
+    ) -> pit_rust_host_lib::anyhow::Result<()> {
+        crate::lock(self).write8(p0, p1);
+        Ok(())
+    }
+    unsafe fn finalize(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+    ) -> pit_rust_host_lib::anyhow::Result<()> {
+        Ok(())
+    }
+}
 
 /// Creates an iterator over the bytes in a buffer.
 ///
@@ -73,6 +126,10 @@ pub fn slice<'a>(
 
 /// Copies bytes from one buffer to another.
 ///
+/// This makes a `read8` and a `write8` call per byte. Only the writes could be
+/// batched, so to copy more than a few bytes between resources, use buffers
+/// implementing the bulk interface and [`bulk::copy`](crate::bulk::copy).
+///
 /// # Arguments
 ///
 /// * `a` - Destination buffer
@@ -85,7 +142,7 @@ pub fn copy<'a, 'b>(
     b: &'b mut dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5,
     bi: u32,
 ) {
-    let l = (a.size() - ai).min(b.size() - bi);
+    let l = a.size().saturating_sub(ai).min(b.size().saturating_sub(bi));
     for i in 0..l {
         a.write8(ai + i, b.read8(bi + i));
     }
@@ -103,10 +160,8 @@ pub fn copy_slice_in<'a, 'b>(
     ai: u32,
     b: &'b [u8],
 ) {
-    for (i, b) in b.iter().enumerate() {
-        if (i as u32) + ai >= a.size() {
-            return;
-        }
+    let l = a.size().saturating_sub(ai);
+    for (i, b) in b.iter().take(l.try_into().unwrap_or(usize::MAX)).enumerate() {
         a.write8(ai + (i as u32), *b as u32)
     }
 }
@@ -123,10 +178,8 @@ pub fn copy_slice_out<'a, 'b>(
     b: &'b mut dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5,
     bi: u32,
 ) {
-    for (i, c) in c.iter_mut().enumerate() {
-        if (i as u32) + bi >= b.size() {
-            return;
-        }
+    let l = b.size().saturating_sub(bi);
+    for (i, c) in c.iter_mut().take(l.try_into().unwrap_or(usize::MAX)).enumerate() {
         *c = (b.read8(bi + (i as u32)) & 0xff) as u8;
     }
 }
@@ -136,10 +189,29 @@ pub fn copy_slice_out<'a, 'b>(
 /// `Snip<T>` wraps a buffer and provides the opposite interface:
 /// - If `T` implements the 64-bit interface, `Snip<T>` implements the 32-bit interface
 /// - If `T` implements the 32-bit interface, `Snip<T>` implements the 64-bit interface
+///
+/// A 64-bit buffer larger than `u32::MAX` bytes does not fit the 32-bit interface.
+/// [`Snip::narrow`] refuses such buffers; wrapped directly, only their first
+/// `u32::MAX` bytes are reachable, and offsets never wrap around.
 pub struct Snip<T> {
     /// The wrapped buffer.
     pub wrapped: T,
 }
+impl<T: crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>
+    Snip<T>
+{
+    /// Wraps a 64-bit buffer for use as a 32-bit buffer.
+    ///
+    /// # Returns
+    ///
+    /// The adapter, or the buffer back if it is larger than `u32::MAX` bytes.
+    pub fn narrow(mut wrapped: T) -> Result<Self, T> {
+        if wrapped.size() > u32::MAX.into() {
+            return Err(wrapped);
+        }
+        Ok(Snip { wrapped })
+    }
+}
 impl<T: crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>
     R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 for Snip<T>
 {
@@ -147,7 +219,7 @@ impl<T: crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b
         self.wrapped.read8(p0 as u64)
     }
     fn size(&mut self) -> (u32) {
-        self.wrapped.size() as u32
+        self.wrapped.size().try_into().unwrap_or(u32::MAX)
     }
     fn write8(&mut self, p0: u32, p1: u32) -> () {
         self.wrapped.write8(p0 as u64, p1)
@@ -157,13 +229,18 @@ impl<T: R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>
     R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d for Snip<T>
 {
     fn read8(&mut self, p0: u64) -> (u32) {
-        self.wrapped.read8(p0 as u32)
+        match u32::try_from(p0) {
+            Ok(a) => self.wrapped.read8(a),
+            Err(_) => 0,
+        }
     }
     fn size(&mut self) -> (u64) {
         self.wrapped.size().into()
     }
     fn write8(&mut self, p0: u64, p1: u32) -> () {
-        self.wrapped.write8(p0 as u32, p1)
+        if let Ok(a) = u32::try_from(p0) {
+            self.wrapped.write8(a, p1)
+        }
     }
 }
 
@@ -178,16 +255,184 @@ pub struct Slice<T> {
     /// The size of the slice.
     pub size: u32,
 }
+impl<T> Slice<T> {
+    /// Maps an offset in the slice to one in the wrapped buffer, or `None` if it is out
+    /// of range.
+    fn offset(&self, p0: u32) -> Option<u32> {
+        if p0 >= self.size {
+            return None;
+        }
+        self.begin.checked_add(p0)
+    }
+}
 impl<T: R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>
     R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 for Slice<T>
 {
     fn read8(&mut self, p0: u32) -> (u32) {
-        self.wrapped.read8(p0 + self.begin)
+        match self.offset(p0) {
+            Some(a) => self.wrapped.read8(a),
+            None => 0,
+        }
     }
     fn size(&mut self) -> (u32) {
         self.size
     }
     fn write8(&mut self, p0: u32, p1: u32) -> () {
-        self.wrapped.write8(p0 + self.begin, p1)
+        if let Some(a) = self.offset(p0) {
+            self.wrapped.write8(a, p1)
+        }
+    }
+}
+
+/// A [`std::io`] cursor over a buffer.
+///
+/// Implements [`Read`](std::io::Read), [`Write`](std::io::Write) and
+/// [`Seek`](std::io::Seek) at `pos`. Buffers have a fixed size, so reads and writes
+/// stop at its end rather than growing it.
+pub struct Cursor<T> {
+    /// The wrapped buffer.
+    pub wrapped: T,
+    /// The current position.
+    pub pos: u64,
+}
+impl<T: R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5> std::io::Read
+    for Cursor<T>
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = (self.wrapped.size() as u64)
+            .saturating_sub(self.pos)
+            .min(buf.len() as u64) as usize;
+        for (i, c) in buf[..n].iter_mut().enumerate() {
+            *c = (self.wrapped.read8(self.pos as u32 + i as u32) & 0xff) as u8;
+        }
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+impl<T: R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5> std::io::Write
+    for Cursor<T>
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = (self.wrapped.size() as u64)
+            .saturating_sub(self.pos)
+            .min(buf.len() as u64) as usize;
+        for (i, c) in buf[..n].iter().enumerate() {
+            self.wrapped.write8(self.pos as u32 + i as u32, *c as u32);
+        }
+        self.pos += n as u64;
+        Ok(n)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+impl<T: R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5> std::io::Seek
+    for Cursor<T>
+{
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.pos = seek(self.pos, self.wrapped.size().into(), pos)?;
+        Ok(self.pos)
+    }
+}
+
+/// Resolves a [`SeekFrom`](std::io::SeekFrom) against a position and buffer size.
+///
+/// Seeking past the end is allowed, as with [`std::io::Cursor`].
+pub(crate) fn seek(cur: u64, size: u64, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+    let (base, off) = match pos {
+        std::io::SeekFrom::Start(a) => return Ok(a),
+        std::io::SeekFrom::End(a) => (size, a),
+        std::io::SeekFrom::Current(a) => (cur, a),
+    };
+    base.checked_add_signed(off).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d as Buffer64;
+    use std::io::{Read, Seek, SeekFrom, Write};
+    use R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 as Buffer;
+
+    #[test]
+    fn ignores_out_of_range_bytes() {
+        let mut v = vec![1u8, 2];
+        Buffer::write8(&mut v, 2, 3);
+        Buffer::write8(&mut v, u32::MAX, 3);
+        assert_eq!(Buffer::read8(&mut v, 2), 0);
+        assert_eq!(Buffer::read8(&mut v, u32::MAX), 0);
+        assert_eq!(v, [1, 2]);
+        let mut r: Arc<[u8]> = Arc::from(&[1u8][..]);
+        Buffer::write8(&mut r, 0, 3);
+        assert_eq!(Buffer::read8(&mut r, 0), 1);
+    }
+
+    #[test]
+    fn slices_stay_in_range() {
+        let mut s = Slice {
+            wrapped: vec![1u8, 2, 3],
+            begin: 1,
+            size: 1,
+        };
+        assert_eq!(Buffer::read8(&mut s, 0), 2);
+        assert_eq!(Buffer::read8(&mut s, 1), 0);
+        Buffer::write8(&mut s, 1, 9);
+        assert_eq!(s.wrapped, [1, 2, 3]);
+        let mut s = Slice {
+            wrapped: vec![1u8],
+            begin: u32::MAX,
+            size: u32::MAX,
+        };
+        assert_eq!(Buffer::read8(&mut s, 1), 0);
+    }
+
+    #[test]
+    fn snips_without_wrapping() {
+        let mut s = Snip { wrapped: vec![1u8] };
+        assert_eq!(Buffer64::read8(&mut s, 1 << 32), 0);
+        Buffer64::write8(&mut s, 1 << 32, 9);
+        assert_eq!(s.wrapped, [1]);
+        assert!(Snip::narrow(vec![0u8; 4]).is_ok());
+    }
+
+    #[test]
+    fn copies_stop_at_the_end() {
+        let mut a = vec![0u8; 3];
+        let mut b = vec![1u8, 2, 3, 4];
+        copy(&mut a, 1, &mut b, 0);
+        assert_eq!(a, [0, 1, 2]);
+        copy(&mut a, 5, &mut b, 0);
+        copy(&mut a, 0, &mut b, u32::MAX);
+        assert_eq!(a, [0, 1, 2]);
+
+        copy_slice_in(&mut a, 2, &[7, 8]);
+        assert_eq!(a, [0, 1, 7]);
+        copy_slice_in(&mut a, u32::MAX, &[7, 8]);
+        let mut c = [9u8; 3];
+        copy_slice_out(&mut c, &mut a, 1);
+        assert_eq!(c, [1, 7, 9]);
+        copy_slice_out(&mut c, &mut a, u32::MAX);
+        assert_eq!(c, [1, 7, 9]);
+    }
+
+    #[test]
+    fn cursors_stop_at_the_end() {
+        let mut c = Cursor {
+            wrapped: vec![1u8, 2, 3],
+            pos: 0,
+        };
+        assert_eq!(c.seek(SeekFrom::End(-1)).unwrap(), 2);
+        assert_eq!(c.write(&[4, 5]).unwrap(), 1);
+        assert_eq!(c.wrapped, [1, 2, 4]);
+        c.seek(SeekFrom::Start(u64::MAX)).unwrap();
+        assert_eq!(c.read(&mut [0; 2]).unwrap(), 0);
+        assert_eq!(c.write(&[1]).unwrap(), 0);
+        assert!(c.seek(SeekFrom::Current(1)).is_err());
+        assert!(c.seek(SeekFrom::End(-4)).is_err());
     }
 }
@@ -28,6 +28,11 @@ pub trait R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 {
     fn size(&mut self) -> (u32);
     fn write8(&mut self, p0: u32, p1: u32) -> ();
 }
+pub struct Batch867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>,
+    queue: Vec<u8>,
+}
 const _: () = {
     #[link_section = ".pit-types"]
     static SECTION_CONTENT: [u8; 60usize] = [
@@ -119,6 +124,69 @@ const _: () = {
             .unwrap()
             .write8(p0, p1);
     }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batch867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batch867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5
+        for Batch867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5
+    {
+        fn read8(&mut self, p0: u32) -> (u32) {
+            self.flush();
+            return R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5::read8(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn size(&mut self) -> (u32) {
+            self.flush();
+            return R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5::size(
+                &mut self.inner,
+            );
+        }
+        fn write8(&mut self, p0: u32, p1: u32) -> () {
+            self.queue.extend_from_slice(&2u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            self.queue.extend_from_slice(&p1.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+    }
     impl From<Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>
         for ::tpit_rt::Tpit<
             Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
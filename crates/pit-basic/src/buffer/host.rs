@@ -0,0 +1,229 @@
+//! # 32-bit Buffer Host Bindings
+//!
+//! This module contains the auto-generated host bindings for the 32-bit buffer interface,
+//! over `pit-rust-host-lib`.
+//!
+//! Host objects implementing `B867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5` are passed to guests as
+//! `buffer.pit` resources with `pit_rust_host_lib::register`, through
+//! `Arc<dyn B867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5<U, E> + Send + Sync>`.
+
+pub trait B867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5<
+    U: 'static,
+    E: ::pit_rust_host_lib::wasm_runtime_layer::backend::WasmEngine,
+> {
+    fn read8(
+        &self,
+        ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        p0: ::pit_rust_host_lib::core::primitive::u32,
+    ) -> ::pit_rust_host_lib::anyhow::Result<
+        (::pit_rust_host_lib::core::primitive::u32),
+    >;
+    fn size(
+        &self,
+        ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+    ) -> ::pit_rust_host_lib::anyhow::Result<
+        (::pit_rust_host_lib::core::primitive::u32),
+    >;
+    fn write8(
+        &self,
+        ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        p0: ::pit_rust_host_lib::core::primitive::u32,
+        p1: ::pit_rust_host_lib::core::primitive::u32,
+    ) -> ::pit_rust_host_lib::anyhow::Result<()>;
+    unsafe fn finalize(
+        &self,
+        ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+    ) -> ::pit_rust_host_lib::anyhow::Result<()>;
+}
+const _: () = {
+    impl<
+        U: 'static,
+        E: ::pit_rust_host_lib::wasm_runtime_layer::backend::WasmEngine,
+    > B867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5<U, E>
+    for ::pit_rust_host_lib::RWrapped<U, E> {
+        fn read8(
+            &self,
+            ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+            p0: ::pit_rust_host_lib::core::primitive::u32,
+        ) -> ::pit_rust_host_lib::anyhow::Result<
+            (::pit_rust_host_lib::core::primitive::u32),
+        > {
+            let mut ctx = ctx;
+            let a = ::pit_rust_host_lib::core::clone::Clone::clone(&self.all[1usize]);
+            let args = ::pit_rust_host_lib::alloc::vec![
+                ::pit_rust_host_lib::wasm_runtime_layer::Value::I32((p0) as
+                ::pit_rust_host_lib::core::primitive::i32)
+            ];
+            let rets = a(
+                ::pit_rust_host_lib::wasm_runtime_layer::AsContextMut::as_context_mut(
+                    &mut ctx,
+                ),
+                args,
+            )?;
+            return Ok(
+                (match ::pit_rust_host_lib::core::clone::Clone::clone(&rets[0usize]) {
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value::I32(t) => {
+                        t as ::pit_rust_host_lib::core::primitive::u32
+                    }
+                    v => {
+                        return ::pit_rust_host_lib::core::result::Result::Err(
+                            ::pit_rust_host_lib::PitError::mismatch("i32", &v).into(),
+                        );
+                    }
+                }),
+            );
+        }
+        fn size(
+            &self,
+            ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        ) -> ::pit_rust_host_lib::anyhow::Result<
+            (::pit_rust_host_lib::core::primitive::u32),
+        > {
+            let mut ctx = ctx;
+            let a = ::pit_rust_host_lib::core::clone::Clone::clone(&self.all[2usize]);
+            let args = ::pit_rust_host_lib::alloc::vec![];
+            let rets = a(
+                ::pit_rust_host_lib::wasm_runtime_layer::AsContextMut::as_context_mut(
+                    &mut ctx,
+                ),
+                args,
+            )?;
+            return Ok(
+                (match ::pit_rust_host_lib::core::clone::Clone::clone(&rets[0usize]) {
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value::I32(t) => {
+                        t as ::pit_rust_host_lib::core::primitive::u32
+                    }
+                    v => {
+                        return ::pit_rust_host_lib::core::result::Result::Err(
+                            ::pit_rust_host_lib::PitError::mismatch("i32", &v).into(),
+                        );
+                    }
+                }),
+            );
+        }
+        fn write8(
+            &self,
+            ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+            p0: ::pit_rust_host_lib::core::primitive::u32,
+            p1: ::pit_rust_host_lib::core::primitive::u32,
+        ) -> ::pit_rust_host_lib::anyhow::Result<()> {
+            let mut ctx = ctx;
+            let a = ::pit_rust_host_lib::core::clone::Clone::clone(&self.all[3usize]);
+            let args = ::pit_rust_host_lib::alloc::vec![
+                ::pit_rust_host_lib::wasm_runtime_layer::Value::I32((p0) as
+                ::pit_rust_host_lib::core::primitive::i32),
+                ::pit_rust_host_lib::wasm_runtime_layer::Value::I32((p1) as
+                ::pit_rust_host_lib::core::primitive::i32)
+            ];
+            let _ = a(
+                ::pit_rust_host_lib::wasm_runtime_layer::AsContextMut::as_context_mut(
+                    &mut ctx,
+                ),
+                args,
+            )?;
+            return Ok(());
+        }
+        unsafe fn finalize(
+            &self,
+            ctx: ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        ) -> ::pit_rust_host_lib::anyhow::Result<()> {
+            self.all[0](ctx, ::pit_rust_host_lib::alloc::vec![])?;
+            Ok(())
+        }
+    }
+    impl<
+        U: 'static,
+        E: ::pit_rust_host_lib::wasm_runtime_layer::backend::WasmEngine,
+    > ::pit_rust_host_lib::IntoWrapped<U, E>
+    for dyn B867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5<
+        U,
+        E,
+    > + Send + Sync {
+        fn into_wrapped(
+            self: ::pit_rust_host_lib::alloc::sync::Arc<Self>,
+        ) -> ::pit_rust_host_lib::Wrapped<U, E> {
+            static RID: ::pit_rust_host_lib::spin::Once<
+                ::pit_rust_host_lib::alloc::sync::Arc<
+                    ::pit_rust_host_lib::pit_core::Interface,
+                >,
+            > = ::pit_rust_host_lib::spin::Once::new();
+            let rid = RID
+                .call_once(|| {
+                    let (_, i) = ::pit_rust_host_lib::pit_core::parse_interface(
+                            "{read8(I32) -> (I32);size() -> (I32);write8(I32,I32) -> ()}",
+                        )
+                        .expect("rendered interfaces parse");
+                    ::pit_rust_host_lib::alloc::sync::Arc::new(i)
+                });
+            ::pit_rust_host_lib::Wrapped {
+                rid: ::pit_rust_host_lib::alloc::sync::Arc::clone(rid),
+                all: ::pit_rust_host_lib::alloc::vec![
+                    { let r = ::pit_rust_host_lib::alloc::sync::Arc::clone(& self);
+                    ::pit_rust_host_lib::alloc::sync::Arc::new(move | ctx :
+                    ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut < '_, U, E
+                    >, _args : ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value > | ->
+                    ::pit_rust_host_lib::anyhow::Result <
+                    ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value >> { unsafe { r
+                    .finalize(ctx) ?; } Ok(::pit_rust_host_lib::alloc::vec![]) }) as
+                    ::pit_rust_host_lib::WrappedFn < U, E > }, { let r =
+                    ::pit_rust_host_lib::alloc::sync::Arc::clone(& self);
+                    ::pit_rust_host_lib::alloc::sync::Arc::new(move | mut ctx :
+                    ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut < '_, U, E
+                    >, args : ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value > | ->
+                    ::pit_rust_host_lib::anyhow::Result <
+                    ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value >> { let p0 = match
+                    ::pit_rust_host_lib::core::clone::Clone::clone(& args[0usize]) {
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value::I32(t) => t as
+                    ::pit_rust_host_lib::core::primitive::u32, v => return
+                    ::pit_rust_host_lib::core::result::Result::Err(::pit_rust_host_lib::PitError::mismatch("i32",
+                    & v).into()) }; let r = r
+                    .read8(::pit_rust_host_lib::wasm_runtime_layer::AsContextMut::as_context_mut(&
+                    mut ctx), p0) ?;
+                    Ok(::pit_rust_host_lib::alloc::vec![::pit_rust_host_lib::wasm_runtime_layer::Value::I32((r)
+                    as ::pit_rust_host_lib::core::primitive::i32)]) }) as
+                    ::pit_rust_host_lib::WrappedFn < U, E > }, { let r =
+                    ::pit_rust_host_lib::alloc::sync::Arc::clone(& self);
+                    ::pit_rust_host_lib::alloc::sync::Arc::new(move | mut ctx :
+                    ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut < '_, U, E
+                    >, _args : ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value > | ->
+                    ::pit_rust_host_lib::anyhow::Result <
+                    ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value >> { let r = r
+                    .size(::pit_rust_host_lib::wasm_runtime_layer::AsContextMut::as_context_mut(&
+                    mut ctx),) ?;
+                    Ok(::pit_rust_host_lib::alloc::vec![::pit_rust_host_lib::wasm_runtime_layer::Value::I32((r)
+                    as ::pit_rust_host_lib::core::primitive::i32)]) }) as
+                    ::pit_rust_host_lib::WrappedFn < U, E > }, { let r =
+                    ::pit_rust_host_lib::alloc::sync::Arc::clone(& self);
+                    ::pit_rust_host_lib::alloc::sync::Arc::new(move | mut ctx :
+                    ::pit_rust_host_lib::wasm_runtime_layer::StoreContextMut < '_, U, E
+                    >, args : ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value > | ->
+                    ::pit_rust_host_lib::anyhow::Result <
+                    ::pit_rust_host_lib::alloc::vec::Vec <
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value >> { let p0 = match
+                    ::pit_rust_host_lib::core::clone::Clone::clone(& args[0usize]) {
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value::I32(t) => t as
+                    ::pit_rust_host_lib::core::primitive::u32, v => return
+                    ::pit_rust_host_lib::core::result::Result::Err(::pit_rust_host_lib::PitError::mismatch("i32",
+                    & v).into()) }; let p1 = match
+                    ::pit_rust_host_lib::core::clone::Clone::clone(& args[1usize]) {
+                    ::pit_rust_host_lib::wasm_runtime_layer::Value::I32(t) => t as
+                    ::pit_rust_host_lib::core::primitive::u32, v => return
+                    ::pit_rust_host_lib::core::result::Result::Err(::pit_rust_host_lib::PitError::mismatch("i32",
+                    & v).into()) }; let _ = r
+                    .write8(::pit_rust_host_lib::wasm_runtime_layer::AsContextMut::as_context_mut(&
+                    mut ctx), p0, p1) ?; Ok(::pit_rust_host_lib::alloc::vec![]) }) as
+                    ::pit_rust_host_lib::WrappedFn < U, E > }
+                ],
+                ctor: ::pit_rust_host_lib::core::option::Option::None,
+                base: ::pit_rust_host_lib::alloc::vec![],
+            }
+        }
+    }
+};
@@ -6,6 +6,7 @@
 //! - The `R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d` trait (64-bit buffer)
 //! - Implementations for common Rust types (`Vec<u8>`, `Box<[u8]>`, slices)
 //! - Helper functions for working with large buffers
+//! - With the `host` feature, host bindings in [`host`]
 //! - Optional IC stable structures support
 //!
 //! ## Buffer Interface
@@ -24,6 +25,10 @@
 //! - `ic-stable-structures` - Enables integration with Internet Computer stable memory
 
 pub mod ffi;
+
+/// Host bindings for `buffer64.pit`.
+#[cfg(feature = "host")]
+pub mod host;
 pub use ffi::*;
 use std::sync::Arc;
 
@@ -34,13 +39,18 @@ macro_rules! buffer_slice_impl {
     ($t:ty) => {
         impl R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d for $t {
             fn read8(&mut self, p0: u64) -> (u32) {
-                return self[p0 as usize].into();
+                return usize::try_from(p0)
+                    .ok()
+                    .and_then(|a| self.get(a))
+                    .map_or(0, |a| (*a).into());
             }
             fn write8(&mut self, p0: u64, p1: u32) -> () {
-                self[p0 as usize] = (p1 & 0xff) as u8;
+                if let Some(a) = usize::try_from(p0).ok().and_then(|a| self.get_mut(a)) {
+                    *a = (p1 & 0xff) as u8;
+                }
             }
             fn size(&mut self) -> (u64) {
-                return self.len().try_into().unwrap();
+                return self.len() as u64;
             }
         }
     };
@@ -48,21 +58,70 @@ macro_rules! buffer_slice_impl {
 buffer_slice_impl!(Vec<u8>);
 buffer_slice_impl!(Box<[u8]>);
 buffer_slice_impl!(&'static mut [u8]);
+#[cfg(feature = "host")]
+buffer_slice_impl!(memmap2::MmapMut);
 macro_rules! buffer_ro_slice_impl {
     ($t:ty) => {
         impl R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d for $t {
             fn read8(&mut self, p0: u64) -> (u32) {
-                return self[p0 as usize].into();
+                return usize::try_from(p0)
+                    .ok()
+                    .and_then(|a| self.get(a))
+                    .map_or(0, |a| (*a).into());
             }
             fn write8(&mut self, p0: u64, p1: u32) -> () {}
             fn size(&mut self) -> (u64) {
-                return self.len().try_into().unwrap();
+                return self.len() as u64;
             }
         }
     };
 }
 buffer_ro_slice_impl!(Arc<[u8]>);
 buffer_ro_slice_impl!(&'static [u8]);
+#[cfg(feature = "host")]
+buffer_ro_slice_impl!(memmap2::Mmap);
+
+/// Serves any buffer to guests from the host, one call at a time.
+///
+/// Register it with `pit_rust_host_lib::register` as an
+/// `Arc<dyn host::B68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d<U, E> + Send + Sync>`.
+#[cfg(feature = "host")]
+impl<
+        U: 'static,
+        E: pit_rust_host_lib::wasm_runtime_layer::backend::WasmEngine,
+        T: R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d,
+    > host::B68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d<U, E>
+    for std::sync::Mutex<T>
+{
+    fn read8(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        p0: u64,
+    ) -> pit_rust_host_lib::anyhow::Result<u32> {
+        Ok(crate::lock(self).read8(p0))
+    }
+    fn size(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+    ) -> pit_rust_host_lib::anyhow::Result<u64> {
+        Ok(crate::lock(self).size())
+    }
+    fn write8(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+        p0: u64,
+        p1: u32,
+    ) -> pit_rust_host_lib::anyhow::Result<()> {
+        crate::lock(self).write8(p0, p1);
+        Ok(())
+    }
+    unsafe fn finalize(
+        &self,
+        _: pit_rust_host_lib::wasm_runtime_layer::StoreContextMut<'_, U, E>,
+    ) -> pit_rust_host_lib::anyhow::Result<()> {
+        Ok(())
+    }
+}
 
 /// Creates an iterator over the bytes in a 64-bit addressable buffer.
 ///
@@ -93,7 +152,7 @@ pub fn copy<'a, 'b>(
     b: &'b mut dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d,
     bi: u64,
 ) {
-    let l = (a.size() - ai).min(b.size() - bi);
+    let l = a.size().saturating_sub(ai).min(b.size().saturating_sub(bi));
     for i in 0..l {
         a.write8(ai + i, b.read8(bi + i));
     }
@@ -111,10 +170,8 @@ pub fn copy_slice_in<'a, 'b>(
     ai: u64,
     b: &'b [u8],
 ) {
-    for (i, b) in b.iter().enumerate() {
-        if (i as u64) + ai >= a.size() {
-            return;
-        }
+    let l = a.size().saturating_sub(ai);
+    for (i, b) in b.iter().take(l.try_into().unwrap_or(usize::MAX)).enumerate() {
         a.write8(ai + (i as u64), *b as u32)
     }
 }
@@ -131,10 +188,8 @@ pub fn copy_slice_out<'a, 'b>(
     b: &'b mut dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d,
     bi: u64,
 ) {
-    for (i, c) in c.iter_mut().enumerate() {
-        if (i as u64) + bi >= b.size() {
-            return;
-        }
+    let l = b.size().saturating_sub(bi);
+    for (i, c) in c.iter_mut().take(l.try_into().unwrap_or(usize::MAX)).enumerate() {
         *c = (b.read8(bi + (i as u64)) & 0xff) as u8;
     }
 }
@@ -150,16 +205,125 @@ pub struct Slice<T> {
     /// The size of the slice (64-bit).
     pub size: u64,
 }
+impl<T> Slice<T> {
+    /// Maps an offset in the slice to one in the wrapped buffer, or `None` if it is out
+    /// of range.
+    fn offset(&self, p0: u64) -> Option<u64> {
+        if p0 >= self.size {
+            return None;
+        }
+        self.begin.checked_add(p0)
+    }
+}
 impl<T: R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>
     R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d for Slice<T>
 {
     fn read8(&mut self, p0: u64) -> (u32) {
-        self.wrapped.read8(p0 + self.begin)
+        match self.offset(p0) {
+            Some(a) => self.wrapped.read8(a),
+            None => 0,
+        }
     }
     fn size(&mut self) -> (u64) {
         self.size
     }
     fn write8(&mut self, p0: u64, p1: u32) -> () {
-        self.wrapped.write8(p0 + self.begin, p1)
+        if let Some(a) = self.offset(p0) {
+            self.wrapped.write8(a, p1)
+        }
+    }
+}
+
+/// A [`std::io`] cursor over a 64-bit addressable buffer.
+///
+/// Implements [`Read`](std::io::Read), [`Write`](std::io::Write) and
+/// [`Seek`](std::io::Seek) at `pos`. Buffers have a fixed size, so reads and writes
+/// stop at its end rather than growing it.
+pub struct Cursor<T> {
+    /// The wrapped buffer.
+    pub wrapped: T,
+    /// The current position.
+    pub pos: u64,
+}
+impl<T: R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d> std::io::Read
+    for Cursor<T>
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self
+            .wrapped
+            .size()
+            .saturating_sub(self.pos)
+            .min(buf.len() as u64) as usize;
+        for (i, c) in buf[..n].iter_mut().enumerate() {
+            *c = (self.wrapped.read8(self.pos + i as u64) & 0xff) as u8;
+        }
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+impl<T: R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d> std::io::Write
+    for Cursor<T>
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self
+            .wrapped
+            .size()
+            .saturating_sub(self.pos)
+            .min(buf.len() as u64) as usize;
+        for (i, c) in buf[..n].iter().enumerate() {
+            self.wrapped.write8(self.pos + i as u64, *c as u32);
+        }
+        self.pos += n as u64;
+        Ok(n)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+impl<T: R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d> std::io::Seek
+    for Cursor<T>
+{
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.pos = crate::buffer::seek(self.pos, self.wrapped.size(), pos)?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d as Buffer64;
+
+    #[test]
+    fn ignores_out_of_range_bytes() {
+        let mut v = vec![1u8, 2];
+        Buffer64::write8(&mut v, u64::MAX, 3);
+        assert_eq!(Buffer64::read8(&mut v, u64::MAX), 0);
+        assert_eq!(v, [1, 2]);
+    }
+
+    #[test]
+    fn slices_stay_in_range() {
+        let mut s = Slice {
+            wrapped: vec![1u8, 2, 3],
+            begin: u64::MAX,
+            size: 2,
+        };
+        assert_eq!(Buffer64::read8(&mut s, 1), 0);
+        Buffer64::write8(&mut s, 1, 9);
+        assert_eq!(s.wrapped, [1, 2, 3]);
+    }
+
+    #[test]
+    fn copies_stop_at_the_end() {
+        let mut a = vec![0u8; 2];
+        let mut b = vec![1u8, 2, 3];
+        copy(&mut a, 0, &mut b, 2);
+        assert_eq!(a, [3, 0]);
+        copy(&mut a, u64::MAX, &mut b, 0);
+        copy_slice_in(&mut a, u64::MAX, &[1]);
+        let mut c = [9u8; 2];
+        copy_slice_out(&mut c, &mut a, u64::MAX);
+        assert_eq!((a, c), (vec![3, 0], [9, 9]));
     }
 }
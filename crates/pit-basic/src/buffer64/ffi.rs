@@ -28,6 +28,11 @@ pub trait R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d {
     fn size(&mut self) -> (u64);
     fn write8(&mut self, p0: u64, p1: u32) -> ();
 }
+pub struct Batch68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>>,
+    queue: Vec<u8>,
+}
 const _: () = {
     #[link_section = ".pit-types"]
     static SECTION_CONTENT: [u8; 60usize] = [
@@ -119,6 +124,69 @@ const _: () = {
             .unwrap()
             .write8(p0, p1);
     }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batch68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batch68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d
+        for Batch68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d
+    {
+        fn read8(&mut self, p0: u64) -> (u32) {
+            self.flush();
+            return R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d::read8(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn size(&mut self) -> (u64) {
+            self.flush();
+            return R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d::size(
+                &mut self.inner,
+            );
+        }
+        fn write8(&mut self, p0: u64, p1: u32) -> () {
+            self.queue.extend_from_slice(&2u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            self.queue.extend_from_slice(&p1.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+    }
     impl From<Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>>
         for ::tpit_rt::Tpit<
             Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
@@ -2,8 +2,13 @@
 //!
 //! Provides buffer trait implementations for Internet Computer stable memory.
 //!
-//! This module allows using IC stable structures as PIT buffers, enabling
-//! persistent storage on the Internet Computer platform.
+//! This module allows using IC stable structures as PIT buffers, bulk buffers and growable
+//! buffers, enabling persistent storage on the Internet Computer platform. The bulk
+//! interfaces are implemented on [`Shared<MemBuf>`](crate::bulk::Shared), for callers
+//! sharing the canister's memory.
+//!
+//! Stable memory grows in 64 KiB pages and cannot shrink, so growing a [`MemBuf`] rounds
+//! up to whole pages and `truncate` has no effect.
 
 use ic_stable_structures::Memory;
 
@@ -27,33 +32,168 @@ pub struct MemBuf<T> {
     /// The wrapped IC memory implementation.
     pub wrapped: T,
 }
+impl<T: Memory> MemBuf<T> {
+    /// Returns the size of the wrapped memory in bytes.
+    fn bytes(&self) -> u64 {
+        self.wrapped.size() * 65536
+    }
+    /// Returns the size of the wrapped memory in bytes, saturated to 32 bits.
+    fn bytes32(&self) -> u32 {
+        self.bytes().try_into().unwrap_or(u32::MAX)
+    }
+    /// Reads a byte, or 0 past the end of the memory.
+    fn get(&self, p0: u64) -> u32 {
+        if p0 >= self.bytes() {
+            return 0;
+        }
+        let mut a = [0u8; 1];
+        self.wrapped.read(p0, &mut a);
+        return a[0] as u32;
+    }
+    /// Writes a byte, ignoring writes past the end of the memory.
+    fn set(&self, p0: u64, p1: u32) {
+        if p0 < self.bytes() {
+            self.wrapped.write(p0, &[(p1 & 0xff) as u8])
+        }
+    }
+    /// Grows the wrapped memory by enough pages for `delta` more bytes, returning the
+    /// previous size in bytes.
+    fn grow_bytes(&mut self, delta: u64) -> Option<u64> {
+        let old = self.wrapped.grow(delta.div_ceil(65536));
+        if old < 0 {
+            return None;
+        }
+        Some(old as u64 * 65536)
+    }
+}
 impl<T: Memory> super::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d
     for MemBuf<T>
 {
     fn read8(&mut self, p0: u64) -> (u32) {
-        let mut a = [0u8; 1];
-        self.wrapped.read(p0, &mut a);
-        return a[0] as u32;
+        return self.get(p0);
     }
     fn size(&mut self) -> (u64) {
-        return self.wrapped.size() * 65536;
+        return self.bytes();
     }
     fn write8(&mut self, p0: u64, p1: u32) -> () {
-        self.wrapped.write(p0, &[(p1 & 0xff) as u8])
+        self.set(p0, p1)
     }
 }
 impl<T: Memory> crate::buffer::R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5
     for MemBuf<T>
 {
     fn read8(&mut self, p0: u32) -> (u32) {
-        let mut a = [0u8; 1];
-        self.wrapped.read(p0.into(), &mut a);
-        return a[0] as u32;
+        return self.get(p0.into());
+    }
+    fn size(&mut self) -> (u32) {
+        return self.bytes32();
+    }
+    fn write8(&mut self, p0: u32, p1: u32) -> () {
+        self.set(p0.into(), p1)
+    }
+}
+impl<T: Memory> crate::bulk64::R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd
+    for crate::bulk::Shared<MemBuf<T>>
+{
+    fn read(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+        let n = crate::bulk::clamp(p0, p2, self.wrapped.bytes());
+        self.wrapped
+            .wrapped
+            .read(p0, unsafe { crate::bulk::mem(p1, n) });
+        return n;
+    }
+    fn read8(&mut self, p0: u64) -> (u32) {
+        return self.wrapped.get(p0);
+    }
+    fn size(&mut self) -> (u64) {
+        return self.wrapped.bytes();
+    }
+    fn write(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+        let n = crate::bulk::clamp(p0, p2, self.wrapped.bytes());
+        self.wrapped
+            .wrapped
+            .write(p0, unsafe { crate::bulk::mem(p1, n) });
+        return n;
+    }
+    fn write8(&mut self, p0: u64, p1: u32) -> () {
+        self.wrapped.set(p0, p1)
+    }
+}
+impl<T: Memory> crate::bulk::R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606
+    for crate::bulk::Shared<MemBuf<T>>
+{
+    fn read(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+        let n = crate::bulk::clamp(p0.into(), p2, self.wrapped.bytes32().into());
+        self.wrapped
+            .wrapped
+            .read(p0.into(), unsafe { crate::bulk::mem(p1, n) });
+        return n;
+    }
+    fn read8(&mut self, p0: u32) -> (u32) {
+        return self.wrapped.get(p0.into());
+    }
+    fn size(&mut self) -> (u32) {
+        return self.wrapped.bytes32();
+    }
+    fn write(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+        let n = crate::bulk::clamp(p0.into(), p2, self.wrapped.bytes32().into());
+        self.wrapped
+            .wrapped
+            .write(p0.into(), unsafe { crate::bulk::mem(p1, n) });
+        return n;
+    }
+    fn write8(&mut self, p0: u32, p1: u32) -> () {
+        self.wrapped.set(p0.into(), p1)
+    }
+}
+impl<T: Memory> crate::growable64::Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a
+    for MemBuf<T>
+{
+    fn grow(&mut self, p0: u64) -> (u64) {
+        return self.grow_bytes(p0).unwrap_or(u64::MAX);
+    }
+    fn read8(&mut self, p0: u64) -> (u32) {
+        return self.get(p0);
+    }
+    fn resize(&mut self, p0: u64) -> (u64) {
+        let size = self.bytes();
+        if p0 > size {
+            self.grow_bytes(p0 - size);
+        }
+        return self.bytes();
+    }
+    fn size(&mut self) -> (u64) {
+        return self.bytes();
+    }
+    fn truncate(&mut self, p0: u64) -> () {}
+    fn write8(&mut self, p0: u64, p1: u32) -> () {
+        self.set(p0, p1)
+    }
+}
+impl<T: Memory> crate::growable::R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73
+    for MemBuf<T>
+{
+    fn grow(&mut self, p0: u32) -> (u32) {
+        if self.bytes() + u64::from(p0) >= 1 << 32 {
+            return u32::MAX;
+        }
+        return self.grow_bytes(p0.into()).map_or(u32::MAX, |a| a as u32);
+    }
+    fn read8(&mut self, p0: u32) -> (u32) {
+        return self.get(p0.into());
+    }
+    fn resize(&mut self, p0: u32) -> (u32) {
+        let size = self.bytes();
+        if u64::from(p0) > size {
+            self.grow_bytes(u64::from(p0) - size);
+        }
+        return self.bytes32();
     }
     fn size(&mut self) -> (u32) {
-        return ((self.wrapped.size() * 65536) & 0xffffffff) as u32;
+        return self.bytes32();
     }
+    fn truncate(&mut self, p0: u32) -> () {}
     fn write8(&mut self, p0: u32, p1: u32) -> () {
-        self.wrapped.write(p0.into(), &[(p1 & 0xff) as u8])
+        self.set(p0.into(), p1)
     }
 }
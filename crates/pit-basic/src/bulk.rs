@@ -0,0 +1,325 @@
+//! # 32-bit Bulk Buffer Module
+//!
+//! Provides the 32-bit bulk buffer interface and implementations.
+//!
+//! This module contains:
+//! - The `R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606` trait (32-bit bulk buffer)
+//! - [`Shared`], which serves the interface from common Rust types (`Vec<u8>`,
+//!   `Box<[u8]>`, slices) and byte-only buffers to callers sharing its memory
+//! - [`Bytewise`], which adapts a byte-only buffer for [`Shared`]
+//! - [`Bytes`], which serves the byte interface from a bulk buffer
+//! - Helper functions for block copies
+//!
+//! ## Buffer Interface
+//!
+//! The buffer interface is defined in `bulk.pit`:
+//! ```text
+//! {
+//!     read(I32, I32, I32) -> (I32);
+//!     read8(I32) -> (I32);
+//!     size() -> (I32);
+//!     write(I32, I32, I32) -> (I32);
+//!     write8(I32, I32) -> ()
+//! }
+//! ```
+//!
+//! ## Shared memory
+//!
+//! `read` and `write` take a pointer and length in the caller's linear memory.
+//! The implementations in this crate access that memory directly, which is only valid
+//! when the caller and the buffer share a memory, as within one wasm32 module. Nothing
+//! translates pointers between modules with separate memories, so a resource reached
+//! through a `pit/{rid}/~{ctor}/read` export would otherwise let any peer read and write
+//! the exporting module's memory at will.
+//!
+//! The implementations are therefore only provided on [`Shared`], whose constructor
+//! is `unsafe`: wrapping a buffer asserts that it is only ever handed to callers
+//! sharing this module's memory.
+
+pub mod ffi;
+use crate::buffer::R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5;
+pub use ffi::*;
+use std::sync::Arc;
+
+/// Returns the caller's memory at `ptr` as a slice.
+///
+/// # Safety
+///
+/// `ptr` and `len` must describe memory of the current address space that is not
+/// otherwise borrowed.
+pub(crate) unsafe fn mem<'a>(ptr: u32, len: u32) -> &'a mut [u8] {
+    if len == 0 {
+        return &mut [];
+    }
+    std::slice::from_raw_parts_mut(ptr as usize as *mut u8, len as usize)
+}
+
+/// A buffer serving the bulk interfaces to callers sharing its linear memory.
+///
+/// Implements the bulk interfaces for `Vec<u8>`, `Box<[u8]>`, slices, [`Bytewise`],
+/// files with the `host` feature, and IC stable memory with the `ic-stable-structures`
+/// feature.
+/// See the [module documentation](self) for why this needs `unsafe`.
+pub struct Shared<T> {
+    pub(crate) wrapped: T,
+}
+impl<T> Shared<T> {
+    /// Wraps a buffer for callers sharing this module's linear memory.
+    ///
+    /// # Safety
+    ///
+    /// Every `read` and `write` call on the result, whether made here or by a peer the
+    /// resource is passed to, must come from code sharing this module's memory and
+    /// pass a pointer and length describing memory that is not otherwise borrowed.
+    pub unsafe fn new(wrapped: T) -> Self {
+        Self { wrapped }
+    }
+    /// Returns the wrapped buffer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.wrapped
+    }
+    /// Unwraps the buffer.
+    pub fn into_inner(self) -> T {
+        self.wrapped
+    }
+}
+
+/// Clamps a block transfer at `offset` of `len` bytes to a buffer of `size` bytes.
+pub(crate) fn clamp(offset: u64, len: u32, size: u64) -> u32 {
+    size.saturating_sub(offset).min(len as u64) as u32
+}
+
+macro_rules! bulk_slice_impl {
+    ($t:ty) => {
+        impl R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 for Shared<$t> {
+            fn read(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+                let n = clamp(p0.into(), p2, self.wrapped.len() as u64);
+                if n == 0 {
+                    return 0;
+                }
+                let b = p0 as usize;
+                unsafe { mem(p1, n) }.copy_from_slice(&self.wrapped[b..b + n as usize]);
+                return n;
+            }
+            fn read8(&mut self, p0: u32) -> (u32) {
+                return self.wrapped.get(p0 as usize).map_or(0, |a| (*a).into());
+            }
+            fn size(&mut self) -> (u32) {
+                return self.wrapped.len().try_into().unwrap_or(u32::MAX);
+            }
+            fn write(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+                let n = clamp(p0.into(), p2, self.wrapped.len() as u64);
+                if n == 0 {
+                    return 0;
+                }
+                let b = p0 as usize;
+                self.wrapped[b..b + n as usize].copy_from_slice(unsafe { mem(p1, n) });
+                return n;
+            }
+            fn write8(&mut self, p0: u32, p1: u32) -> () {
+                if let Some(a) = self.wrapped.get_mut(p0 as usize) {
+                    *a = (p1 & 0xff) as u8;
+                }
+            }
+        }
+    };
+}
+bulk_slice_impl!(Vec<u8>);
+bulk_slice_impl!(Box<[u8]>);
+bulk_slice_impl!(&'static mut [u8]);
+macro_rules! bulk_ro_slice_impl {
+    ($t:ty) => {
+        impl R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 for Shared<$t> {
+            fn read(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+                let n = clamp(p0.into(), p2, self.wrapped.len() as u64);
+                if n == 0 {
+                    return 0;
+                }
+                let b = p0 as usize;
+                unsafe { mem(p1, n) }.copy_from_slice(&self.wrapped[b..b + n as usize]);
+                return n;
+            }
+            fn read8(&mut self, p0: u32) -> (u32) {
+                return self.wrapped.get(p0 as usize).map_or(0, |a| (*a).into());
+            }
+            fn size(&mut self) -> (u32) {
+                return self.wrapped.len().try_into().unwrap_or(u32::MAX);
+            }
+            fn write(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+                return 0;
+            }
+            fn write8(&mut self, p0: u32, p1: u32) -> () {}
+        }
+    };
+}
+bulk_ro_slice_impl!(Arc<[u8]>);
+bulk_ro_slice_impl!(&'static [u8]);
+
+/// Adapter serving the bulk interface from a byte-only buffer, as `Shared<Bytewise<T>>`.
+///
+/// Block transfers fall back to one `read8` or `write8` call per byte, so a peer
+/// implementing only `buffer.pit` can be used wherever a bulk buffer is expected.
+pub struct Bytewise<T> {
+    /// The wrapped buffer.
+    pub wrapped: T,
+}
+impl<T: R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>
+    R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 for Shared<Bytewise<T>>
+{
+    fn read(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+        let n = clamp(p0.into(), p2, self.wrapped.wrapped.size().into());
+        for (i, c) in unsafe { mem(p1, n) }.iter_mut().enumerate() {
+            *c = (self.wrapped.wrapped.read8(p0 + i as u32) & 0xff) as u8;
+        }
+        return n;
+    }
+    fn read8(&mut self, p0: u32) -> (u32) {
+        self.wrapped.wrapped.read8(p0)
+    }
+    fn size(&mut self) -> (u32) {
+        self.wrapped.wrapped.size()
+    }
+    fn write(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+        let n = clamp(p0.into(), p2, self.wrapped.wrapped.size().into());
+        for (i, c) in unsafe { mem(p1, n) }.iter().enumerate() {
+            self.wrapped.wrapped.write8(p0 + i as u32, *c as u32);
+        }
+        return n;
+    }
+    fn write8(&mut self, p0: u32, p1: u32) -> () {
+        self.wrapped.wrapped.write8(p0, p1)
+    }
+}
+
+/// Adapter serving the byte interface from a bulk buffer.
+pub struct Bytes<T> {
+    /// The wrapped buffer.
+    pub wrapped: T,
+}
+impl<T: R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>
+    R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 for Bytes<T>
+{
+    fn read8(&mut self, p0: u32) -> (u32) {
+        self.wrapped.read8(p0)
+    }
+    fn size(&mut self) -> (u32) {
+        self.wrapped.size()
+    }
+    fn write8(&mut self, p0: u32, p1: u32) -> () {
+        self.wrapped.write8(p0, p1)
+    }
+}
+
+/// Copies bytes from a Rust slice into a bulk buffer in one call.
+///
+/// The buffer is given a pointer into this module's memory, so this is only available
+/// where pointers are 32 bits wide, as on wasm32.
+///
+/// # Arguments
+///
+/// * `a` - Destination buffer
+/// * `ai` - Starting offset in destination
+/// * `b` - Source slice
+///
+/// # Returns
+///
+/// The number of bytes copied.
+#[cfg(target_pointer_width = "32")]
+pub fn copy_slice_in<'a, 'b>(
+    a: &'a mut dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606,
+    ai: u32,
+    b: &'b [u8],
+) -> u32 {
+    a.write(ai, b.as_ptr() as usize as u32, b.len() as u32)
+}
+
+/// Copies bytes from a bulk buffer into a Rust slice in one call.
+///
+/// Like [`copy_slice_in`], this is only available where pointers are 32 bits wide.
+///
+/// # Arguments
+///
+/// * `c` - Destination slice
+/// * `b` - Source buffer
+/// * `bi` - Starting offset in source
+///
+/// # Returns
+///
+/// The number of bytes copied.
+#[cfg(target_pointer_width = "32")]
+pub fn copy_slice_out<'a, 'b>(
+    c: &'a mut [u8],
+    b: &'b mut dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606,
+    bi: u32,
+) -> u32 {
+    b.read(bi, c.as_mut_ptr() as usize as u32, c.len() as u32)
+}
+
+/// Copies bytes from one bulk buffer to another through a fixed-size staging block.
+///
+/// Like [`copy_slice_in`], this is only available where pointers are 32 bits wide.
+///
+/// # Arguments
+///
+/// * `a` - Destination buffer
+/// * `ai` - Starting offset in destination
+/// * `b` - Source buffer
+/// * `bi` - Starting offset in source
+#[cfg(target_pointer_width = "32")]
+pub fn copy<'a, 'b>(
+    a: &'a mut dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606,
+    ai: u32,
+    b: &'b mut dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606,
+    bi: u32,
+) {
+    let mut block = [0u8; 4096];
+    let l = a.size().saturating_sub(ai).min(b.size().saturating_sub(bi));
+    let mut i = 0;
+    while i < l {
+        let n = copy_slice_out(&mut block[..(l - i).min(4096) as usize], b, bi + i);
+        if n == 0 {
+            return;
+        }
+        copy_slice_in(a, ai + i, &block[..n as usize]);
+        i += n;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 as Bulk;
+
+    #[test]
+    fn clamps_transfers() {
+        assert_eq!(clamp(0, 4, 3), 3);
+        assert_eq!(clamp(2, 4, 3), 1);
+        assert_eq!(clamp(5, 4, 3), 0);
+        assert_eq!(clamp(u64::MAX, u32::MAX, u64::MAX), 0);
+    }
+
+    #[test]
+    fn transfers_past_the_end_do_nothing() {
+        // Safety: transfers past the end return before touching the (null) pointer.
+        let mut s = unsafe { Shared::new(vec![1u8, 2]) };
+        assert_eq!(Bulk::read(&mut s, 2, 0, 4), 0);
+        assert_eq!(Bulk::write(&mut s, u32::MAX, 0, 4), 0);
+        assert_eq!(Bulk::read8(&mut s, 2), 0);
+        Bulk::write8(&mut s, 2, 3);
+        assert_eq!(s.into_inner(), [1, 2]);
+    }
+
+    #[test]
+    fn adapts_byte_buffers() {
+        let mut s = unsafe {
+            Shared::new(Bytewise {
+                wrapped: vec![1u8, 2],
+            })
+        };
+        assert_eq!(Bulk::read(&mut s, 2, 0, 4), 0);
+        assert_eq!(Bulk::size(&mut s), 2);
+        let mut b = Bytes { wrapped: s };
+        R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5::write8(&mut b, 1, 3);
+        assert_eq!(b.wrapped.into_inner().wrapped, [1, 3]);
+    }
+}
@@ -0,0 +1,273 @@
+//! # 32-bit Bulk Buffer FFI
+//!
+//! This module contains the auto-generated FFI bindings for the 32-bit bulk buffer interface.
+//!
+//! The resource ID `2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606`
+//! corresponds to the `bulk.pit` interface definition:
+//!
+//! ```text
+//! {
+//!     read(I32, I32, I32) -> (I32);
+//!     read8(I32) -> (I32);
+//!     size() -> (I32);
+//!     write(I32, I32, I32) -> (I32);
+//!     write8(I32, I32) -> ()
+//! }
+//! ```
+
+/// A 32-bit addressable byte buffer interface with block transfers.
+///
+/// A superset of the 32-bit byte buffer interface, adding block reads and writes
+/// between the buffer and the caller's linear memory.
+///
+/// # Methods
+///
+/// - `read(offset, ptr, len)` - Copy up to `len` bytes at `offset` to `ptr`, returning the count
+/// - `read8(offset)` - Read a single byte at the given offset
+/// - `size()` - Get the total size of the buffer in bytes
+/// - `write(offset, ptr, len)` - Copy up to `len` bytes at `ptr` to `offset`, returning the count
+/// - `write8(offset, value)` - Write a single byte at the given offset
+pub trait R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 {
+    fn read(&mut self, p0: u32, p1: u32, p2: u32) -> (u32);
+    fn read8(&mut self, p0: u32) -> (u32);
+    fn size(&mut self) -> (u32);
+    fn write(&mut self, p0: u32, p1: u32, p2: u32) -> (u32);
+    fn write8(&mut self, p0: u32, p1: u32) -> ();
+}
+pub struct Batch2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>>,
+    queue: Vec<u8>,
+}
+const _: () = {
+    #[link_section = ".pit-types"]
+    static SECTION_CONTENT: [u8; 115usize] = [
+        123u8, 114u8, 101u8, 97u8, 100u8, 40u8, 73u8, 51u8, 50u8, 44u8, 73u8, 51u8, 50u8, 44u8,
+        73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 114u8,
+        101u8, 97u8, 100u8, 56u8, 40u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 73u8,
+        51u8, 50u8, 41u8, 59u8, 115u8, 105u8, 122u8, 101u8, 40u8, 41u8, 32u8, 45u8, 62u8, 32u8,
+        40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 119u8, 114u8, 105u8, 116u8, 101u8, 40u8, 73u8, 51u8,
+        50u8, 44u8, 73u8, 51u8, 50u8, 44u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8,
+        73u8, 51u8, 50u8, 41u8, 59u8, 119u8, 114u8, 105u8, 116u8, 101u8, 56u8, 40u8, 73u8, 51u8,
+        50u8, 44u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 41u8, 125u8, 0u8,
+    ];
+    fn alloc<T>(m: &mut ::std::collections::BTreeMap<u32, T>, x: T) -> u32 {
+        let mut u = 0;
+        while m.contains_key(&u) {
+            u += 1;
+        }
+        m.insert(u, x);
+        return u;
+    }
+    #[derive(Default)]
+    struct TableCell {
+        all: std::cell::UnsafeCell<
+            ::std::collections::BTreeMap<
+                u32,
+                Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>,
+            >,
+        >,
+    }
+    unsafe impl Send for TableCell {}
+    unsafe impl Sync for TableCell {}
+    static TABLE: ::std::sync::LazyLock<TableCell> =
+        ::std::sync::LazyLock::new(|| TableCell::default());
+    impl R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606
+        for ::tpit_rt::Tpit<
+            Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>,
+        >
+    {
+        fn read(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606"
+            )]
+            extern "C" {
+                #[link_name = "read"]
+                fn go(this: u32, p0: u32, p1: u32, p2: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0, p1, p2) };
+        }
+        fn read8(&mut self, p0: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606"
+            )]
+            extern "C" {
+                #[link_name = "read8"]
+                fn go(this: u32, p0: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn size(&mut self) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606"
+            )]
+            extern "C" {
+                #[link_name = "size"]
+                fn go(this: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr()) };
+        }
+        fn write(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606"
+            )]
+            extern "C" {
+                #[link_name = "write"]
+                fn go(this: u32, p0: u32, p1: u32, p2: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0, p1, p2) };
+        }
+        fn write8(&mut self, p0: u32, p1: u32) -> () {
+            #[link(
+                wasm_import_module = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606"
+            )]
+            extern "C" {
+                #[link_name = "write8"]
+                fn go(this: u32, p0: u32, p1: u32) -> ();
+            }
+            return unsafe { go(self.ptr(), p0, p1) };
+        }
+    }
+    #[export_name = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606/~d85c68f7e6bfb8ba4bd2d343816fae550294180eb3d2dfe61e0bde0cf27217ef.drop"]
+    extern "C" fn _drop(a: u32) {
+        unsafe { (&mut *(TABLE.all.get())).remove(&a) };
+    }
+    #[export_name = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606/~d85c68f7e6bfb8ba4bd2d343816fae550294180eb3d2dfe61e0bde0cf27217ef/read"]
+    extern "C" fn read(id: u32, p0: u32, p1: u32, p2: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read(p0, p1, p2);
+    }
+    #[export_name = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606/~d85c68f7e6bfb8ba4bd2d343816fae550294180eb3d2dfe61e0bde0cf27217ef/read8"]
+    extern "C" fn read8(id: u32, p0: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read8(p0);
+    }
+    #[export_name = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606/~d85c68f7e6bfb8ba4bd2d343816fae550294180eb3d2dfe61e0bde0cf27217ef/size"]
+    extern "C" fn size(id: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .size();
+    }
+    #[export_name = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606/~d85c68f7e6bfb8ba4bd2d343816fae550294180eb3d2dfe61e0bde0cf27217ef/write"]
+    extern "C" fn write(id: u32, p0: u32, p1: u32, p2: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write(p0, p1, p2);
+    }
+    #[export_name = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606/~d85c68f7e6bfb8ba4bd2d343816fae550294180eb3d2dfe61e0bde0cf27217ef/write8"]
+    extern "C" fn write8(id: u32, p0: u32, p1: u32) -> () {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write8(p0, p1);
+    }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batch2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batch2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606 {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606
+        for Batch2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606
+    {
+        fn read(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+            self.flush();
+            return R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606::read(
+                &mut self.inner,
+                p0,
+                p1,
+                p2,
+            );
+        }
+        fn read8(&mut self, p0: u32) -> (u32) {
+            self.flush();
+            return R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606::read8(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn size(&mut self) -> (u32) {
+            self.flush();
+            return R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606::size(
+                &mut self.inner,
+            );
+        }
+        fn write(&mut self, p0: u32, p1: u32, p2: u32) -> (u32) {
+            self.flush();
+            return R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606::write(
+                &mut self.inner,
+                p0,
+                p1,
+                p2,
+            );
+        }
+        fn write8(&mut self, p0: u32, p1: u32) -> () {
+            self.queue.extend_from_slice(&4u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            self.queue.extend_from_slice(&p1.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+    }
+    impl From<Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>>
+        for ::tpit_rt::Tpit<
+            Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>,
+        >
+    {
+        fn from(
+            a: Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>,
+        ) -> Self {
+            #[link(
+                wasm_import_module = "pit/2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606"
+            )]
+            extern "C" {
+                #[link_name = "~d85c68f7e6bfb8ba4bd2d343816fae550294180eb3d2dfe61e0bde0cf27217ef"]
+                fn _push(
+                    a: u32,
+                ) -> ::tpit_rt::Tpit<
+                    Box<dyn R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606>,
+                >;
+            }
+            return unsafe { _push(alloc(&mut *(TABLE.all.get()), a)) };
+        }
+    }
+};
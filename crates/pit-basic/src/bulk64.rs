@@ -0,0 +1,240 @@
+//! # 64-bit Bulk Buffer Module
+//!
+//! Provides the 64-bit bulk buffer interface and implementations.
+//!
+//! This module contains:
+//! - The `R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd` trait (64-bit bulk buffer)
+//! - Implementations on [`Shared`] for common Rust types (`Vec<u8>`, `Box<[u8]>`,
+//!   slices) and byte-only buffers
+//! - [`Bytewise`], which adapts a byte-only buffer for [`Shared`]
+//! - [`Bytes`], which serves the byte interface from a bulk buffer
+//! - Helper functions for block copies
+//!
+//! ## Buffer Interface
+//!
+//! The buffer interface is defined in `bulk64.pit`:
+//! ```text
+//! {
+//!     read(I64, I32, I32) -> (I32);
+//!     read8(I64) -> (I32);
+//!     size() -> (I64);
+//!     write(I64, I32, I32) -> (I32);
+//!     write8(I64, I32) -> ()
+//! }
+//! ```
+//!
+//! Offsets are 64-bit, but each block transfer is limited to the caller's 32-bit
+//! memory. See the [`bulk`](crate::bulk) module for how that memory is accessed, and
+//! why the implementations are only provided on [`Shared`].
+
+pub mod ffi;
+use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d;
+pub use crate::bulk::Shared;
+use crate::bulk::{clamp, mem};
+pub use ffi::*;
+use std::sync::Arc;
+
+macro_rules! bulk_slice_impl {
+    ($t:ty) => {
+        impl R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd for Shared<$t> {
+            fn read(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+                let n = clamp(p0, p2, self.wrapped.len() as u64);
+                if n == 0 {
+                    return 0;
+                }
+                let b = p0 as usize;
+                unsafe { mem(p1, n) }.copy_from_slice(&self.wrapped[b..b + n as usize]);
+                return n;
+            }
+            fn read8(&mut self, p0: u64) -> (u32) {
+                return usize::try_from(p0)
+                    .ok()
+                    .and_then(|a| self.wrapped.get(a))
+                    .map_or(0, |a| (*a).into());
+            }
+            fn size(&mut self) -> (u64) {
+                return self.wrapped.len() as u64;
+            }
+            fn write(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+                let n = clamp(p0, p2, self.wrapped.len() as u64);
+                if n == 0 {
+                    return 0;
+                }
+                let b = p0 as usize;
+                self.wrapped[b..b + n as usize].copy_from_slice(unsafe { mem(p1, n) });
+                return n;
+            }
+            fn write8(&mut self, p0: u64, p1: u32) -> () {
+                if let Some(a) = usize::try_from(p0)
+                    .ok()
+                    .and_then(|a| self.wrapped.get_mut(a))
+                {
+                    *a = (p1 & 0xff) as u8;
+                }
+            }
+        }
+    };
+}
+bulk_slice_impl!(Vec<u8>);
+bulk_slice_impl!(Box<[u8]>);
+bulk_slice_impl!(&'static mut [u8]);
+macro_rules! bulk_ro_slice_impl {
+    ($t:ty) => {
+        impl R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd for Shared<$t> {
+            fn read(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+                let n = clamp(p0, p2, self.wrapped.len() as u64);
+                if n == 0 {
+                    return 0;
+                }
+                let b = p0 as usize;
+                unsafe { mem(p1, n) }.copy_from_slice(&self.wrapped[b..b + n as usize]);
+                return n;
+            }
+            fn read8(&mut self, p0: u64) -> (u32) {
+                return usize::try_from(p0)
+                    .ok()
+                    .and_then(|a| self.wrapped.get(a))
+                    .map_or(0, |a| (*a).into());
+            }
+            fn size(&mut self) -> (u64) {
+                return self.wrapped.len() as u64;
+            }
+            fn write(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+                return 0;
+            }
+            fn write8(&mut self, p0: u64, p1: u32) -> () {}
+        }
+    };
+}
+bulk_ro_slice_impl!(Arc<[u8]>);
+bulk_ro_slice_impl!(&'static [u8]);
+
+/// Adapter serving the bulk interface from a byte-only buffer, as `Shared<Bytewise<T>>`.
+///
+/// Block transfers fall back to one `read8` or `write8` call per byte, so a peer
+/// implementing only `buffer64.pit` can be used wherever a bulk buffer is expected.
+pub struct Bytewise<T> {
+    /// The wrapped buffer.
+    pub wrapped: T,
+}
+impl<T: R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>
+    R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd for Shared<Bytewise<T>>
+{
+    fn read(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+        let n = clamp(p0, p2, self.wrapped.wrapped.size());
+        for (i, c) in unsafe { mem(p1, n) }.iter_mut().enumerate() {
+            *c = (self.wrapped.wrapped.read8(p0 + i as u64) & 0xff) as u8;
+        }
+        return n;
+    }
+    fn read8(&mut self, p0: u64) -> (u32) {
+        self.wrapped.wrapped.read8(p0)
+    }
+    fn size(&mut self) -> (u64) {
+        self.wrapped.wrapped.size()
+    }
+    fn write(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+        let n = clamp(p0, p2, self.wrapped.wrapped.size());
+        for (i, c) in unsafe { mem(p1, n) }.iter().enumerate() {
+            self.wrapped.wrapped.write8(p0 + i as u64, *c as u32);
+        }
+        return n;
+    }
+    fn write8(&mut self, p0: u64, p1: u32) -> () {
+        self.wrapped.wrapped.write8(p0, p1)
+    }
+}
+
+/// Adapter serving the byte interface from a bulk buffer.
+pub struct Bytes<T> {
+    /// The wrapped buffer.
+    pub wrapped: T,
+}
+impl<T: R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>
+    R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d for Bytes<T>
+{
+    fn read8(&mut self, p0: u64) -> (u32) {
+        self.wrapped.read8(p0)
+    }
+    fn size(&mut self) -> (u64) {
+        self.wrapped.size()
+    }
+    fn write8(&mut self, p0: u64, p1: u32) -> () {
+        self.wrapped.write8(p0, p1)
+    }
+}
+
+/// Copies bytes from a Rust slice into a bulk buffer in one call.
+///
+/// The buffer is given a pointer into this module's memory, so this is only available
+/// where pointers are 32 bits wide, as on wasm32.
+///
+/// # Arguments
+///
+/// * `a` - Destination buffer
+/// * `ai` - Starting offset in destination
+/// * `b` - Source slice
+///
+/// # Returns
+///
+/// The number of bytes copied.
+#[cfg(target_pointer_width = "32")]
+pub fn copy_slice_in<'a, 'b>(
+    a: &'a mut dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd,
+    ai: u64,
+    b: &'b [u8],
+) -> u32 {
+    a.write(ai, b.as_ptr() as usize as u32, b.len() as u32)
+}
+
+/// Copies bytes from a bulk buffer into a Rust slice in one call.
+///
+/// Like [`copy_slice_in`], this is only available where pointers are 32 bits wide.
+///
+/// # Arguments
+///
+/// * `c` - Destination slice
+/// * `b` - Source buffer
+/// * `bi` - Starting offset in source
+///
+/// # Returns
+///
+/// The number of bytes copied.
+#[cfg(target_pointer_width = "32")]
+pub fn copy_slice_out<'a, 'b>(
+    c: &'a mut [u8],
+    b: &'b mut dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd,
+    bi: u64,
+) -> u32 {
+    b.read(bi, c.as_mut_ptr() as usize as u32, c.len() as u32)
+}
+
+/// Copies bytes from one bulk buffer to another through a fixed-size staging block.
+///
+/// Like [`copy_slice_in`], this is only available where pointers are 32 bits wide.
+///
+/// # Arguments
+///
+/// * `a` - Destination buffer
+/// * `ai` - Starting offset in destination
+/// * `b` - Source buffer
+/// * `bi` - Starting offset in source
+#[cfg(target_pointer_width = "32")]
+pub fn copy<'a, 'b>(
+    a: &'a mut dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd,
+    ai: u64,
+    b: &'b mut dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd,
+    bi: u64,
+) {
+    let mut block = [0u8; 4096];
+    let l = a.size().saturating_sub(ai).min(b.size().saturating_sub(bi));
+    let mut i = 0;
+    while i < l {
+        let n = copy_slice_out(&mut block[..(l - i).min(4096) as usize], b, bi + i);
+        if n == 0 {
+            return;
+        }
+        copy_slice_in(a, ai + i, &block[..n as usize]);
+        i += n as u64;
+    }
+}
@@ -0,0 +1,273 @@
+//! # 64-bit Bulk Buffer FFI
+//!
+//! This module contains the auto-generated FFI bindings for the 64-bit bulk buffer interface.
+//!
+//! The resource ID `8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd`
+//! corresponds to the `bulk64.pit` interface definition:
+//!
+//! ```text
+//! {
+//!     read(I64, I32, I32) -> (I32);
+//!     read8(I64) -> (I32);
+//!     size() -> (I64);
+//!     write(I64, I32, I32) -> (I32);
+//!     write8(I64, I32) -> ()
+//! }
+//! ```
+
+/// A 64-bit addressable byte buffer interface with block transfers.
+///
+/// A superset of the 64-bit byte buffer interface, adding block reads and writes
+/// between the buffer and the caller's linear memory.
+///
+/// # Methods
+///
+/// - `read(offset, ptr, len)` - Copy up to `len` bytes at `offset` to `ptr`, returning the count
+/// - `read8(offset)` - Read a single byte at the given offset
+/// - `size()` - Get the total size of the buffer in bytes
+/// - `write(offset, ptr, len)` - Copy up to `len` bytes at `ptr` to `offset`, returning the count
+/// - `write8(offset, value)` - Write a single byte at the given offset
+pub trait R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd {
+    fn read(&mut self, p0: u64, p1: u32, p2: u32) -> (u32);
+    fn read8(&mut self, p0: u64) -> (u32);
+    fn size(&mut self) -> (u64);
+    fn write(&mut self, p0: u64, p1: u32, p2: u32) -> (u32);
+    fn write8(&mut self, p0: u64, p1: u32) -> ();
+}
+pub struct Batch8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>>,
+    queue: Vec<u8>,
+}
+const _: () = {
+    #[link_section = ".pit-types"]
+    static SECTION_CONTENT: [u8; 115usize] = [
+        123u8, 114u8, 101u8, 97u8, 100u8, 40u8, 73u8, 54u8, 52u8, 44u8, 73u8, 51u8, 50u8, 44u8,
+        73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 114u8,
+        101u8, 97u8, 100u8, 56u8, 40u8, 73u8, 54u8, 52u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 73u8,
+        51u8, 50u8, 41u8, 59u8, 115u8, 105u8, 122u8, 101u8, 40u8, 41u8, 32u8, 45u8, 62u8, 32u8,
+        40u8, 73u8, 54u8, 52u8, 41u8, 59u8, 119u8, 114u8, 105u8, 116u8, 101u8, 40u8, 73u8, 54u8,
+        52u8, 44u8, 73u8, 51u8, 50u8, 44u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8,
+        73u8, 51u8, 50u8, 41u8, 59u8, 119u8, 114u8, 105u8, 116u8, 101u8, 56u8, 40u8, 73u8, 54u8,
+        52u8, 44u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 41u8, 125u8, 0u8,
+    ];
+    fn alloc<T>(m: &mut ::std::collections::BTreeMap<u32, T>, x: T) -> u32 {
+        let mut u = 0;
+        while m.contains_key(&u) {
+            u += 1;
+        }
+        m.insert(u, x);
+        return u;
+    }
+    #[derive(Default)]
+    struct TableCell {
+        all: std::cell::UnsafeCell<
+            ::std::collections::BTreeMap<
+                u32,
+                Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>,
+            >,
+        >,
+    }
+    unsafe impl Send for TableCell {}
+    unsafe impl Sync for TableCell {}
+    static TABLE: ::std::sync::LazyLock<TableCell> =
+        ::std::sync::LazyLock::new(|| TableCell::default());
+    impl R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd
+        for ::tpit_rt::Tpit<
+            Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>,
+        >
+    {
+        fn read(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd"
+            )]
+            extern "C" {
+                #[link_name = "read"]
+                fn go(this: u32, p0: u64, p1: u32, p2: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0, p1, p2) };
+        }
+        fn read8(&mut self, p0: u64) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd"
+            )]
+            extern "C" {
+                #[link_name = "read8"]
+                fn go(this: u32, p0: u64) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn size(&mut self) -> (u64) {
+            #[link(
+                wasm_import_module = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd"
+            )]
+            extern "C" {
+                #[link_name = "size"]
+                fn go(this: u32) -> (u64);
+            }
+            return unsafe { go(self.ptr()) };
+        }
+        fn write(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd"
+            )]
+            extern "C" {
+                #[link_name = "write"]
+                fn go(this: u32, p0: u64, p1: u32, p2: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0, p1, p2) };
+        }
+        fn write8(&mut self, p0: u64, p1: u32) -> () {
+            #[link(
+                wasm_import_module = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd"
+            )]
+            extern "C" {
+                #[link_name = "write8"]
+                fn go(this: u32, p0: u64, p1: u32) -> ();
+            }
+            return unsafe { go(self.ptr(), p0, p1) };
+        }
+    }
+    #[export_name = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd/~36cbfb76d2b3adb3baf21fc35a66b64cc55df362e32ef716124dfc846b56b70b.drop"]
+    extern "C" fn _drop(a: u32) {
+        unsafe { (&mut *(TABLE.all.get())).remove(&a) };
+    }
+    #[export_name = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd/~36cbfb76d2b3adb3baf21fc35a66b64cc55df362e32ef716124dfc846b56b70b/read"]
+    extern "C" fn read(id: u32, p0: u64, p1: u32, p2: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read(p0, p1, p2);
+    }
+    #[export_name = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd/~36cbfb76d2b3adb3baf21fc35a66b64cc55df362e32ef716124dfc846b56b70b/read8"]
+    extern "C" fn read8(id: u32, p0: u64) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read8(p0);
+    }
+    #[export_name = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd/~36cbfb76d2b3adb3baf21fc35a66b64cc55df362e32ef716124dfc846b56b70b/size"]
+    extern "C" fn size(id: u32) -> (u64) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .size();
+    }
+    #[export_name = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd/~36cbfb76d2b3adb3baf21fc35a66b64cc55df362e32ef716124dfc846b56b70b/write"]
+    extern "C" fn write(id: u32, p0: u64, p1: u32, p2: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write(p0, p1, p2);
+    }
+    #[export_name = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd/~36cbfb76d2b3adb3baf21fc35a66b64cc55df362e32ef716124dfc846b56b70b/write8"]
+    extern "C" fn write8(id: u32, p0: u64, p1: u32) -> () {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write8(p0, p1);
+    }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batch8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batch8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd
+        for Batch8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd
+    {
+        fn read(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+            self.flush();
+            return R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd::read(
+                &mut self.inner,
+                p0,
+                p1,
+                p2,
+            );
+        }
+        fn read8(&mut self, p0: u64) -> (u32) {
+            self.flush();
+            return R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd::read8(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn size(&mut self) -> (u64) {
+            self.flush();
+            return R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd::size(
+                &mut self.inner,
+            );
+        }
+        fn write(&mut self, p0: u64, p1: u32, p2: u32) -> (u32) {
+            self.flush();
+            return R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd::write(
+                &mut self.inner,
+                p0,
+                p1,
+                p2,
+            );
+        }
+        fn write8(&mut self, p0: u64, p1: u32) -> () {
+            self.queue.extend_from_slice(&4u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            self.queue.extend_from_slice(&p1.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+    }
+    impl From<Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>>
+        for ::tpit_rt::Tpit<
+            Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>,
+        >
+    {
+        fn from(
+            a: Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>,
+        ) -> Self {
+            #[link(
+                wasm_import_module = "pit/8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd"
+            )]
+            extern "C" {
+                #[link_name = "~36cbfb76d2b3adb3baf21fc35a66b64cc55df362e32ef716124dfc846b56b70b"]
+                fn _push(
+                    a: u32,
+                ) -> ::tpit_rt::Tpit<
+                    Box<dyn R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd>,
+                >;
+            }
+            return unsafe { _push(alloc(&mut *(TABLE.all.get()), a)) };
+        }
+    }
+};
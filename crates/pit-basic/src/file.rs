@@ -0,0 +1,178 @@
+//! # File-backed Buffers
+//!
+//! Host-side implementations of the 32-bit and 64-bit buffer interfaces over on-disk
+//! data, so a host can hand a large dataset to a guest without loading it into memory.
+//!
+//! - [`FileBuf`] implements both interfaces over a `std::fs::File` with positioned reads
+//!   and writes; every `read8` or `write8` is a system call, so prefer it for sparse
+//!   access, and a mapping for dense access
+//! - `memmap2::Mmap` and `memmap2::MmapMut` implement both interfaces over a mapping of
+//!   the file, read-only and writable respectively
+//!
+//! Files and mappings keep their size: writes past the end are ignored, and I/O errors
+//! read as 0, matching the other implementations in this crate.
+//!
+//! Guests reach any of them through the host bindings in [`crate::buffer::host`] and
+//! [`crate::buffer64::host`], which are implemented for buffers in a `Mutex`:
+//!
+//! ```ignore
+//! let file = std::fs::File::open("dataset.bin")?;
+//! let map = unsafe { memmap2::Mmap::map(&file)? };
+//! let buf: Arc<dyn B68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d<U, E> + Send + Sync> =
+//!     Arc::new(Mutex::new(map));
+//! let buf = pit_rust_host_lib::register(&mut store, buf);
+//! ```
+
+use crate::buffer::R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5;
+use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d;
+use std::fs::File;
+
+/// A file used as a buffer.
+///
+/// The size is queried once, when the buffer is created, so the file must not be
+/// resized by anything else while it is in use.
+pub struct FileBuf {
+    file: File,
+    size: u64,
+}
+impl FileBuf {
+    /// Wraps a file, querying its size.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error querying the file's metadata failed with.
+    pub fn new(file: File) -> std::io::Result<Self> {
+        let size = file.metadata()?.len();
+        Ok(Self { file, size })
+    }
+    /// Unwraps the file.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+    /// Returns the size of the buffer, saturated to 32 bits.
+    fn size32(&self) -> u32 {
+        self.size.try_into().unwrap_or(u32::MAX)
+    }
+    /// Reads the byte at `offset`, or 0 past the end or on error.
+    fn get(&self, offset: u64) -> u32 {
+        let mut a = [0u8; 1];
+        if offset >= self.size || read_at(&self.file, offset, &mut a) != 1 {
+            return 0;
+        }
+        a[0] as u32
+    }
+    /// Writes the byte at `offset`, ignoring writes past the end and errors.
+    fn set(&self, offset: u64, value: u32) {
+        if offset < self.size {
+            write_at(&self.file, offset, &[(value & 0xff) as u8]);
+        }
+    }
+}
+
+/// Reads into `buf` from `offset` with one positioned read, returning the number of
+/// bytes read, or 0 on error.
+fn read_at(f: &File, offset: u64, buf: &mut [u8]) -> u32 {
+    #[cfg(unix)]
+    let r = std::os::unix::fs::FileExt::read_at(f, buf, offset);
+    #[cfg(windows)]
+    let r = std::os::windows::fs::FileExt::seek_read(f, buf, offset);
+    #[cfg(not(any(unix, windows)))]
+    let r = {
+        use std::io::{Read, Seek};
+        let mut f = f;
+        f.seek(std::io::SeekFrom::Start(offset))
+            .and_then(|_| f.read(buf))
+    };
+    r.map_or(0, |n| n as u32)
+}
+
+/// Writes `buf` at `offset` with one positioned write, returning the number of bytes
+/// written, or 0 on error.
+fn write_at(f: &File, offset: u64, buf: &[u8]) -> u32 {
+    #[cfg(unix)]
+    let r = std::os::unix::fs::FileExt::write_at(f, buf, offset);
+    #[cfg(windows)]
+    let r = std::os::windows::fs::FileExt::seek_write(f, buf, offset);
+    #[cfg(not(any(unix, windows)))]
+    let r = {
+        use std::io::{Seek, Write};
+        let mut f = f;
+        f.seek(std::io::SeekFrom::Start(offset))
+            .and_then(|_| f.write(buf))
+    };
+    r.map_or(0, |n| n as u32)
+}
+
+impl R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5 for FileBuf {
+    fn read8(&mut self, p0: u32) -> (u32) {
+        return self.get(p0.into());
+    }
+    fn write8(&mut self, p0: u32, p1: u32) -> () {
+        self.set(p0.into(), p1)
+    }
+    fn size(&mut self) -> (u32) {
+        return self.size32();
+    }
+}
+impl R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d for FileBuf {
+    fn read8(&mut self, p0: u64) -> (u32) {
+        return self.get(p0);
+    }
+    fn write8(&mut self, p0: u64, p1: u32) -> () {
+        self.set(p0, p1)
+    }
+    fn size(&mut self) -> (u64) {
+        return self.size;
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d as Buffer64;
+
+    #[test]
+    fn keeps_its_size() {
+        let path = std::env::temp_dir().join(format!("pit-basic-file-{}", std::process::id()));
+        std::fs::write(&path, [1u8, 2]).unwrap();
+        let f = File::options().read(true).write(true).open(&path).unwrap();
+        let mut b = FileBuf::new(f).unwrap();
+        assert_eq!(Buffer64::size(&mut b), 2);
+        Buffer64::write8(&mut b, 1, 3);
+        Buffer64::write8(&mut b, 2, 4);
+        assert_eq!(Buffer64::read8(&mut b, 1), 3);
+        assert_eq!(Buffer64::read8(&mut b, u64::MAX), 0);
+        drop(b);
+        assert_eq!(std::fs::read(&path).unwrap(), [1, 3]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn serves_guests_from_the_host() {
+        use crate::buffer64::host::B68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d as Host64;
+        use pit_rust_host_lib::wasm_runtime_layer::{AsContext, AsContextMut, Engine, Store};
+        use pit_rust_host_lib::Wrapped;
+        use std::sync::{Arc, Mutex};
+        type E = wasmi_runtime_layer::Engine;
+
+        let path = std::env::temp_dir().join(format!("pit-basic-host-{}", std::process::id()));
+        std::fs::write(&path, [1u8, 2]).unwrap();
+        let f = File::options().read(true).write(true).open(&path).unwrap();
+        let mut s = Store::new(&Engine::new(E::default()), ());
+        let b: Arc<dyn Host64<(), E> + Send + Sync> =
+            Arc::new(Mutex::new(FileBuf::new(f).unwrap()));
+        let r = pit_rust_host_lib::register(&mut s, b);
+        let w = Arc::new(
+            r.downcast::<'_, '_, Wrapped<(), E>, (), E>(s.as_context())
+                .unwrap()
+                .clone(),
+        );
+        assert_eq!(w.size(s.as_context_mut()).unwrap(), 2);
+        w.write8(s.as_context_mut(), 1, 3).unwrap();
+        w.write8(s.as_context_mut(), 2, 4).unwrap();
+        assert_eq!(w.read8(s.as_context_mut(), 1).unwrap(), 3);
+        assert_eq!(w.read8(s.as_context_mut(), 2).unwrap(), 0);
+        drop(w);
+        assert_eq!(std::fs::read(&path).unwrap(), [1, 3]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+//! # 32-bit Growable Buffer Module
+//!
+//! Provides the 32-bit growable buffer interface and implementations.
+//!
+//! This module contains:
+//! - The `R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73` trait (32-bit growable buffer)
+//! - An implementation for `Vec<u8>`
+//! - [`append`], which grows a buffer to hold more data
+//!
+//! ## Buffer Interface
+//!
+//! The buffer interface is defined in `growable.pit`:
+//! ```text
+//! {
+//!     grow(I32) -> (I32);
+//!     read8(I32) -> (I32);
+//!     resize(I32) -> (I32);
+//!     size() -> (I32);
+//!     truncate(I32) -> ();
+//!     write8(I32, I32) -> ()
+//! }
+//! ```
+//!
+//! `grow` mirrors `memory.grow`: it returns the previous size, or `u32::MAX` if the
+//! buffer cannot grow. Buffers may grow in larger steps than asked for, as IC stable
+//! memory does in 64 KiB pages, and may be unable to shrink, so `resize` returns the
+//! size actually reached.
+
+pub mod ffi;
+pub use ffi::*;
+
+impl R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73 for Vec<u8> {
+    fn grow(&mut self, p0: u32) -> (u32) {
+        let old = self.len();
+        match old.checked_add(p0 as usize) {
+            Some(n) if u32::try_from(n).is_ok() && self.try_reserve(p0 as usize).is_ok() => {
+                Vec::resize(self, n, 0);
+                return old as u32;
+            }
+            _ => return u32::MAX,
+        }
+    }
+    fn read8(&mut self, p0: u32) -> (u32) {
+        return self.get(p0 as usize).map_or(0, |a| (*a).into());
+    }
+    fn resize(&mut self, p0: u32) -> (u32) {
+        let n = p0 as usize;
+        if self.try_reserve(n.saturating_sub(self.len())).is_ok() {
+            Vec::resize(self, n, 0);
+        }
+        return self.len().try_into().unwrap_or(u32::MAX);
+    }
+    fn size(&mut self) -> (u32) {
+        return self.len().try_into().unwrap_or(u32::MAX);
+    }
+    fn truncate(&mut self, p0: u32) -> () {
+        Vec::truncate(self, p0 as usize)
+    }
+    fn write8(&mut self, p0: u32, p1: u32) -> () {
+        if let Some(a) = self.get_mut(p0 as usize) {
+            *a = (p1 & 0xff) as u8;
+        }
+    }
+}
+
+/// Grows a buffer and copies bytes from a Rust slice into the new space.
+///
+/// # Arguments
+///
+/// * `a` - Destination buffer
+/// * `b` - Source slice
+///
+/// # Returns
+///
+/// The offset the bytes were copied to, or `None` if the buffer could not grow. Buffers
+/// growing in larger steps leave the rest of the new space zeroed after the copy.
+pub fn append<'a, 'b>(
+    a: &'a mut dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73,
+    b: &'b [u8],
+) -> Option<u32> {
+    let n = u32::try_from(b.len()).ok()?;
+    let at = a.grow(n);
+    if at == u32::MAX {
+        return None;
+    }
+    for (i, c) in b.iter().enumerate() {
+        a.write8(at + i as u32, *c as u32);
+    }
+    Some(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73 as Growable;
+
+    #[test]
+    fn grows_and_shrinks() {
+        let mut v = vec![1u8];
+        assert_eq!(Growable::grow(&mut v, 2), 1);
+        assert_eq!(v, [1, 0, 0]);
+        assert_eq!(Growable::resize(&mut v, 5), 5);
+        assert_eq!(Growable::resize(&mut v, 2), 2);
+        Growable::truncate(&mut v, 1);
+        Growable::truncate(&mut v, 4);
+        assert_eq!(Growable::size(&mut v), 1);
+    }
+
+    #[test]
+    fn refuses_to_grow_past_u32() {
+        let mut v = vec![1u8];
+        assert_eq!(Growable::grow(&mut v, u32::MAX), u32::MAX);
+        assert_eq!(v, [1]);
+    }
+
+    #[test]
+    fn ignores_out_of_range_bytes() {
+        let mut v = vec![1u8];
+        Growable::write8(&mut v, 1, 2);
+        assert_eq!(Growable::read8(&mut v, 1), 0);
+        assert_eq!(v, [1]);
+    }
+
+    #[test]
+    fn appends() {
+        let mut v = vec![1u8];
+        assert_eq!(append(&mut v, &[2, 3]), Some(1));
+        assert_eq!(append(&mut v, &[]), Some(3));
+        assert_eq!(v, [1, 2, 3]);
+    }
+}
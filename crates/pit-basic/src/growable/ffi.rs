@@ -0,0 +1,297 @@
+//! # 32-bit Growable Buffer FFI
+//!
+//! This module contains the auto-generated FFI bindings for the 32-bit growable buffer interface.
+//!
+//! The resource ID `47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73`
+//! corresponds to the `growable.pit` interface definition:
+//!
+//! ```text
+//! {
+//!     grow(I32) -> (I32);
+//!     read8(I32) -> (I32);
+//!     resize(I32) -> (I32);
+//!     size() -> (I32);
+//!     truncate(I32) -> ();
+//!     write8(I32, I32) -> ()
+//! }
+//! ```
+
+/// A 32-bit addressable byte buffer interface whose size can change.
+///
+/// A superset of the 32-bit byte buffer interface, letting the user of a buffer
+/// make room for data of unknown length instead of sizing the buffer up front.
+///
+/// # Methods
+///
+/// - `grow(delta)` - Enlarge the buffer by at least `delta` bytes, returning the previous size, or all ones on failure
+/// - `read8(offset)` - Read a single byte at the given offset, or 0 past the end
+/// - `resize(size)` - Set the size of the buffer as close to `size` as supported, returning the new size
+/// - `size()` - Get the total size of the buffer in bytes
+/// - `truncate(size)` - Shrink the buffer to at most `size` bytes, if supported
+/// - `write8(offset, value)` - Write a single byte at the given offset, ignored past the end
+pub trait R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73 {
+    fn grow(&mut self, p0: u32) -> (u32);
+    fn read8(&mut self, p0: u32) -> (u32);
+    fn resize(&mut self, p0: u32) -> (u32);
+    fn size(&mut self) -> (u32);
+    fn truncate(&mut self, p0: u32) -> ();
+    fn write8(&mut self, p0: u32, p1: u32) -> ();
+}
+pub struct Batch47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73 {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>>,
+    queue: Vec<u8>,
+}
+const _: () = {
+    #[link_section = ".pit-types"]
+    static SECTION_CONTENT: [u8; 120usize] = [
+        123u8, 103u8, 114u8, 111u8, 119u8, 40u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8,
+        40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 114u8, 101u8, 97u8, 100u8, 56u8, 40u8, 73u8, 51u8,
+        50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 114u8, 101u8,
+        115u8, 105u8, 122u8, 101u8, 40u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8,
+        73u8, 51u8, 50u8, 41u8, 59u8, 115u8, 105u8, 122u8, 101u8, 40u8, 41u8, 32u8, 45u8, 62u8,
+        32u8, 40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 116u8, 114u8, 117u8, 110u8, 99u8, 97u8, 116u8,
+        101u8, 40u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 41u8, 59u8, 119u8,
+        114u8, 105u8, 116u8, 101u8, 56u8, 40u8, 73u8, 51u8, 50u8, 44u8, 73u8, 51u8, 50u8, 41u8,
+        32u8, 45u8, 62u8, 32u8, 40u8, 41u8, 125u8, 0u8,
+    ];
+    fn alloc<T>(m: &mut ::std::collections::BTreeMap<u32, T>, x: T) -> u32 {
+        let mut u = 0;
+        while m.contains_key(&u) {
+            u += 1;
+        }
+        m.insert(u, x);
+        return u;
+    }
+    #[derive(Default)]
+    struct TableCell {
+        all: std::cell::UnsafeCell<
+            ::std::collections::BTreeMap<
+                u32,
+                Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>,
+            >,
+        >,
+    }
+    unsafe impl Send for TableCell {}
+    unsafe impl Sync for TableCell {}
+    static TABLE: ::std::sync::LazyLock<TableCell> =
+        ::std::sync::LazyLock::new(|| TableCell::default());
+    impl R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73
+        for ::tpit_rt::Tpit<
+            Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>,
+        >
+    {
+        fn grow(&mut self, p0: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "grow"]
+                fn go(this: u32, p0: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn read8(&mut self, p0: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "read8"]
+                fn go(this: u32, p0: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn resize(&mut self, p0: u32) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "resize"]
+                fn go(this: u32, p0: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn size(&mut self) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "size"]
+                fn go(this: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr()) };
+        }
+        fn truncate(&mut self, p0: u32) -> () {
+            #[link(
+                wasm_import_module = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "truncate"]
+                fn go(this: u32, p0: u32) -> ();
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn write8(&mut self, p0: u32, p1: u32) -> () {
+            #[link(
+                wasm_import_module = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "write8"]
+                fn go(this: u32, p0: u32, p1: u32) -> ();
+            }
+            return unsafe { go(self.ptr(), p0, p1) };
+        }
+    }
+    #[export_name = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73/~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9.drop"]
+    extern "C" fn _drop(a: u32) {
+        unsafe { (&mut *(TABLE.all.get())).remove(&a) };
+    }
+    #[export_name = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73/~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9/grow"]
+    extern "C" fn grow(id: u32, p0: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .grow(p0);
+    }
+    #[export_name = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73/~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9/read8"]
+    extern "C" fn read8(id: u32, p0: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read8(p0);
+    }
+    #[export_name = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73/~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9/resize"]
+    extern "C" fn resize(id: u32, p0: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .resize(p0);
+    }
+    #[export_name = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73/~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9/size"]
+    extern "C" fn size(id: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .size();
+    }
+    #[export_name = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73/~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9/truncate"]
+    extern "C" fn truncate(id: u32, p0: u32) -> () {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .truncate(p0);
+    }
+    #[export_name = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73/~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9/write8"]
+    extern "C" fn write8(id: u32, p0: u32, p1: u32) -> () {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write8(p0, p1);
+    }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batch47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73 {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batch47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73 {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73
+        for Batch47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73
+    {
+        fn grow(&mut self, p0: u32) -> (u32) {
+            self.flush();
+            return R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73::grow(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn read8(&mut self, p0: u32) -> (u32) {
+            self.flush();
+            return R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73::read8(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn resize(&mut self, p0: u32) -> (u32) {
+            self.flush();
+            return R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73::resize(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn size(&mut self) -> (u32) {
+            self.flush();
+            return R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73::size(
+                &mut self.inner,
+            );
+        }
+        fn truncate(&mut self, p0: u32) -> () {
+            self.queue.extend_from_slice(&4u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+        fn write8(&mut self, p0: u32, p1: u32) -> () {
+            self.queue.extend_from_slice(&5u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            self.queue.extend_from_slice(&p1.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+    }
+    impl From<Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>>
+        for ::tpit_rt::Tpit<
+            Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>,
+        >
+    {
+        fn from(
+            a: Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>,
+        ) -> Self {
+            #[link(
+                wasm_import_module = "pit/47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73"
+            )]
+            extern "C" {
+                #[link_name = "~0edcdc7ad4a5768d39078ef88c1892ab316bd7c5621399c837fac8c619e955f9"]
+                fn _push(
+                    a: u32,
+                ) -> ::tpit_rt::Tpit<
+                    Box<dyn R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73>,
+                >;
+            }
+            return unsafe { _push(alloc(&mut *(TABLE.all.get()), a)) };
+        }
+    }
+};
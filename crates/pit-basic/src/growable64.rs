@@ -0,0 +1,134 @@
+//! # 64-bit Growable Buffer Module
+//!
+//! Provides the 64-bit growable buffer interface and implementations.
+//!
+//! This module contains:
+//! - The `Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a` trait (64-bit growable buffer)
+//! - An implementation for `Vec<u8>`
+//! - [`append`], which grows a buffer to hold more data
+//!
+//! ## Buffer Interface
+//!
+//! The buffer interface is defined in `growable64.pit`:
+//! ```text
+//! {
+//!     grow(I64) -> (I64);
+//!     read8(I64) -> (I32);
+//!     resize(I64) -> (I64);
+//!     size() -> (I64);
+//!     truncate(I64) -> ();
+//!     write8(I64, I32) -> ()
+//! }
+//! ```
+//!
+//! The semantics match [`crate::growable`], with `u64::MAX` returned by a failed `grow`.
+
+pub mod ffi;
+pub use ffi::*;
+
+impl Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a for Vec<u8> {
+    fn grow(&mut self, p0: u64) -> (u64) {
+        let old = self.len();
+        match usize::try_from(p0)
+            .ok()
+            .and_then(|a| Some((a, old.checked_add(a)?)))
+        {
+            Some((a, n)) if self.try_reserve(a).is_ok() => {
+                Vec::resize(self, n, 0);
+                return old as u64;
+            }
+            _ => return u64::MAX,
+        }
+    }
+    fn read8(&mut self, p0: u64) -> (u32) {
+        return usize::try_from(p0)
+            .ok()
+            .and_then(|a| self.get(a))
+            .map_or(0, |a| (*a).into());
+    }
+    fn resize(&mut self, p0: u64) -> (u64) {
+        if let Ok(n) = usize::try_from(p0) {
+            if self.try_reserve(n.saturating_sub(self.len())).is_ok() {
+                Vec::resize(self, n, 0);
+            }
+        }
+        return self.len() as u64;
+    }
+    fn size(&mut self) -> (u64) {
+        return self.len() as u64;
+    }
+    fn truncate(&mut self, p0: u64) -> () {
+        Vec::truncate(self, usize::try_from(p0).unwrap_or(usize::MAX))
+    }
+    fn write8(&mut self, p0: u64, p1: u32) -> () {
+        if let Some(a) = usize::try_from(p0).ok().and_then(|a| self.get_mut(a)) {
+            *a = (p1 & 0xff) as u8;
+        }
+    }
+}
+
+/// Grows a 64-bit buffer and copies bytes from a Rust slice into the new space.
+///
+/// # Arguments
+///
+/// * `a` - Destination buffer
+/// * `b` - Source slice
+///
+/// # Returns
+///
+/// The offset the bytes were copied to, or `None` if the buffer could not grow. Buffers
+/// growing in larger steps leave the rest of the new space zeroed after the copy.
+pub fn append<'a, 'b>(
+    a: &'a mut dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a,
+    b: &'b [u8],
+) -> Option<u64> {
+    let at = a.grow(b.len() as u64);
+    if at == u64::MAX {
+        return None;
+    }
+    for (i, c) in b.iter().enumerate() {
+        a.write8(at + i as u64, *c as u32);
+    }
+    Some(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a as Growable;
+
+    #[test]
+    fn grows_and_shrinks() {
+        let mut v = vec![1u8];
+        assert_eq!(Growable::grow(&mut v, 2), 1);
+        assert_eq!(v, [1, 0, 0]);
+        assert_eq!(Growable::resize(&mut v, 5), 5);
+        assert_eq!(Growable::resize(&mut v, 2), 2);
+        Growable::truncate(&mut v, 1);
+        Growable::truncate(&mut v, u64::MAX);
+        assert_eq!(Growable::size(&mut v), 1);
+    }
+
+    #[test]
+    fn fails_without_changing_size() {
+        let mut v = vec![1u8];
+        assert_eq!(Growable::grow(&mut v, u64::MAX), u64::MAX);
+        assert_eq!(Growable::resize(&mut v, u64::MAX), 1);
+        assert_eq!(v, [1]);
+    }
+
+    #[test]
+    fn ignores_out_of_range_bytes() {
+        let mut v = vec![1u8];
+        Growable::write8(&mut v, u64::MAX, 2);
+        assert_eq!(Growable::read8(&mut v, u64::MAX), 0);
+        assert_eq!(v, [1]);
+    }
+
+    #[test]
+    fn appends() {
+        let mut v = vec![1u8];
+        assert_eq!(append(&mut v, &[2, 3]), Some(1));
+        assert_eq!(v, [1, 2, 3]);
+    }
+}
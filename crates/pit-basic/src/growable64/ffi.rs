@@ -0,0 +1,297 @@
+//! # 64-bit Growable Buffer FFI
+//!
+//! This module contains the auto-generated FFI bindings for the 64-bit growable buffer interface.
+//!
+//! The resource ID `a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a`
+//! corresponds to the `growable64.pit` interface definition:
+//!
+//! ```text
+//! {
+//!     grow(I64) -> (I64);
+//!     read8(I64) -> (I32);
+//!     resize(I64) -> (I64);
+//!     size() -> (I64);
+//!     truncate(I64) -> ();
+//!     write8(I64, I32) -> ()
+//! }
+//! ```
+
+/// A 64-bit addressable byte buffer interface whose size can change.
+///
+/// A superset of the 64-bit byte buffer interface, letting the user of a buffer
+/// make room for data of unknown length instead of sizing the buffer up front.
+///
+/// # Methods
+///
+/// - `grow(delta)` - Enlarge the buffer by at least `delta` bytes, returning the previous size, or all ones on failure
+/// - `read8(offset)` - Read a single byte at the given offset, or 0 past the end
+/// - `resize(size)` - Set the size of the buffer as close to `size` as supported, returning the new size
+/// - `size()` - Get the total size of the buffer in bytes
+/// - `truncate(size)` - Shrink the buffer to at most `size` bytes, if supported
+/// - `write8(offset, value)` - Write a single byte at the given offset, ignored past the end
+pub trait Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a {
+    fn grow(&mut self, p0: u64) -> (u64);
+    fn read8(&mut self, p0: u64) -> (u32);
+    fn resize(&mut self, p0: u64) -> (u64);
+    fn size(&mut self) -> (u64);
+    fn truncate(&mut self, p0: u64) -> ();
+    fn write8(&mut self, p0: u64, p1: u32) -> ();
+}
+pub struct Batcha983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>>,
+    queue: Vec<u8>,
+}
+const _: () = {
+    #[link_section = ".pit-types"]
+    static SECTION_CONTENT: [u8; 120usize] = [
+        123u8, 103u8, 114u8, 111u8, 119u8, 40u8, 73u8, 54u8, 52u8, 41u8, 32u8, 45u8, 62u8, 32u8,
+        40u8, 73u8, 54u8, 52u8, 41u8, 59u8, 114u8, 101u8, 97u8, 100u8, 56u8, 40u8, 73u8, 54u8,
+        52u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 114u8, 101u8,
+        115u8, 105u8, 122u8, 101u8, 40u8, 73u8, 54u8, 52u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8,
+        73u8, 54u8, 52u8, 41u8, 59u8, 115u8, 105u8, 122u8, 101u8, 40u8, 41u8, 32u8, 45u8, 62u8,
+        32u8, 40u8, 73u8, 54u8, 52u8, 41u8, 59u8, 116u8, 114u8, 117u8, 110u8, 99u8, 97u8, 116u8,
+        101u8, 40u8, 73u8, 54u8, 52u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 41u8, 59u8, 119u8,
+        114u8, 105u8, 116u8, 101u8, 56u8, 40u8, 73u8, 54u8, 52u8, 44u8, 73u8, 51u8, 50u8, 41u8,
+        32u8, 45u8, 62u8, 32u8, 40u8, 41u8, 125u8, 0u8,
+    ];
+    fn alloc<T>(m: &mut ::std::collections::BTreeMap<u32, T>, x: T) -> u32 {
+        let mut u = 0;
+        while m.contains_key(&u) {
+            u += 1;
+        }
+        m.insert(u, x);
+        return u;
+    }
+    #[derive(Default)]
+    struct TableCell {
+        all: std::cell::UnsafeCell<
+            ::std::collections::BTreeMap<
+                u32,
+                Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>,
+            >,
+        >,
+    }
+    unsafe impl Send for TableCell {}
+    unsafe impl Sync for TableCell {}
+    static TABLE: ::std::sync::LazyLock<TableCell> =
+        ::std::sync::LazyLock::new(|| TableCell::default());
+    impl Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a
+        for ::tpit_rt::Tpit<
+            Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>,
+        >
+    {
+        fn grow(&mut self, p0: u64) -> (u64) {
+            #[link(
+                wasm_import_module = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "grow"]
+                fn go(this: u32, p0: u64) -> (u64);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn read8(&mut self, p0: u64) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "read8"]
+                fn go(this: u32, p0: u64) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn resize(&mut self, p0: u64) -> (u64) {
+            #[link(
+                wasm_import_module = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "resize"]
+                fn go(this: u32, p0: u64) -> (u64);
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn size(&mut self) -> (u64) {
+            #[link(
+                wasm_import_module = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "size"]
+                fn go(this: u32) -> (u64);
+            }
+            return unsafe { go(self.ptr()) };
+        }
+        fn truncate(&mut self, p0: u64) -> () {
+            #[link(
+                wasm_import_module = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "truncate"]
+                fn go(this: u32, p0: u64) -> ();
+            }
+            return unsafe { go(self.ptr(), p0) };
+        }
+        fn write8(&mut self, p0: u64, p1: u32) -> () {
+            #[link(
+                wasm_import_module = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "write8"]
+                fn go(this: u32, p0: u64, p1: u32) -> ();
+            }
+            return unsafe { go(self.ptr(), p0, p1) };
+        }
+    }
+    #[export_name = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a/~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7.drop"]
+    extern "C" fn _drop(a: u32) {
+        unsafe { (&mut *(TABLE.all.get())).remove(&a) };
+    }
+    #[export_name = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a/~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7/grow"]
+    extern "C" fn grow(id: u32, p0: u64) -> (u64) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .grow(p0);
+    }
+    #[export_name = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a/~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7/read8"]
+    extern "C" fn read8(id: u32, p0: u64) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read8(p0);
+    }
+    #[export_name = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a/~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7/resize"]
+    extern "C" fn resize(id: u32, p0: u64) -> (u64) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .resize(p0);
+    }
+    #[export_name = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a/~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7/size"]
+    extern "C" fn size(id: u32) -> (u64) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .size();
+    }
+    #[export_name = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a/~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7/truncate"]
+    extern "C" fn truncate(id: u32, p0: u64) -> () {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .truncate(p0);
+    }
+    #[export_name = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a/~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7/write8"]
+    extern "C" fn write8(id: u32, p0: u64, p1: u32) -> () {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write8(p0, p1);
+    }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batcha983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batcha983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a
+        for Batcha983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a
+    {
+        fn grow(&mut self, p0: u64) -> (u64) {
+            self.flush();
+            return Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a::grow(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn read8(&mut self, p0: u64) -> (u32) {
+            self.flush();
+            return Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a::read8(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn resize(&mut self, p0: u64) -> (u64) {
+            self.flush();
+            return Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a::resize(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn size(&mut self) -> (u64) {
+            self.flush();
+            return Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a::size(
+                &mut self.inner,
+            );
+        }
+        fn truncate(&mut self, p0: u64) -> () {
+            self.queue.extend_from_slice(&4u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+        fn write8(&mut self, p0: u64, p1: u32) -> () {
+            self.queue.extend_from_slice(&5u32.to_le_bytes());
+            self.queue.extend_from_slice(&p0.to_le_bytes());
+            self.queue.extend_from_slice(&p1.to_le_bytes());
+            if self.queue.len() >= BATCH_LIMIT {
+                self.flush();
+            }
+        }
+    }
+    impl From<Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>>
+        for ::tpit_rt::Tpit<
+            Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>,
+        >
+    {
+        fn from(
+            a: Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>,
+        ) -> Self {
+            #[link(
+                wasm_import_module = "pit/a983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a"
+            )]
+            extern "C" {
+                #[link_name = "~05ddf409a256b40b69f82ac8f49c42a07c4a50f180cea1d5513cb14723c98ee7"]
+                fn _push(
+                    a: u32,
+                ) -> ::tpit_rt::Tpit<
+                    Box<dyn Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a>,
+                >;
+            }
+            return unsafe { _push(alloc(&mut *(TABLE.all.get()), a)) };
+        }
+    }
+};
@@ -10,6 +10,12 @@
 //!
 //! - [`buffer`] - 32-bit addressable buffer interface implementations
 //! - [`buffer64`] - 64-bit addressable buffer interface implementations
+//! - [`bulk`] - 32-bit buffer interface with block transfers
+//! - [`bulk64`] - 64-bit buffer interface with block transfers
+//! - [`growable`] - 32-bit buffer interface that can change size
+//! - [`growable64`] - 64-bit buffer interface that can change size
+//! - [`reader`] - Stream reader interface
+//! - [`writer`] - Stream writer interface
 //!
 //! ## Buffer Interface
 //!
@@ -18,9 +24,50 @@
 //! - `write8(offset, value)` - Write a byte at the given offset
 //! - `size()` - Get the buffer size
 //!
+//! The bulk interfaces (`bulk.pit` and `bulk64.pit`) add `read(offset, ptr, len)` and
+//! `write(offset, ptr, len)`, which copy whole blocks between the buffer and the caller's
+//! linear memory in one call. They are implemented on [`bulk::Shared`], which may only
+//! be handed to callers sharing the implementing module's memory; wrapping
+//! [`bulk::Bytewise`] or [`bulk64::Bytewise`] in it provides them on top of buffers that
+//! only implement the byte interface.
+//!
+//! The growable interfaces (`growable.pit` and `growable64.pit`) add `grow(delta)`,
+//! `resize(size)` and `truncate(size)`, so data of unknown length can be written without
+//! sizing the buffer up front. They are implemented for `Vec<u8>` and, with the
+//! `ic-stable-structures` feature, for IC stable memory.
+//!
+//! ## Out-of-range access
+//!
+//! Offsets come from peer modules and are not trusted. The implementations in this crate
+//! check them: reads past the end return 0, writes past the end are ignored, block
+//! transfers and copies stop at the end of the buffer, and sizes too large for the
+//! interface saturate to its maximum rather than wrapping. To refuse a 64-bit buffer
+//! that does not fit the 32-bit interface, use [`buffer::Snip::narrow`].
+//!
+//! This only covers offsets. The pointers taken by the bulk interfaces cannot be
+//! checked, which is why those are only implemented on [`bulk::Shared`].
+//!
+//! ## Standard I/O
+//!
+//! [`buffer::Cursor`] and [`buffer64::Cursor`] implement [`std::io::Read`],
+//! [`std::io::Write`] and [`std::io::Seek`] over any buffer, and [`reader::Reader`] and
+//! [`writer::Writer`] implement [`std::io::Read`] and [`std::io::Write`] over reader and
+//! writer resources, so PIT resources work with `std::io::copy` and anything else
+//! taking a reader or writer:
+//!
+//! In the other direction, [`reader::Stream`] and [`writer::Stream`] serve the reader
+//! and writer interfaces from any [`std::io::Read`] or [`std::io::Write`], such as a
+//! file or an in-memory `Vec<u8>`.
+//!
+//! ```ignore
+//! let mut out = writer::Writer { wrapped: w };
+//! std::io::copy(&mut reader::Reader { wrapped: r }, &mut out)?;
+//! ```
+//!
 //! ## Features
 //!
-//! - `host` - Enable host-side functionality
+//! - `host` - Enable host-side functionality: the file-backed buffers in [`file`], and
+//!   host bindings serving any buffer to guests, in [`buffer::host`] and [`buffer64::host`]
 //! - `host-ic` - Enable Internet Computer stable structures support
 //! - `ic-stable-structures` - Enable IC stable structures dependency
 
@@ -40,3 +87,53 @@ pub mod buffer;
 /// See [`buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d`]
 /// for the main buffer trait.
 pub mod buffer64;
+
+/// 32-bit bulk buffer module.
+///
+/// See [`bulk::R2dd1dfcc327dde48a856b9b253cfbda11901e138e75457f46eb95dacf8446606`]
+/// for the main bulk buffer trait.
+pub mod bulk;
+
+/// 64-bit bulk buffer module.
+///
+/// See [`bulk64::R8486ba6e42a5b0b5dc9333f677ee38f3c6bdfb3da28f3d725b3f2d031a60e7fd`]
+/// for the main bulk buffer trait.
+pub mod bulk64;
+
+/// 32-bit growable buffer module.
+///
+/// See [`growable::R47583569d26219990c771eb5c73bc669839b4f0c7ce72024e40b4beb1b4f8a73`]
+/// for the main growable buffer trait.
+pub mod growable;
+
+/// 64-bit growable buffer module.
+///
+/// See [`growable64::Ra983cd096914ee0a4bb140716fbf8eb3b661ac58e2938f5e2cbf1dec7d5ada3a`]
+/// for the main growable buffer trait.
+pub mod growable64;
+
+/// File and memory-mapped buffers for hosts.
+///
+/// See the module documentation for the implementations provided.
+#[cfg(feature = "host")]
+pub mod file;
+
+/// Stream reader module.
+///
+/// See [`reader::Reader`] for the [`std::io::Read`] adapter and [`reader::Stream`] for
+/// readers over Rust streams.
+pub mod reader;
+
+/// Stream writer module.
+///
+/// See [`writer::Writer`] for the [`std::io::Write`] adapter and [`writer::Stream`] for
+/// writers over Rust streams.
+pub mod writer;
+
+/// Locks a buffer shared with guests by the host bindings.
+///
+/// Buffer methods leave no state half-updated, so a poisoned lock is still used.
+#[cfg(feature = "host")]
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
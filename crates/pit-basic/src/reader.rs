@@ -0,0 +1,178 @@
+//! # Reader Module
+//!
+//! Provides the reader interface and a [`std::io::Read`] adapter over it.
+//!
+//! This module contains:
+//! - The `Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6` trait (reader)
+//! - [`Reader`], which implements [`std::io::Read`] over a reader resource
+//! - [`Stream`], which implements the reader interface over a [`std::io::Read`]
+//!
+//! ## Reader Interface
+//!
+//! The reader interface is defined in `reader.pit`:
+//! ```text
+//! {
+//!     read(I32) -> (R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5);
+//!     read64(I64) -> (R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d)
+//! }
+//! ```
+//!
+//! Each call lends a buffer holding at most the requested number of bytes from the
+//! stream, which the reader keeps alive until its next call. An empty buffer marks the
+//! end of the stream.
+//!
+//! ## Conversions
+//!
+//! - Files, sockets and in-memory data such as `&'static [u8]` or
+//!   `std::io::Cursor<Vec<u8>>` become readers with [`Stream::new`]
+//! - A buffer becomes a reader with `Stream::new(buffer::Cursor { .. })`, see
+//!   [`crate::buffer::Cursor`]
+//! - A reader is drained into a `Vec<u8>`, itself a buffer, with
+//!   [`std::io::Read::read_to_end`] on a [`Reader`]
+
+/// Generated bindings for `reader.pit`.
+pub mod ffi;
+use crate::buffer::{
+    copy_slice_out, R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5,
+};
+use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d;
+pub use ffi::*;
+use tpit_rt::{Lent, Tpit};
+
+/// Adapter implementing [`std::io::Read`] over a reader resource.
+///
+/// Every call to [`read`](std::io::Read::read) makes one `read` call for up to the
+/// length of the destination.
+pub struct Reader<T> {
+    /// The wrapped reader.
+    pub wrapped: T,
+}
+impl<T: Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6> std::io::Read
+    for Reader<T>
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut b = self.wrapped.read(buf.len().min(u32::MAX as usize) as u32);
+        let n = (b.size() as usize).min(buf.len());
+        copy_slice_out(&mut buf[..n], &mut *b, 0);
+        Ok(n)
+    }
+}
+
+/// Reader resource reading from a [`std::io::Read`].
+///
+/// Every `read` call makes one read from the wrapped stream, of at most [`CHUNK`]
+/// bytes, so the returned buffers are never larger than that. The PIT interface has
+/// no way to report errors, so an I/O error ends the stream like end of file does.
+///
+/// Buffers are lent, so the stream keeps the last one returned by each method until
+/// that method is called again.
+pub struct Stream<T> {
+    /// The wrapped stream.
+    pub wrapped: T,
+    lent: Option<Tpit<Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>>,
+    lent64:
+        Option<Tpit<Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>>>,
+}
+
+/// The largest buffer handed out by [`Stream`].
+pub const CHUNK: u64 = 65536;
+
+impl<T> Stream<T> {
+    /// Creates a reader resource over `wrapped`.
+    pub fn new(wrapped: T) -> Self {
+        Self {
+            wrapped,
+            lent: None,
+            lent64: None,
+        }
+    }
+}
+impl<T: std::io::Read> Stream<T> {
+    fn chunk(&mut self, n: u64) -> Vec<u8> {
+        let mut v = vec![0u8; n.min(CHUNK) as usize];
+        let n = loop {
+            match self.wrapped.read(&mut v) {
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                r => break r.unwrap_or(0),
+            }
+        };
+        v.truncate(n);
+        v
+    }
+}
+impl<T: std::io::Read> Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6
+    for Stream<T>
+{
+    fn read(
+        &mut self,
+        p0: u32,
+    ) -> (Lent<'_, Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>)
+    {
+        let v = self.chunk(p0.into());
+        Lent::of(self.lent.insert(Tpit::from(Box::new(v)
+            as Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>)))
+    }
+    fn read64(
+        &mut self,
+        p0: u64,
+    ) -> (Lent<'_, Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>>)
+    {
+        let v = self.chunk(p0);
+        Lent::of(self.lent64.insert(Tpit::from(Box::new(v)
+            as Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::ManuallyDrop;
+
+    /// Yields `data` at most `max` bytes at a time, after one interrupted call, then
+    /// fails.
+    struct Source {
+        data: Vec<u8>,
+        max: usize,
+        interrupted: bool,
+    }
+    impl std::io::Read for Source {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if !self.interrupted {
+                self.interrupted = true;
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            if self.data.is_empty() {
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            let n = buf.len().min(self.max).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data.drain(..n);
+            Ok(n)
+        }
+    }
+
+    // Streams are kept in `ManuallyDrop`, as dropping a `Tpit` needs the TPIT imports,
+    // which only exist in wasm; nothing is lent by `chunk`.
+    #[test]
+    fn reads_in_chunks() {
+        let mut s = ManuallyDrop::new(Stream::new(&[7u8; 100000][..]));
+        assert_eq!(s.chunk(u64::MAX).len(), CHUNK as usize);
+        assert_eq!(s.chunk(10), [7; 10]);
+        assert_eq!(s.chunk(0), []);
+    }
+
+    #[test]
+    fn ends_at_an_error() {
+        let mut s = ManuallyDrop::new(Stream::new(Source {
+            data: vec![1, 2, 3],
+            max: 2,
+            interrupted: false,
+        }));
+        assert_eq!(s.chunk(10), [1, 2]);
+        assert_eq!(s.chunk(10), [3]);
+        assert_eq!(s.chunk(10), []);
+    }
+}
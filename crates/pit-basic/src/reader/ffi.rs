@@ -0,0 +1,212 @@
+use crate::buffer::R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5;
+use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d;
+
+pub trait Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6 {
+    fn read(
+        &mut self,
+        p0: u32,
+    ) -> (::tpit_rt::Lent<
+        '_,
+        Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
+    >);
+    fn read64(
+        &mut self,
+        p0: u64,
+    ) -> (::tpit_rt::Lent<
+        '_,
+        Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
+    >);
+}
+pub struct Batchbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6 {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>>,
+    queue: Vec<u8>,
+}
+const _: () = {
+    #[link_section = ".pit-types"]
+    static SECTION_CONTENT: [u8; 166usize] = [
+        123u8, 114u8, 101u8, 97u8, 100u8, 40u8, 73u8, 51u8, 50u8, 41u8, 32u8, 45u8, 62u8, 32u8,
+        40u8, 82u8, 56u8, 54u8, 55u8, 50u8, 48u8, 55u8, 52u8, 48u8, 53u8, 102u8, 101u8, 56u8, 55u8,
+        102u8, 100u8, 97u8, 54u8, 50u8, 48u8, 99u8, 50u8, 100u8, 55u8, 97u8, 53u8, 52u8, 56u8,
+        53u8, 101u8, 56u8, 101u8, 53u8, 101u8, 50u8, 55u8, 52u8, 54u8, 51u8, 54u8, 97u8, 56u8,
+        57u8, 56u8, 97u8, 49u8, 54u8, 54u8, 102u8, 98u8, 54u8, 55u8, 52u8, 52u8, 52u8, 56u8, 98u8,
+        52u8, 51u8, 57u8, 49u8, 102u8, 102u8, 99u8, 53u8, 41u8, 59u8, 114u8, 101u8, 97u8, 100u8,
+        54u8, 52u8, 40u8, 73u8, 54u8, 52u8, 41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 82u8, 54u8, 56u8,
+        100u8, 97u8, 49u8, 54u8, 55u8, 55u8, 49u8, 50u8, 100u8, 100u8, 102u8, 49u8, 54u8, 48u8,
+        49u8, 97u8, 101u8, 100u8, 55u8, 57u8, 48u8, 56u8, 99u8, 57u8, 57u8, 57u8, 55u8, 50u8,
+        101u8, 54u8, 50u8, 97u8, 52u8, 49u8, 98u8, 100u8, 101u8, 97u8, 49u8, 101u8, 50u8, 56u8,
+        98u8, 50u8, 52u8, 49u8, 51u8, 48u8, 54u8, 97u8, 54u8, 98u8, 53u8, 56u8, 100u8, 50u8, 57u8,
+        101u8, 53u8, 51u8, 50u8, 100u8, 41u8, 125u8, 0u8,
+    ];
+    fn alloc<T>(m: &mut ::std::collections::BTreeMap<u32, T>, x: T) -> u32 {
+        let mut u = 0;
+        while m.contains_key(&u) {
+            u += 1;
+        }
+        m.insert(u, x);
+        return u;
+    }
+    #[derive(Default)]
+    struct TableCell {
+        all: std::cell::UnsafeCell<
+            ::std::collections::BTreeMap<
+                u32,
+                Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>,
+            >,
+        >,
+    }
+    unsafe impl Send for TableCell {}
+    unsafe impl Sync for TableCell {}
+    static TABLE: ::std::sync::LazyLock<TableCell> =
+        ::std::sync::LazyLock::new(|| TableCell::default());
+    impl Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6
+        for ::tpit_rt::Tpit<
+            Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>,
+        >
+    {
+        fn read(
+            &mut self,
+            p0: u32,
+        ) -> (::tpit_rt::Lent<
+            '_,
+            Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
+        >) {
+            #[link(
+                wasm_import_module = "tpit/bf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6"
+            )]
+            extern "C" {
+                #[link_name = "read"]
+                fn go(this: u32, p0: u32) -> (u32);
+            }
+            let r = unsafe { go(self.ptr(), p0) };
+            return unsafe { ::tpit_rt::Lent::new(r) };
+        }
+        fn read64(
+            &mut self,
+            p0: u64,
+        ) -> (::tpit_rt::Lent<
+            '_,
+            Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
+        >) {
+            #[link(
+                wasm_import_module = "tpit/bf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6"
+            )]
+            extern "C" {
+                #[link_name = "read64"]
+                fn go(this: u32, p0: u64) -> (u32);
+            }
+            let r = unsafe { go(self.ptr(), p0) };
+            return unsafe { ::tpit_rt::Lent::new(r) };
+        }
+    }
+    #[export_name = "tpit/bf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6/~c66b4443667e8c9bb437e891c9e7a273290e6b8667234d27b1eb8b97acb1cc01.drop"]
+    extern "C" fn _drop(a: u32) {
+        unsafe { (&mut *(TABLE.all.get())).remove(&a) };
+    }
+    #[export_name = "tpit/bf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6/~c66b4443667e8c9bb437e891c9e7a273290e6b8667234d27b1eb8b97acb1cc01/read"]
+    extern "C" fn read(id: u32, p0: u32) -> (u32) {
+        let r = unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read(p0);
+        return r.ptr();
+    }
+    #[export_name = "tpit/bf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6/~c66b4443667e8c9bb437e891c9e7a273290e6b8667234d27b1eb8b97acb1cc01/read64"]
+    extern "C" fn read64(id: u32, p0: u64) -> (u32) {
+        let r = unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .read64(p0);
+        return r.ptr();
+    }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batchbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6 {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/bf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batchbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6 {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6
+        for Batchbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6
+    {
+        fn read(
+            &mut self,
+            p0: u32,
+        ) -> (::tpit_rt::Lent<
+            '_,
+            Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
+        >) {
+            self.flush();
+            return Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6::read(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn read64(
+            &mut self,
+            p0: u64,
+        ) -> (::tpit_rt::Lent<
+            '_,
+            Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
+        >) {
+            self.flush();
+            return Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6::read64(
+                &mut self.inner,
+                p0,
+            );
+        }
+    }
+    impl From<Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>>
+        for ::tpit_rt::Tpit<
+            Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>,
+        >
+    {
+        fn from(
+            a: Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>,
+        ) -> Self {
+            #[link(
+                wasm_import_module = "pit/bf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6"
+            )]
+            extern "C" {
+                #[link_name = "~c66b4443667e8c9bb437e891c9e7a273290e6b8667234d27b1eb8b97acb1cc01"]
+                fn _push(
+                    a: u32,
+                ) -> ::tpit_rt::Tpit<
+                    Box<dyn Rbf0885d2d24ecbc6981a65f1b9a2e0bcdee0443f17ca667a8b17817a15b3e8f6>,
+                >;
+            }
+            return unsafe { _push(alloc(&mut *(TABLE.all.get()), a)) };
+        }
+    }
+};
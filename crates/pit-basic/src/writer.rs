@@ -0,0 +1,176 @@
+//! # Writer Module
+//!
+//! Provides the writer interface and a [`std::io::Write`] adapter over it.
+//!
+//! This module contains:
+//! - The `Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206` trait (writer)
+//! - [`Writer`], which implements [`std::io::Write`] over a writer resource
+//! - [`Stream`], which implements the writer interface over a [`std::io::Write`]
+//!
+//! ## Writer Interface
+//!
+//! The writer interface is defined in `writer.pit`:
+//! ```text
+//! {
+//!     write(R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5) -> (I32);
+//!     write64(R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d) -> (I64)
+//! }
+//! ```
+//!
+//! Each call writes a prefix of the borrowed buffer and returns its length.
+//!
+//! ## Conversions
+//!
+//! - Files, sockets and in-memory sinks such as `Vec<u8>` become writers with [`Stream`];
+//!   the collected `Vec<u8>` is itself a buffer
+//! - Writing into a fixed-size buffer is done with
+//!   `Stream { wrapped: buffer::Cursor { .. } }`, see [`crate::buffer::Cursor`]
+//! - A buffer is written to a writer resource by passing it to `write`, or as bytes
+//!   through a [`Writer`]
+
+/// Generated bindings for `writer.pit`.
+pub mod ffi;
+use crate::buffer::R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5;
+use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d;
+pub use ffi::*;
+use std::sync::Arc;
+use tpit_rt::Tpit;
+
+/// Adapter implementing [`std::io::Write`] over a writer resource.
+///
+/// Every call to [`write`](std::io::Write::write) copies the data into a read-only
+/// buffer and makes one `write` call with it.
+pub struct Writer<T> {
+    /// The wrapped writer.
+    pub wrapped: T,
+}
+impl<T: Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206> std::io::Write
+    for Writer<T>
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let b: Arc<[u8]> = buf[..buf.len().min(u32::MAX as usize)].into();
+        let mut b = Tpit::from(Box::new(b)
+            as Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>);
+        Ok((self.wrapped.write(&mut b) as usize).min(buf.len()))
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writer resource writing to a [`std::io::Write`].
+///
+/// Buffers are copied out in blocks of 4096 bytes. The PIT interface has no way to
+/// report errors, so `write` stops at the first I/O error and returns the number of
+/// bytes written until then. Wrap unbuffered streams in a [`std::io::BufWriter`],
+/// which is flushed when the resource is dropped.
+pub struct Stream<T> {
+    /// The wrapped stream.
+    pub wrapped: T,
+}
+impl<T: std::io::Write> Stream<T> {
+    fn put(&mut self, n: u64, mut get: impl FnMut(u64) -> u8) -> u64 {
+        let mut block = [0u8; 4096];
+        let mut i = 0;
+        while i < n {
+            let k = (n - i).min(4096) as usize;
+            for (j, c) in block[..k].iter_mut().enumerate() {
+                *c = get(i + j as u64);
+            }
+            let mut b = &block[..k];
+            while !b.is_empty() {
+                match self.wrapped.write(b) {
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Ok(0) | Err(_) => return i,
+                    Ok(m) => {
+                        i += m as u64;
+                        b = &b[m..];
+                    }
+                }
+            }
+        }
+        i
+    }
+}
+impl<T: std::io::Write> Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206
+    for Stream<T>
+{
+    fn write(
+        &mut self,
+        p0: &mut Tpit<Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>,
+    ) -> (u32) {
+        let n = p0.size();
+        self.put(n.into(), |i| (p0.read8(i as u32) & 0xff) as u8) as u32
+    }
+    fn write64(
+        &mut self,
+        p0: &mut Tpit<Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>>,
+    ) -> (u64) {
+        let n = p0.size();
+        self.put(n, |i| (p0.read8(i) & 0xff) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accepts at most `max` bytes per call, after one interrupted call, and fails
+    /// once `limit` bytes are written.
+    struct Sink {
+        data: Vec<u8>,
+        max: usize,
+        limit: usize,
+        interrupted: bool,
+    }
+    impl std::io::Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if !self.interrupted {
+                self.interrupted = true;
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            if self.data.len() >= self.limit {
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            let n = buf.len().min(self.max).min(self.limit - self.data.len());
+            self.data.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn puts_in_blocks() {
+        let mut s = Stream {
+            wrapped: Vec::new(),
+        };
+        assert_eq!(s.put(10000, |i| i as u8), 10000);
+        assert_eq!(s.wrapped.len(), 10000);
+        assert!(s.wrapped.iter().enumerate().all(|(i, &c)| c == i as u8));
+        assert_eq!(s.put(0, |_| unreachable!()), 0);
+    }
+
+    #[test]
+    fn puts_through_short_writes_until_an_error() {
+        let mut s = Stream {
+            wrapped: Sink {
+                data: vec![],
+                max: 3,
+                limit: 5000,
+                interrupted: false,
+            },
+        };
+        assert_eq!(s.put(10000, |i| i as u8), 5000);
+        assert!(s
+            .wrapped
+            .data
+            .iter()
+            .enumerate()
+            .all(|(i, &c)| c == i as u8));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::buffer::R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5;
+use crate::buffer64::R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d;
+
+pub trait Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206 {
+    fn write(
+        &mut self,
+        p0: &mut ::tpit_rt::Tpit<
+            Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
+        >,
+    ) -> (u32);
+    fn write64(
+        &mut self,
+        p0: &mut ::tpit_rt::Tpit<
+            Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
+        >,
+    ) -> (u64);
+}
+pub struct Batchba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206 {
+    pub inner:
+        ::tpit_rt::Tpit<Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>>,
+    queue: Vec<u8>,
+}
+const _: () = {
+    #[link_section = ".pit-types"]
+    static SECTION_CONTENT: [u8; 168usize] = [
+        123u8, 119u8, 114u8, 105u8, 116u8, 101u8, 40u8, 82u8, 56u8, 54u8, 55u8, 50u8, 48u8, 55u8,
+        52u8, 48u8, 53u8, 102u8, 101u8, 56u8, 55u8, 102u8, 100u8, 97u8, 54u8, 50u8, 48u8, 99u8,
+        50u8, 100u8, 55u8, 97u8, 53u8, 52u8, 56u8, 53u8, 101u8, 56u8, 101u8, 53u8, 101u8, 50u8,
+        55u8, 52u8, 54u8, 51u8, 54u8, 97u8, 56u8, 57u8, 56u8, 97u8, 49u8, 54u8, 54u8, 102u8, 98u8,
+        54u8, 55u8, 52u8, 52u8, 52u8, 56u8, 98u8, 52u8, 51u8, 57u8, 49u8, 102u8, 102u8, 99u8, 53u8,
+        41u8, 32u8, 45u8, 62u8, 32u8, 40u8, 73u8, 51u8, 50u8, 41u8, 59u8, 119u8, 114u8, 105u8,
+        116u8, 101u8, 54u8, 52u8, 40u8, 82u8, 54u8, 56u8, 100u8, 97u8, 49u8, 54u8, 55u8, 55u8,
+        49u8, 50u8, 100u8, 100u8, 102u8, 49u8, 54u8, 48u8, 49u8, 97u8, 101u8, 100u8, 55u8, 57u8,
+        48u8, 56u8, 99u8, 57u8, 57u8, 57u8, 55u8, 50u8, 101u8, 54u8, 50u8, 97u8, 52u8, 49u8, 98u8,
+        100u8, 101u8, 97u8, 49u8, 101u8, 50u8, 56u8, 98u8, 50u8, 52u8, 49u8, 51u8, 48u8, 54u8,
+        97u8, 54u8, 98u8, 53u8, 56u8, 100u8, 50u8, 57u8, 101u8, 53u8, 51u8, 50u8, 100u8, 41u8,
+        32u8, 45u8, 62u8, 32u8, 40u8, 73u8, 54u8, 52u8, 41u8, 125u8, 0u8,
+    ];
+    fn alloc<T>(m: &mut ::std::collections::BTreeMap<u32, T>, x: T) -> u32 {
+        let mut u = 0;
+        while m.contains_key(&u) {
+            u += 1;
+        }
+        m.insert(u, x);
+        return u;
+    }
+    #[derive(Default)]
+    struct TableCell {
+        all: std::cell::UnsafeCell<
+            ::std::collections::BTreeMap<
+                u32,
+                Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>,
+            >,
+        >,
+    }
+    unsafe impl Send for TableCell {}
+    unsafe impl Sync for TableCell {}
+    static TABLE: ::std::sync::LazyLock<TableCell> =
+        ::std::sync::LazyLock::new(|| TableCell::default());
+    impl Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206
+        for ::tpit_rt::Tpit<
+            Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>,
+        >
+    {
+        fn write(
+            &mut self,
+            p0: &mut ::tpit_rt::Tpit<
+                Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
+            >,
+        ) -> (u32) {
+            #[link(
+                wasm_import_module = "tpit/ba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206"
+            )]
+            extern "C" {
+                #[link_name = "write"]
+                fn go(this: u32, p0: u32) -> (u32);
+            }
+            return unsafe { go(self.ptr(), p0.ptr()) };
+        }
+        fn write64(
+            &mut self,
+            p0: &mut ::tpit_rt::Tpit<
+                Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
+            >,
+        ) -> (u64) {
+            #[link(
+                wasm_import_module = "tpit/ba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206"
+            )]
+            extern "C" {
+                #[link_name = "write64"]
+                fn go(this: u32, p0: u32) -> (u64);
+            }
+            return unsafe { go(self.ptr(), p0.ptr()) };
+        }
+    }
+    #[export_name = "tpit/ba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206/~f4d1e7c003f4ffeaabdaed931ab67e988f20ff3905bd50aa4bce9e63adc3536a.drop"]
+    extern "C" fn _drop(a: u32) {
+        unsafe { (&mut *(TABLE.all.get())).remove(&a) };
+    }
+    #[export_name = "tpit/ba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206/~f4d1e7c003f4ffeaabdaed931ab67e988f20ff3905bd50aa4bce9e63adc3536a/write"]
+    extern "C" fn write(id: u32, p0: u32) -> (u32) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write(unsafe { ::tpit_rt::Tpit::summon(&mut { p0 }) });
+    }
+    #[export_name = "tpit/ba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206/~f4d1e7c003f4ffeaabdaed931ab67e988f20ff3905bd50aa4bce9e63adc3536a/write64"]
+    extern "C" fn write64(id: u32, p0: u32) -> (u64) {
+        return unsafe { &mut *(TABLE.all.get()) }
+            .get_mut(&id)
+            .unwrap()
+            .write64(unsafe { ::tpit_rt::Tpit::summon(&mut { p0 }) });
+    }
+    const BATCH_LIMIT: usize = 4096;
+    impl Batchba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206 {
+        pub fn new(
+            inner: ::tpit_rt::Tpit<
+                Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>,
+            >,
+        ) -> Self {
+            Self {
+                inner,
+                queue: vec![],
+            }
+        }
+        pub fn flush(&mut self) {
+            if self.queue.is_empty() {
+                return;
+            }
+            #[link(
+                wasm_import_module = "tpit/ba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206"
+            )]
+            extern "C" {
+                #[link_name = "@batch"]
+                fn go(this: u32, p0: u32, p1: u32);
+            }
+            unsafe {
+                go(
+                    self.inner.ptr(),
+                    self.queue.as_ptr() as u32,
+                    self.queue.len() as u32,
+                )
+            };
+            self.queue.clear();
+        }
+    }
+    impl Drop for Batchba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206 {
+        fn drop(&mut self) {
+            self.flush();
+        }
+    }
+    impl Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206
+        for Batchba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206
+    {
+        fn write(
+            &mut self,
+            p0: &mut ::tpit_rt::Tpit<
+                Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>,
+            >,
+        ) -> (u32) {
+            self.flush();
+            return Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206::write(
+                &mut self.inner,
+                p0,
+            );
+        }
+        fn write64(
+            &mut self,
+            p0: &mut ::tpit_rt::Tpit<
+                Box<dyn R68da167712ddf1601aed7908c99972e62a41bdea1e28b241306a6b58d29e532d>,
+            >,
+        ) -> (u64) {
+            self.flush();
+            return Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206::write64(
+                &mut self.inner,
+                p0,
+            );
+        }
+    }
+    impl From<Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>>
+        for ::tpit_rt::Tpit<
+            Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>,
+        >
+    {
+        fn from(
+            a: Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>,
+        ) -> Self {
+            #[link(
+                wasm_import_module = "pit/ba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206"
+            )]
+            extern "C" {
+                #[link_name = "~f4d1e7c003f4ffeaabdaed931ab67e988f20ff3905bd50aa4bce9e63adc3536a"]
+                fn _push(
+                    a: u32,
+                ) -> ::tpit_rt::Tpit<
+                    Box<dyn Rba605f4053a13290a61455843f5bef1c09f92055ed154bcec6b8713ded049206>,
+                >;
+            }
+            return unsafe { _push(alloc(&mut *(TABLE.all.get()), a)) };
+        }
+    }
+};
@@ -0,0 +1,276 @@
+//! Host-side C code generation.
+//!
+//! Generates C code embedding PIT modules through the standard wasm C API (`wasm.h`),
+//! mirroring `pit_rust_host_lib`. For each interface, the generated header provides:
+//! - `R{rid}_host_emit`, defining the `pit/{rid}.{method}` and `pit/{rid}.~{ctor}`
+//!   imports of a module, like `pit_rust_host_lib::emit`
+//! - `R{rid}_{method}`, calling a method of a resource from the host
+//! - `R{rid}_{method}_type`, the type of each method import
+//!
+//! Together with `pit_host_init`, which defines `pit.drop` like
+//! `pit_rust_host_lib::init`, they come from the runtime returned by [`runtime`]:
+//!
+//! ```c
+//! pit_host_t host = {store, module, NULL};
+//! wasm_importtype_vec_t imports;
+//! wasm_module_imports(module, &imports);
+//! wasm_extern_t *externs[imports.size];
+//! pit_host_init(&host, &imports, externs);
+//! R{rid}_host_emit(&host, &imports, externs);
+//! // ... define the remaining imports, then instantiate ...
+//! host.instance = instance;
+//! ```
+//!
+//! Define `R{rid}_HOST_IMPL` before including a header in one translation unit to
+//! emit its definitions. Batch imports are not defined.
+
+use itertools::Itertools;
+use pit_core::{Arg, Interface};
+
+/// Generates a host-side C header from a PIT interface definition.
+///
+/// # Arguments
+///
+/// * `i` - The PIT interface to generate host code for
+///
+/// # Returns
+///
+/// A string containing the complete C header file content.
+pub fn cify(i: &Interface) -> String {
+    let rid = i.rid_str();
+    let methods = i.methods.keys().map(|a| format!("\"{a}\"")).join(", ");
+    let n = i.methods.len();
+    let decls = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            format!(
+                r#"
+    wasm_functype_t *R{rid}_{a}_type(void);
+    wasm_trap_t *R{rid}_{a}({});"#,
+                sig(b)
+            )
+        })
+        .join("");
+    let impls = i
+        .methods
+        .iter()
+        .enumerate()
+        .map(|(j, (a, b))| {
+            let params = std::iter::once("PIT_HOST_REF_KIND")
+                .chain(b.params.iter().map(valtype))
+                .collect::<Vec<_>>();
+            let rets = b.rets.iter().map(valtype).collect::<Vec<_>>();
+            let args = b
+                .params
+                .iter()
+                .enumerate()
+                .map(|(idx, a)| to_val(a, &format!("v{idx}")))
+                .collect::<Vec<_>>();
+            let outs = b
+                .rets
+                .iter()
+                .enumerate()
+                .map(|(idx, a)| {
+                    format!(
+                        "if (r{idx}) *r{idx} = {};",
+                        from_val(a, &format!("rets_[{idx}]"))
+                    )
+                })
+                .join("\n        ");
+            format!(
+                r#"
+    wasm_functype_t *R{rid}_{a}_type(void){{
+        wasm_valtype_vec_t params, results;
+        {}
+        {}
+        return wasm_functype_new(&params, &results);
+    }}
+    wasm_trap_t *R{rid}_{a}({}){{
+        pit_resource_t *res = pit_host_resource(self, R{rid}_RID);
+        if (!res) return pit_host_trap(host, "pit: resource does not implement pit/{rid}");
+        {}
+        {}
+        wasm_trap_t *trap = res->call(res, {j}, &args, &rets);
+        if (trap) return trap;
+        {outs}
+        return NULL;
+    }}"#,
+                valtype_vec("params", &params),
+                valtype_vec("results", &rets),
+                sig(b),
+                val_vec("args", &args),
+                val_vec("rets", &vec!["WASM_INIT_VAL".to_owned(); b.rets.len()]),
+            )
+        })
+        .join("");
+    let emits = i
+        .methods
+        .iter()
+        .enumerate()
+        .map(|(j, (a, _))| {
+            format!(
+                r#" else if (pit_host_name_eq(name, "{a}")) {{
+                wasm_functype_t *type = R{rid}_{a}_type();
+                f = pit_host_method(host, R{rid}_RID, {j}, type);
+                wasm_functype_delete(type);
+            }}"#
+            )
+        })
+        .join("");
+    format!(
+        r#"
+    #ifndef R{rid}_HOST_H
+    #define R{rid}_HOST_H
+    #include <pit_host.h>
+    #include <stdint.h>
+    #define R{rid}_RID "{rid}"
+    void R{rid}_host_emit(pit_host_t *host, const wasm_importtype_vec_t *imports, wasm_extern_t **externs);{decls}
+    #endif
+    #if defined(R{rid}_HOST_IMPL) && !defined(R{rid}_HOST_IMPL_H)
+    #define R{rid}_HOST_IMPL_H
+    /* Method names, in sorted order. */
+    static const char *const R{rid}_methods[{}] = {{{methods}}};{impls}
+    void R{rid}_host_emit(pit_host_t *host, const wasm_importtype_vec_t *imports, wasm_extern_t **externs){{
+        for (size_t i = 0; i < imports->size; i++) {{
+            if (!pit_host_name_eq(wasm_importtype_module(imports->data[i]), "pit/{rid}")) continue;
+            const wasm_name_t *name = wasm_importtype_name(imports->data[i]);
+            wasm_func_t *f = NULL;
+            if (name->size && name->data[0] == '~') {{
+                f = pit_host_ctor(host, R{rid}_RID, R{rid}_methods, {n}, name);
+            }}{emits}
+            if (f) externs[i] = wasm_func_as_extern(f);
+        }}
+    }}
+    #endif
+    "#,
+        n.max(1)
+    )
+}
+
+/// Returns the parameters of the host-side function calling a method: the host, the
+/// resource, the arguments, and pointers receiving the results, which may be NULL.
+fn sig(b: &pit_core::Sig) -> String {
+    ["pit_host_t *host".to_owned(), "wasm_ref_t *self".to_owned()]
+        .into_iter()
+        .chain(
+            b.params
+                .iter()
+                .enumerate()
+                .map(|(idx, a)| decl(hty(a), &format!("v{idx}"))),
+        )
+        .chain(
+            b.rets
+                .iter()
+                .enumerate()
+                .map(|(idx, a)| decl(hty(a), &format!("*r{idx}"))),
+        )
+        .join(", ")
+}
+
+/// Declares a C variable of a type.
+fn decl(ty: &str, name: &str) -> String {
+    if ty.ends_with('*') {
+        format!("{ty}{name}")
+    } else {
+        format!("{ty} {name}")
+    }
+}
+
+/// Returns the host-side C type of a PIT argument.
+fn hty(a: &Arg) -> &'static str {
+    match a {
+        Arg::I32 => "uint32_t",
+        Arg::I64 => "uint64_t",
+        Arg::F32 => "float",
+        Arg::F64 => "double",
+        Arg::Resource { .. } => "wasm_ref_t *",
+        _ => todo!(),
+    }
+}
+
+/// Returns the `wasm_valkind_t` of a PIT argument.
+fn valtype(a: &Arg) -> &'static str {
+    match a {
+        Arg::I32 => "WASM_I32",
+        Arg::I64 => "WASM_I64",
+        Arg::F32 => "WASM_F32",
+        Arg::F64 => "WASM_F64",
+        Arg::Resource { .. } => "PIT_HOST_REF_KIND",
+        _ => todo!(),
+    }
+}
+
+/// Converts a host-side C value into a `wasm_val_t`.
+fn to_val(a: &Arg, v: &str) -> String {
+    match a {
+        Arg::I32 => format!("WASM_I32_VAL((int32_t){v})"),
+        Arg::I64 => format!("WASM_I64_VAL((int64_t){v})"),
+        Arg::F32 => format!("WASM_F32_VAL({v})"),
+        Arg::F64 => format!("WASM_F64_VAL({v})"),
+        Arg::Resource { .. } => format!("{{.kind = PIT_HOST_REF_KIND, .of = {{.ref = {v}}}}}"),
+        _ => todo!(),
+    }
+}
+
+/// Converts a `wasm_val_t` into a host-side C value.
+fn from_val(a: &Arg, v: &str) -> String {
+    match a {
+        Arg::I32 => format!("(uint32_t){v}.of.i32"),
+        Arg::I64 => format!("(uint64_t){v}.of.i64"),
+        Arg::F32 => format!("{v}.of.f32"),
+        Arg::F64 => format!("{v}.of.f64"),
+        Arg::Resource { .. } => format!("{v}.of.ref"),
+        _ => todo!(),
+    }
+}
+
+/// Declares a `wasm_valtype_vec_t` holding new value types of the given kinds.
+fn valtype_vec(name: &str, kinds: &[&str]) -> String {
+    if kinds.is_empty() {
+        return format!("wasm_valtype_vec_new_empty(&{name});");
+    }
+    format!(
+        "wasm_valtype_t *{name}_[] = {{{}}};\n        wasm_valtype_vec_new(&{name}, {}, {name}_);",
+        kinds
+            .iter()
+            .map(|k| format!("wasm_valtype_new({k})"))
+            .join(", "),
+        kinds.len()
+    )
+}
+
+/// Declares a `wasm_val_vec_t` over an array of the given values.
+fn val_vec(name: &str, vals: &[String]) -> String {
+    if vals.is_empty() {
+        return format!("wasm_val_vec_t {name} = WASM_EMPTY_VEC;");
+    }
+    format!(
+        "wasm_val_t {name}_[] = {{{}}};\n        wasm_val_vec_t {name} = WASM_ARRAY_VEC({name}_);",
+        vals.join(", ")
+    )
+}
+
+/// Returns the host runtime the generated headers depend on, as `(file name, contents)`
+/// pairs.
+///
+/// The runtime provides `pit_host.h`, declaring `pit_host_t`, the `pit_resource_t`
+/// resources are made of and the `pit_wrapped_t` guest resources, and `pit_host.c`,
+/// implementing them along with `pit.drop` and the import functions. Build it with the
+/// host application, against the `wasm.h` of its engine.
+pub fn runtime() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("pit_host.h", include_str!("runtime/pit_host.h")),
+        ("pit_host.c", include_str!("runtime/pit_host.c")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use pit_golden::{golden, iface};
+
+    #[test]
+    fn host_header() {
+        golden!("host.h", &super::cify(&iface()));
+    }
+}
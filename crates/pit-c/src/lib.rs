@@ -17,28 +17,111 @@
 //!
 //! ```ignore
 //! use pit_core::parse_interface;
-//! use pit_c::cify;
+//! use pit_c::{cify, Opts};
 //!
 //! let interface_str = "{ method(I32) -> (I64); }";
 //! let (_, interface) = parse_interface(interface_str).unwrap();
-//! let c_header = cify(&interface);
+//! let c_header = cify(&Opts { tpit: false, salt: vec![], name: None }, &interface);
 //! ```
 //!
 //! ## Dependencies
 //!
 //! The generated C code requires:
 //! - `interface99.h` - For interface/trait implementation
-//! - `handle.h` - For handle management
+//! - `handle.h` - For handle management, provided by [`runtime`]
 //! - `stdint.h` - For fixed-width integer types
+//!
+//! Build with clang for wasm32 with `-mreference-types`. Methods with more than one
+//! result return them as a struct, which needs the multivalue ABI
+//! (`-mmultivalue -Xclang -target-abi -Xclang experimental-mv`) to match the PIT
+//! signature, and cannot include resources, as externrefs cannot be struct fields.
+//!
+//! ## TPIT
+//!
+//! With [`Opts::tpit`], headers use `tpit/{rid}` imports and exports with `uint32_t`
+//! handles instead of externrefs, like Rust guests generated in TPIT mode. They build
+//! with any wasm32 toolchain, with `PIT_TPIT` defined for both them and the runtime,
+//! and the resulting module is converted to PIT ABI v1 with `pit untpit`. Several
+//! results may then include resources.
+//!
+//! ## Implementations
+//!
+//! Defining `R{rid}_IMPORTS` before including a header emits the import side of the
+//! interface, which must be compiled once per module. Each header also carries one
+//! implementation, exported under a constructor ID derived from [`Opts::salt`] the same
+//! way as for Rust guests, and emitted by defining `{prefix}_IMPL`, where the [`prefix`]
+//! is `R{rid}_{name}` with [`Opts::name`], `R{rid}_{salt}` with the salt in hex for a
+//! salt without a name, and `R{rid}` otherwise. Implementations with distinct salts can
+//! therefore be linked into one module, or included in one translation unit:
+//!
+//! ```c
+//! #define R{rid}_IMPORTS
+//! #define R{rid}_a_IMPL
+//! #define R{rid}_b_IMPL
+//! #include <R{rid}_a.h>
+//! #include <R{rid}_b.h>
+//! // R{rid}_a_of(x) and R{rid}_b_of(x) export x through different constructors.
+//! ```
+//!
+//! Interface objects converted implicitly, when passed to imports or returned from
+//! exports, use the unnamed implementation.
+//!
+//! ## Ownership
+//!
+//! Values of an interface type are interface99 objects. Passing one to an import or
+//! returning one from an export wraps it as a new PIT resource, which takes ownership:
+//! the object is dropped through `Droppable` and its `self` freed when the resource is.
+//! Resources received from other modules are wrapped in heap-allocated `handle_t`s.
+//!
+//! Borrowed resources, passed without `&`, stay with their owner, so like
+//! `R{rid}Handle &` in `pit-cpp` they are plain `handle_t`s whatever their interface:
+//! they are lent with `handle_borrow`, and their methods called through
+//! `DYN(handle_t, R{rid}, &h)`. Handles to borrowed resources received from other
+//! modules are released with `handle_release`, which frees the handle, and in TPIT
+//! mode its table slot through `tpit.void`, without dropping the resource. Exports do
+//! this for their parameters after the call; callers do it for borrowed results.
+//!
+//! ## Hosts
+//!
+//! The [`host`] module generates the other side: C code embedding PIT modules through
+//! the wasm C API.
+
+pub mod host;
 
 use itertools::Itertools;
 use pit_core::{Arg, Interface, ResTy};
+use sha3::Digest;
 use std::iter::once;
 
+/// Options for C code generation.
+pub struct Opts {
+    /// Whether to use TPIT (`uint32_t` handles, converted by `pit untpit`) or native externref.
+    pub tpit: bool,
+    /// Additional bytes to include in the constructor ID hash for disambiguation.
+    pub salt: Vec<u8>,
+    /// Names the implementation, prefixing its C symbols with `R{rid}_{name}`; see [`prefix`].
+    pub name: Option<String>,
+}
+
+/// Returns the prefix of the C symbols and macros of the implementation in a header.
+///
+/// This is `R{rid}_{name}` with [`Opts::name`], `R{rid}_{salt}` with the salt in hex if
+/// only [`Opts::salt`] is set, and `R{rid}` for the unnamed, unsalted implementation, so
+/// that implementations with distinct constructor IDs never share symbols.
+pub fn prefix(opts: &Opts, i: &Interface) -> String {
+    let rid = i.rid_str();
+    match &opts.name {
+        Some(n) => format!("R{rid}_{n}"),
+        None if !opts.salt.is_empty() => format!("R{rid}_{}", hex::encode(&opts.salt)),
+        None => format!("R{rid}"),
+    }
+}
+
 /// Generates a C header file from a PIT interface definition.
 ///
 /// # Arguments
 ///
+/// * `opts` - Code generation options
 /// * `i` - The PIT interface to convert
 ///
 /// # Returns
@@ -52,189 +135,318 @@ use std::iter::once;
 /// # Example
 ///
 /// ```ignore
-/// let c_code = cify(&my_interface);
+/// let c_code = cify(&Opts { tpit: false, salt: vec![], name: None }, &my_interface);
 /// std::fs::write("MyInterface.h", c_code)?;
 /// ```
-pub fn cify(i: &Interface) -> String {
+pub fn cify(opts: &Opts, i: &Interface) -> String {
     let rid = i.rid_str();
+    let (ffi, xref, t, mode) = if opts.tpit {
+        (
+            FFIKind::TPIT,
+            "uint32_t",
+            "t",
+            format!("#ifndef PIT_TPIT\n    #error \"R{rid}.h was generated for TPIT; define PIT_TPIT\"\n    #endif"),
+        )
+    } else {
+        (
+            FFIKind::FFI,
+            "__externref_t",
+            "",
+            format!("#ifdef PIT_TPIT\n    #error \"R{rid}.h was generated for externref; do not define PIT_TPIT\"\n    #endif"),
+        )
+    };
+    let p = prefix(opts, i);
+    let mut ha = sha3::Sha3_256::default();
+    ha.update(format!("~{rid}"));
+    ha.update(&opts.salt);
+    let ha = hex::encode(ha.finalize());
     let iface = i
         .methods
         .iter()
         .map(|(a, b)| {
             format!(
-                "vfunc(R{rid}_{a}_res,R{rid}_{a},VSelf,{})",
-                b.params.iter().map(|a| cty(i, a, &FFIKind::C {})).join(",")
+                "vfunc(R{rid}_{a}_res,R{rid}_{a},{})",
+                once("VSelf".to_owned())
+                    .chain(b.params.iter().map(|a| cty(i, a, &FFIKind::C {})))
+                    .join(",")
             )
         })
         .join(" ");
+    let includes = i
+        .methods
+        .values()
+        .flat_map(|b| b.params.iter().chain(b.rets.iter()))
+        .filter_map(|a| match a {
+            Arg::Resource {
+                ty: ResTy::Of(a), ..
+            } => Some(format!("#include <R{}.h>", hex::encode(a))),
+            _ => None,
+        })
+        .unique()
+        .join("\n");
     let types = i
         .methods
         .iter()
         .map(|(a, b)| {
+            let fres = match b.rets.len() {
+                0 => format!("typedef void R{rid}_{a}_fres;"),
+                1 => format!(
+                    "typedef {} R{rid}_{a}_fres;",
+                    cty(i, &b.rets[0], &ffi)
+                ),
+                _ if !opts.tpit && b.rets.iter().any(|a| matches!(a, Arg::Resource { .. })) => format!(
+                    "#error \"R{rid}.{a}: several results including resources cannot cross the C ABI\""
+                ),
+                _ => format!(
+                    "typedef struct R{rid}_{a}_fres{{ {} }} R{rid}_{a}_fres;",
+                    b.rets
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, a)| {
+                            let a = cty(i, a, &ffi);
+                            format!("{a} v{idx};")
+                        })
+                        .join("")
+                ),
+            };
             format!(
                 r#"
-    typedef struct R{rid}_{a}_res{{
-    {}
-    }} R{rid}_{a}_res;
-    typedef struct R{rid}_{a}_fres{{
+    typedef struct R{rid}_{a}_res R{rid}_{a}_res;
+    {fres}
+    "#
+            )
+        })
+        .join("");
+    // Results may hold the interface itself, so they are defined after it.
+    let results = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            format!(
+                r#"
+    struct R{rid}_{a}_res{{
     {}
-    }} R{rid}_{a}_fres;
-    "#,
+    }};"#,
                 b.rets
                     .iter()
                     .enumerate()
                     .map(|(idx, a)| {
                         let a = cty(i, a, &FFIKind::C {});
-                        format!("{a} v{idx}")
+                        format!("{a} v{idx};")
                     })
-                    .join(";"),
-                b.rets
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, a)| {
-                        let a = cty(i, a, &FFIKind::FFI);
-                        format!("{a} v{idx}")
-                    })
-                    .join(";")
+                    .join(""),
             )
         })
-        .join(";");
-    let impls = i
+        .join("");
+    let imports = i
         .methods
         .iter()
         .map(|(a, b)| {
+            let fres = |idx: usize| {
+                if b.rets.len() == 1 {
+                    "fres".to_owned()
+                } else {
+                    format!("fres.v{idx}")
+                }
+            };
+            let call = format!(
+                "R{rid}_{a}_impl({})",
+                once("handle_borrow(*self)".to_owned())
+                    .chain(
+                        b.params
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, a)| to_ffi(i, a, format!("v{idx}"))),
+                    )
+                    .join(",")
+            );
+            let call = if b.rets.is_empty() {
+                format!("{call};")
+            } else {
+                format!("R{rid}_{a}_fres fres = {call};")
+            };
             format!(
                 r#"
-                static __attribute__((import_module("pit/{rid}"), import_name("{a}"))) R{rid}_{a}_fres R{rid}_{a}_impl({});
+                __attribute__((import_module("{t}pit/{rid}"), import_name("{a}"))) extern R{rid}_{a}_fres R{rid}_{a}_impl({});
                 R{rid}_{a}_res handle_t_R{rid}_{a}({}){{
-                VSELF(handle_t);
-                    R{rid}_{a}_fres fres = R{rid}_{a}_impl(handle_borrow(self),{});
-                    return R{rid}_{a}_res{{
-                        {}
-                    }};
-                }};
-                __attribute__((export_name("pit/{rid}/~c_impl/{a}"))) static R{rid}_{a}_fres R{rid}_{a}_export({}){{
-                    R{rid}_{a}_res res = VCALL(me,R{rid}_{a},{});
-                    return R{rid}_{a}_fres{{
+                    VSELF(handle_t);
+                    {call}
+                    return (R{rid}_{a}_res){{
                         {}
                     }};
-                }};
+                }}
         "#,
-                b.rets
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, a)| {
-                        let a = cty(i, a, &FFIKind::FFI);
+                once(format!("{xref} self"))
+                    .chain(b.params.iter().enumerate().map(|(idx, a)| {
+                        let a = cty(i, a, &ffi);
                         format!("{a} v{idx}")
-                    })
+                    }))
                     .join(","),
-                once(format!("__externref_t self")).chain(b.params
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, a)| {
+                once("VSelf".to_owned())
+                    .chain(b.params.iter().enumerate().map(|(idx, a)| {
                         let a = cty(i, a, &FFIKind::C {});
                         format!("{a} v{idx}")
                     }))
                     .join(","),
-                b.params.iter().enumerate().map(|(idx,a)|{
-                    let mut s = format!("v{idx}");
-                    if let Arg::Resource { ty, nullable, take, ann } = a{
-                        s = format!("handle_new({s})");
-                        if !matches!(ty,ResTy::None){
-                            let c = cty(i, a, &FFIKind::C {  });
-                            s = format!("DYN(handle_t,{c},({{
-                                handle_t handle = {s};
-                                handle_t* h = malloc(sizeof(handle_t));
-                                *h = handle;
-                                h
-                            }}))")
-                        }
-                    }
-                    return s;
-                }).join(","),
-                b.rets.iter().enumerate().map(|(idx,a)|{
-                    let mut s = format!("fres.v{idx}");
-                    if let Arg::Resource { ty, nullable, take, ann } = a{
-                        if !matches!(ty,ResTy::None){
-                            let c = cty(i, a, &FFIKind::C {  });
-                            s = format!("{c}_ref({s})")
-                        }
-                        s = format!("handle_pop({s})")
-                    }
-                    return s;
-                }).join(","),
-                once(format!("R{rid} me")).chain(b.params
+                b.rets
                     .iter()
                     .enumerate()
-                    .map(|(idx, a)| {
-                        let a = cty(i, a, &FFIKind::FFI);
+                    .map(|(idx, a)| to_c(i, a, fres(idx)))
+                    .join(","),
+            )
+        })
+        .join("\n");
+    let exports = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let rets = b
+                .rets
+                .iter()
+                .enumerate()
+                .map(|(idx, a)| to_ffi(i, a, format!("res.v{idx}")))
+                .collect::<Vec<_>>();
+            let ret = match rets.len() {
+                0 => "return;".to_owned(),
+                1 => format!("return {};", rets[0]),
+                _ => format!("return (R{rid}_{a}_fres){{ {} }};", rets.join(",")),
+            };
+            // Borrowed resources stay with the caller, so only their handles are released.
+            let borrowed = b
+                .params
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| matches!(a, Arg::Resource { take: false, .. }))
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+            let wrap = borrowed
+                .iter()
+                .map(|idx| format!("handle_t p{idx} = handle_new(v{idx});\n                    "))
+                .join("");
+            let unwrap = borrowed
+                .iter()
+                .map(|idx| format!("handle_release(p{idx});\n                    "))
+                .join("");
+            format!(
+                r#"
+                __attribute__((export_name("{t}pit/{rid}/~{ha}/{a}"))) R{rid}_{a}_fres {p}_{a}_export({}){{
+                    {wrap}R{rid}_{a}_res res = VCALL({});
+                    {unwrap}{ret}
+                }}
+        "#,
+                once(format!("R{rid} *me"))
+                    .chain(b.params.iter().enumerate().map(|(idx, a)| {
+                        let a = cty(i, a, &ffi);
                         format!("{a} v{idx}")
                     }))
                     .join(","),
-                b.params.iter().enumerate().map(|(idx,a)|{
-                    let mut s = format!("v{idx}");
-                    if let Arg::Resource { ty, nullable, take, ann } = a{
-                        if !matches!(ty,ResTy::None){
-                            let c = cty(i, a, &FFIKind::C {  });
-                            s = format!("{c}_ref({s})")
-                        }
-                        s = format!("handle_{}({s})",if *take{
-                            "pop"
-                        }else{
-                            "borrow"
-                        })
-                    }
-                    return s;
-                }).join(","),
-                b.rets.iter().enumerate().map(|(idx,a)|{
-                    let mut s = format!("res.v{idx}");
-                    if let Arg::Resource { ty, nullable, take, ann } = a{
-                        s = format!("handle_new({s})");
-                        if !matches!(ty,ResTy::None){
-                            let c = cty(i, a, &FFIKind::C {  });
-                            s = format!("DYN(handle_t,{c},({{
-                                handle_t handle = {s};
-                                handle_t* h = malloc(sizeof(handle_t));
-                                *h = handle;
-                                h
-                            }}))")
-                        }
-                    }
-                    return s;
-                }).join(","),
+                once("*me".to_owned())
+                    .chain(once(format!("R{rid}_{a}")))
+                    .chain(b.params.iter().enumerate().map(|(idx, a)| match a {
+                        Arg::Resource { take: false, .. } => format!("p{idx}"),
+                        _ => to_c(i, a, format!("v{idx}")),
+                    }))
+                    .join(","),
             )
         })
-        .join(";\n");
+        .join("\n");
     format!(
         r#"
-    #ifndef R{rid}
-    #define R{rid}
+    #ifndef R{rid}_H
+    #define R{rid}_H
+    {mode}
     #include <interface99.h>
     #include <handle.h>
     #include <stdint.h>
+    #include <stdlib.h>
+    {includes}
     #define R{rid}_IFACE {iface}
     #define R{rid}_EXTENDS (Droppable)
     {types}
-    interface(R{rid});
+    interface(R{rid});{results}
     declImplExtern(R{rid},handle_t);
-    extern __externref_t R{rid}_ref(R{rid} rid);
-    #define R{rid}_of(a) handle_new(R{rid}_ref(a))
     #endif
-    #ifdef R{rid}_IMPL
-    __attribute__((import_module("pit/{rid}"), import_name("~c_impl"))) extern __externref_t $$of_${rid}({rid} rid);
-    __externref_t R{rid}_ref({rid} rid){{
-        return $$of_${rid}(rid);
-    }};
-    __attribute__((export_name("pit/{rid}/~c_impl.drop"))) static void drop({rid} rid){{
-        VCALL_SUPER(rid,Droppable,drop);
-        free(rid.self);
-    }};
-    {impls}
+    #ifndef {p}_CTOR_H
+    #define {p}_CTOR_H
+    extern {xref} {p}_ref(R{rid} rid);
+    #define {p}_of(a) handle_new({p}_ref(a))
+    #endif
+    #if defined(R{rid}_IMPORTS) && !defined(R{rid}_IMPORTS_H)
+    #define R{rid}_IMPORTS_H
+    {imports}
     implExtern(R{rid},handle_t);
     #endif
+    #if defined({p}_IMPL) && !defined({p}_CTOR_IMPL_H)
+    #define {p}_CTOR_IMPL_H
+    __attribute__((import_module("{t}pit/{rid}"), import_name("~{ha}"))) extern {xref} {p}_push(R{rid} *rid);
+    {xref} {p}_ref(R{rid} rid){{
+        R{rid} *p = malloc(sizeof(R{rid}));
+        if (!p) abort();
+        *p = rid;
+        return {p}_push(p);
+    }}
+    __attribute__((export_name("{t}pit/{rid}/~{ha}.drop"))) void {p}_drop(R{rid} *rid){{
+        VCALL_SUPER(*rid,Droppable,drop);
+        free(rid->self);
+        free(rid);
+    }}
+    {exports}
+    #endif
     "#
     )
 }
+
+/// Converts a value received across the FFI boundary into its C representation.
+///
+/// Untyped and borrowed resources are stored in a new handle. Typed resources whose
+/// ownership is taken are wrapped in a heap-allocated handle implementing their
+/// interface.
+fn to_c(i: &Interface, t: &Arg, v: String) -> String {
+    match t {
+        Arg::Resource { ty, take, .. } => match ty {
+            _ if !*take => format!("handle_new({v})"),
+            ResTy::None => format!("handle_new({v})"),
+            _ => {
+                let c = cty(i, t, &FFIKind::C {});
+                format!("DYN(handle_t,{c},handle_box({v}))")
+            }
+        },
+        _ => v,
+    }
+}
+
+/// Converts a C value into its representation across the FFI boundary.
+///
+/// Borrowed resources lend the reference in their handle. Untyped resources whose
+/// ownership is taken give it up, and typed ones are exported as new resources.
+fn to_ffi(i: &Interface, t: &Arg, v: String) -> String {
+    match t {
+        Arg::Resource { ty, take, .. } => match ty {
+            _ if !*take => format!("handle_borrow({v})"),
+            ResTy::None => format!("handle_pop({v})"),
+            _ => {
+                let c = cty(i, t, &FFIKind::C {});
+                format!("{c}_ref({v})")
+            }
+        },
+        _ => v,
+    }
+}
+
+/// Returns the C runtime the generated headers depend on, as `(file name, contents)`
+/// pairs.
+///
+/// The runtime provides `handle.h`, declaring the handle table (`handle_t`,
+/// `handle_new`, `handle_borrow`, `handle_pop`, `handle_release`, `handle_box`) and the `Droppable`
+/// interface, and `handle.c`, implementing them. Build `handle.c` once per module, with
+/// clang for wasm32 and `-mreference-types`, or with `PIT_TPIT` defined for headers
+/// generated with [`Opts::tpit`].
+pub fn runtime() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("handle.h", include_str!("runtime/handle.h")),
+        ("handle.c", include_str!("runtime/handle.c")),
+    ]
+}
 /// FFI kind enumeration for type conversion.
 ///
 /// Determines whether a type should be rendered for the WebAssembly FFI boundary
@@ -242,6 +454,8 @@ pub fn cify(i: &Interface) -> String {
 pub enum FFIKind {
     /// Raw WebAssembly FFI type (uses `__externref_t` for resources)
     FFI,
+    /// Raw TPIT FFI type (uses `uint32_t` handles for resources)
+    TPIT,
     /// High-level C type (uses concrete resource types)
     C {},
 }
@@ -256,7 +470,7 @@ pub enum FFIKind {
 ///
 /// # Returns
 ///
-/// A string containing the C type name.
+/// A string containing the C type name; borrowed resources are `handle_t`s.
 pub fn cty(i: &Interface, t: &Arg, ffi_kind: &FFIKind) -> String {
     match t {
         Arg::I32 => format!("uint32_t"),
@@ -270,7 +484,9 @@ pub fn cty(i: &Interface, t: &Arg, ffi_kind: &FFIKind) -> String {
             ann,
         } => match ffi_kind {
             FFIKind::FFI => format!("__externref_t"),
+            FFIKind::TPIT => "uint32_t".to_owned(),
             FFIKind::C {} => match ty {
+                _ if !*take => "handle_t".to_owned(),
                 pit_core::ResTy::None => format!("handle_t"),
                 pit_core::ResTy::Of(a) => format!("R{}", hex::encode(a)),
                 pit_core::ResTy::This => format!("R{}", i.rid_str()),
@@ -280,3 +496,91 @@ pub fn cty(i: &Interface, t: &Arg, ffi_kind: &FFIKind) -> String {
         _ => todo!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pit_golden::{golden, iface, other};
+    use std::process::Command;
+
+    #[test]
+    fn guest_header() {
+        let opts = Opts {
+            tpit: false,
+            salt: vec![],
+            name: None,
+        };
+        golden!("guest.h", &cify(&opts, &iface()));
+    }
+
+    #[test]
+    fn tpit_guest_header() {
+        let opts = Opts {
+            tpit: true,
+            salt: vec![],
+            name: None,
+        };
+        golden!("guest_tpit.h", &cify(&opts, &iface()));
+    }
+
+    #[test]
+    fn named_guest_header() {
+        let opts = Opts {
+            tpit: false,
+            salt: b"second".to_vec(),
+            name: Some("second".to_owned()),
+        };
+        golden!("guest_named.h", &cify(&opts, &iface()));
+    }
+
+    #[test]
+    fn prefixes_salted_implementations() {
+        let rid = iface().rid_str();
+        let mut opts = Opts {
+            tpit: false,
+            salt: vec![],
+            name: None,
+        };
+        assert_eq!(prefix(&opts, &iface()), format!("R{rid}"));
+        opts.salt = b"ab".to_vec();
+        assert_eq!(prefix(&opts, &iface()), format!("R{rid}_6162"));
+        let h = cify(&opts, &iface());
+        assert!(h.contains(&format!("#if defined(R{rid}_6162_IMPL)")));
+        assert!(h.contains(&format!("R{rid}_6162_drop(")));
+        assert!(h.contains(&format!("#if defined(R{rid}_IMPORTS)")));
+        opts.name = Some("a".to_owned());
+        assert_eq!(prefix(&opts, &iface()), format!("R{rid}_a"));
+    }
+
+    /// Checks the TPIT headers, with their imports and implementations, and the
+    /// runtime with the C compiler. `tests/include` stands in for interface99, whose
+    /// macros it expands to plain declarations.
+    #[test]
+    fn compiles_tpit_guest() {
+        let opts = Opts {
+            tpit: true,
+            salt: vec![],
+            name: None,
+        };
+        let dir = pit_golden::scratch("c");
+        let mut main = String::new();
+        for i in [iface(), other()] {
+            let rid = i.rid_str();
+            std::fs::write(dir.join(format!("R{rid}.h")), cify(&opts, &i)).unwrap();
+            main += &format!(
+                "#define R{rid}_IMPORTS\n#define {}_IMPL\n#include <R{rid}.h>\n",
+                prefix(&opts, &i)
+            );
+        }
+        std::fs::write(dir.join("main.c"), main).unwrap();
+        pit_golden::write_all(&dir, runtime());
+        pit_golden::run(
+            Command::new("cc")
+                .current_dir(&dir)
+                .args(["-fsyntax-only", "-Wno-attributes", "-DPIT_TPIT", "-I."])
+                .arg("-I")
+                .arg(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/include"))
+                .args(["main.c", "handle.c"]),
+        );
+    }
+}
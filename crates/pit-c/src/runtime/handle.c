@@ -0,0 +1,91 @@
+/*
+ * Handle table runtime for C code generated by pit-c; see handle.h.
+ */
+#include <handle.h>
+
+#ifdef PIT_TPIT
+
+__attribute__((import_module("tpit"), import_name("drop"))) extern void
+handle_drop_ref(uint32_t ref);
+
+__attribute__((import_module("tpit"), import_name("void"))) extern void
+handle_void_ref(uint32_t ref);
+
+handle_t handle_new(pit_ref_t ref) { return ref; }
+
+pit_ref_t handle_borrow(handle_t handle) { return handle; }
+
+pit_ref_t handle_pop(handle_t handle) { return handle; }
+
+void handle_release(handle_t handle) {
+    if (handle)
+        handle_void_ref(handle);
+}
+
+#else
+
+static __externref_t handle_table[0];
+
+/* Free slots of the table, reused before growing it. */
+static uint32_t *handle_free;
+static uint32_t handle_nfree;
+static uint32_t handle_cap;
+
+__attribute__((import_module("pit"), import_name("drop"))) extern void
+handle_drop_ref(__externref_t ref);
+
+handle_t handle_new(__externref_t ref) {
+    uint32_t i;
+    if (handle_nfree) {
+        i = handle_free[--handle_nfree];
+        __builtin_wasm_table_set(handle_table, i, ref);
+    } else {
+        int old = __builtin_wasm_table_grow(handle_table, ref, 1);
+        if (old < 0)
+            abort();
+        i = (uint32_t)old;
+    }
+    return i + 1;
+}
+
+__externref_t handle_borrow(handle_t handle) {
+    if (!handle)
+        return __builtin_wasm_ref_null_extern();
+    return __builtin_wasm_table_get(handle_table, handle - 1);
+}
+
+__externref_t handle_pop(handle_t handle) {
+    if (!handle)
+        return __builtin_wasm_ref_null_extern();
+    __externref_t ref = __builtin_wasm_table_get(handle_table, handle - 1);
+    __builtin_wasm_table_set(handle_table, handle - 1, __builtin_wasm_ref_null_extern());
+    if (handle_nfree == handle_cap) {
+        handle_cap = handle_cap ? handle_cap * 2 : 16;
+        handle_free = realloc(handle_free, handle_cap * sizeof(uint32_t));
+        if (!handle_free)
+            abort();
+    }
+    handle_free[handle_nfree++] = handle - 1;
+    return ref;
+}
+
+void handle_release(handle_t handle) { (void)handle_pop(handle); }
+
+#endif
+
+handle_t *handle_box(pit_ref_t ref) {
+    handle_t *h = malloc(sizeof(handle_t));
+    if (!h)
+        abort();
+    *h = handle_new(ref);
+    return h;
+}
+
+void handle_t_drop(VSelf) {
+    VSELF(handle_t);
+    if (!*self)
+        return;
+    handle_drop_ref(handle_pop(*self));
+    *self = 0;
+}
+implExtern(Droppable, handle_t);
@@ -0,0 +1,58 @@
+/*
+ * Handle table runtime for C code generated by pit-c.
+ *
+ * Externrefs cannot be stored in linear memory, so generated code keeps them in a
+ * wasm table and passes around handles, which are indices into it. Handle 0 is the
+ * null handle.
+ *
+ * Requires clang targeting wasm32 with reference types (-mreference-types) and
+ * interface99 on the include path.
+ *
+ * When PIT_TPIT is defined, as needed by headers generated in TPIT mode, references
+ * are already u32 TPIT handles, which are used as-is, dropped through tpit.drop, and
+ * released without dropping their resource through tpit.void. This works with any
+ * wasm32 toolchain; run `pit untpit` on the resulting module.
+ */
+#ifndef PIT_HANDLE_H
+#define PIT_HANDLE_H
+#include <interface99.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/* A reference to a PIT resource, as passed across the FFI boundary. */
+#ifdef PIT_TPIT
+typedef uint32_t pit_ref_t;
+#else
+typedef __externref_t pit_ref_t;
+#endif
+
+/* An index into the handle table, plus one; in TPIT mode, the TPIT handle itself. */
+typedef uint32_t handle_t;
+
+/* Implemented by every resource; drops the underlying PIT resource. */
+#define Droppable_IFACE vfunc(void, drop, VSelf)
+interface(Droppable);
+
+/* Stores a reference in a new handle, taking ownership of it. */
+handle_t handle_new(pit_ref_t ref);
+
+/* Returns the reference in a handle, which keeps owning it. */
+pit_ref_t handle_borrow(handle_t handle);
+
+/* Returns the reference in a handle and frees the handle, passing ownership on. */
+pit_ref_t handle_pop(handle_t handle);
+
+/*
+ * Frees a handle to a borrowed resource without dropping the resource, which stays
+ * with its owner. Borrowed results of imports are released with this.
+ */
+void handle_release(handle_t handle);
+
+/* Stores a reference in a new handle allocated on the heap, for use with DYN. */
+handle_t *handle_box(pit_ref_t ref);
+
+/* Drops the resource in a handle and frees the handle; the handle itself stays allocated. */
+void handle_t_drop(VSelf);
+declImplExtern(Droppable, handle_t);
+
+#endif
@@ -0,0 +1,239 @@
+/*
+ * Host runtime for embedding PIT modules through the wasm C API; see pit_host.h.
+ */
+#include <pit_host.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static void pit_host_finalize(void *p) {
+    pit_resource_t *res = p;
+    if (res->finalize)
+        res->finalize(res);
+    else
+        free(res);
+}
+
+wasm_ref_t *pit_host_ref(pit_host_t *host, pit_resource_t *res) {
+    wasm_foreign_t *foreign = wasm_foreign_new(host->store);
+    wasm_ref_t *ref = wasm_foreign_as_ref(foreign);
+    wasm_ref_set_host_info_with_finalizer(ref, res, pit_host_finalize);
+    ref = wasm_ref_copy(ref);
+    wasm_foreign_delete(foreign);
+    return ref;
+}
+
+pit_resource_t *pit_host_resource(const wasm_ref_t *ref, const char *rid) {
+    if (!ref)
+        return NULL;
+    pit_resource_t *res = wasm_ref_get_host_info(ref);
+    if (!res || strcmp(res->rid, rid))
+        return NULL;
+    return res;
+}
+
+wasm_trap_t *pit_host_trap(pit_host_t *host, const char *msg) {
+    wasm_message_t message;
+    wasm_name_new_from_string_nt(&message, msg);
+    wasm_trap_t *trap = wasm_trap_new(host->store, &message);
+    wasm_name_delete(&message);
+    return trap;
+}
+
+int pit_host_name_eq(const wasm_name_t *name, const char *s) {
+    size_t n = strlen(s);
+    return name->size == n && !memcmp(name->data, s, n);
+}
+
+static wasm_trap_t *pit_host_drop(void *env, const wasm_val_vec_t *args, wasm_val_vec_t *rets) {
+    (void)rets;
+    if (!args->data[0].of.ref)
+        return pit_host_trap(env, "pit.drop: null resource");
+    pit_resource_t *res = wasm_ref_get_host_info(args->data[0].of.ref);
+    if (!res || !res->drop)
+        return NULL;
+    void (*drop)(pit_resource_t *) = res->drop;
+    res->drop = NULL;
+    drop(res);
+    return NULL;
+}
+
+void pit_host_init(pit_host_t *host, const wasm_importtype_vec_t *imports, wasm_extern_t **externs) {
+    for (size_t i = 0; i < imports->size; i++) {
+        if (!pit_host_name_eq(wasm_importtype_module(imports->data[i]), "pit") ||
+            !pit_host_name_eq(wasm_importtype_name(imports->data[i]), "drop"))
+            continue;
+        wasm_valtype_vec_t params, results;
+        wasm_valtype_t *ps[] = {wasm_valtype_new(PIT_HOST_REF_KIND)};
+        wasm_valtype_vec_new(&params, 1, ps);
+        wasm_valtype_vec_new_empty(&results);
+        wasm_functype_t *type = wasm_functype_new(&params, &results);
+        wasm_func_t *f = wasm_func_new_with_env(host->store, type, pit_host_drop, host, NULL);
+        wasm_functype_delete(type);
+        externs[i] = wasm_func_as_extern(f);
+    }
+}
+
+static wasm_trap_t *pit_wrapped_call(pit_resource_t *self, size_t method,
+                                     const wasm_val_vec_t *args, wasm_val_vec_t *rets) {
+    pit_wrapped_t *w = (pit_wrapped_t *)self;
+    if (method >= w->nmethods || !w->methods[method])
+        return pit_host_trap(w->host, "pit: missing method export");
+    wasm_val_t *all = malloc((args->size + 1) * sizeof(wasm_val_t));
+    if (!all)
+        return pit_host_trap(w->host, "pit: out of memory");
+    all[0] = w->base;
+    if (args->size)
+        memcpy(all + 1, args->data, args->size * sizeof(wasm_val_t));
+    wasm_val_vec_t v = {args->size + 1, all};
+    wasm_trap_t *trap = wasm_func_call(w->methods[method], &v, rets);
+    free(all);
+    return trap;
+}
+
+static void pit_wrapped_drop(pit_resource_t *self) {
+    pit_wrapped_t *w = (pit_wrapped_t *)self;
+    if (!w->drop)
+        return;
+    wasm_val_t args[] = {w->base};
+    wasm_val_vec_t a = WASM_ARRAY_VEC(args);
+    wasm_val_vec_t r = WASM_EMPTY_VEC;
+    wasm_trap_t *trap = wasm_func_call(w->drop, &a, &r);
+    if (trap)
+        wasm_trap_delete(trap);
+}
+
+static void pit_wrapped_finalize(pit_resource_t *self) {
+    pit_wrapped_t *w = (pit_wrapped_t *)self;
+    if (w->drop)
+        wasm_func_delete(w->drop);
+    for (size_t i = 0; i < w->nmethods; i++)
+        if (w->methods[i])
+            wasm_func_delete(w->methods[i]);
+    free(w);
+}
+
+pit_wrapped_t *pit_wrapped_new(pit_host_t *host, const char *rid, const char *ctor, wasm_val_t base,
+                               const char *const *methods, size_t nmethods) {
+    pit_wrapped_t *w = calloc(1, sizeof(pit_wrapped_t) + nmethods * sizeof(wasm_func_t *));
+    if (!w)
+        return NULL;
+    w->res.rid = rid;
+    w->res.call = pit_wrapped_call;
+    w->res.drop = pit_wrapped_drop;
+    w->res.finalize = pit_wrapped_finalize;
+    w->host = host;
+    w->base = base;
+    w->nmethods = nmethods;
+    /* Exports are named `pit/{rid}/~{ctor}` followed by `.drop` or `/{method}`. */
+    size_t plen = strlen(rid) + strlen(ctor) + 6;
+    char *prefix = malloc(plen + 1);
+    if (!prefix) {
+        free(w);
+        return NULL;
+    }
+    snprintf(prefix, plen + 1, "pit/%s/~%s", rid, ctor);
+    wasm_exporttype_vec_t types;
+    wasm_extern_vec_t exports;
+    wasm_module_exports(host->module, &types);
+    wasm_instance_exports(host->instance, &exports);
+    for (size_t i = 0; i < types.size && i < exports.size; i++) {
+        const wasm_name_t *name = wasm_exporttype_name(types.data[i]);
+        wasm_func_t *f = wasm_extern_as_func(exports.data[i]);
+        if (!f || name->size <= plen || memcmp(name->data, prefix, plen))
+            continue;
+        const char *rest = name->data + plen;
+        size_t rlen = name->size - plen;
+        if (rlen == 5 && !memcmp(rest, ".drop", 5)) {
+            w->drop = wasm_func_copy(f);
+            continue;
+        }
+        if (rest[0] != '/')
+            continue;
+        for (size_t j = 0; j < nmethods; j++) {
+            if (!w->methods[j] && strlen(methods[j]) == rlen - 1 &&
+                !memcmp(rest + 1, methods[j], rlen - 1)) {
+                w->methods[j] = wasm_func_copy(f);
+                break;
+            }
+        }
+    }
+    wasm_extern_vec_delete(&exports);
+    wasm_exporttype_vec_delete(&types);
+    free(prefix);
+    return w;
+}
+
+typedef struct pit_host_ctor_env {
+    pit_host_t *host;
+    const char *rid;
+    const char *const *methods;
+    size_t nmethods;
+    char ctor[];
+} pit_host_ctor_env_t;
+
+static wasm_trap_t *pit_host_ctor_call(void *env, const wasm_val_vec_t *args, wasm_val_vec_t *rets) {
+    pit_host_ctor_env_t *e = env;
+    if (!e->host->instance)
+        return pit_host_trap(e->host, "pit: constructor called before instantiation");
+    pit_wrapped_t *w =
+        pit_wrapped_new(e->host, e->rid, e->ctor, args->data[0], e->methods, e->nmethods);
+    if (!w)
+        return pit_host_trap(e->host, "pit: out of memory");
+    rets->data[0].kind = PIT_HOST_REF_KIND;
+    rets->data[0].of.ref = pit_host_ref(e->host, &w->res);
+    return NULL;
+}
+
+wasm_func_t *pit_host_ctor(pit_host_t *host, const char *rid, const char *const *methods,
+                           size_t nmethods, const wasm_name_t *name) {
+    if (!name->size || name->data[0] != '~')
+        return NULL;
+    pit_host_ctor_env_t *e = malloc(sizeof(pit_host_ctor_env_t) + name->size);
+    if (!e)
+        return NULL;
+    e->host = host;
+    e->rid = rid;
+    e->methods = methods;
+    e->nmethods = nmethods;
+    memcpy(e->ctor, name->data + 1, name->size - 1);
+    e->ctor[name->size - 1] = 0;
+    wasm_valtype_vec_t params, results;
+    wasm_valtype_t *ps[] = {wasm_valtype_new(WASM_I32)};
+    wasm_valtype_t *rs[] = {wasm_valtype_new(PIT_HOST_REF_KIND)};
+    wasm_valtype_vec_new(&params, 1, ps);
+    wasm_valtype_vec_new(&results, 1, rs);
+    wasm_functype_t *type = wasm_functype_new(&params, &results);
+    wasm_func_t *f = wasm_func_new_with_env(host->store, type, pit_host_ctor_call, e, free);
+    wasm_functype_delete(type);
+    return f;
+}
+
+typedef struct pit_host_method_env {
+    pit_host_t *host;
+    const char *rid;
+    size_t method;
+} pit_host_method_env_t;
+
+static wasm_trap_t *pit_host_method_call(void *env, const wasm_val_vec_t *args,
+                                         wasm_val_vec_t *rets) {
+    pit_host_method_env_t *e = env;
+    if (!args->data[0].of.ref)
+        return pit_host_trap(e->host, "pit: null resource");
+    pit_resource_t *res = pit_host_resource(args->data[0].of.ref, e->rid);
+    if (!res)
+        return pit_host_trap(e->host, "pit: resource does not implement the interface");
+    wasm_val_vec_t rest = {args->size - 1, args->data + 1};
+    return res->call(res, e->method, &rest, rets);
+}
+
+wasm_func_t *pit_host_method(pit_host_t *host, const char *rid, size_t method,
+                             const wasm_functype_t *type) {
+    pit_host_method_env_t *e = malloc(sizeof(pit_host_method_env_t));
+    if (!e)
+        return NULL;
+    e->host = host;
+    e->rid = rid;
+    e->method = method;
+    return wasm_func_new_with_env(host->store, type, pit_host_method_call, e, free);
+}
@@ -0,0 +1,86 @@
+/*
+ * Host runtime for embedding PIT modules through the standard wasm C API (wasm.h),
+ * used by host code generated by pit-c.
+ *
+ * Resources are foreign references whose host info is a pit_resource_t. Guest
+ * resources, created by `pit/{rid}.~{ctor}` imports, are pit_wrapped_ts calling the
+ * `pit/{rid}/~{ctor}/...` exports of the instance; the host can implement its own by
+ * filling in a pit_resource_t and passing it to pit_host_ref.
+ *
+ * Engines naming the externref value kind differently can define PIT_HOST_REF_KIND.
+ */
+#ifndef PIT_HOST_H
+#define PIT_HOST_H
+#include <stddef.h>
+#include <stdint.h>
+#include <wasm.h>
+
+#ifndef PIT_HOST_REF_KIND
+#define PIT_HOST_REF_KIND WASM_ANYREF
+#endif
+
+/* The store a module lives in, and the instance implementing its constructors. */
+typedef struct pit_host {
+    wasm_store_t *store;
+    const wasm_module_t *module;
+    /* Set once the module has been instantiated; constructors trap before that. */
+    wasm_instance_t *instance;
+} pit_host_t;
+
+typedef struct pit_resource pit_resource_t;
+
+/* A resource implementing the interface with the resource ID `rid`. */
+struct pit_resource {
+    const char *rid;
+    /* Calls a method, by its index among the methods sorted by name, without the resource argument. */
+    wasm_trap_t *(*call)(pit_resource_t *self, size_t method, const wasm_val_vec_t *args,
+                         wasm_val_vec_t *rets);
+    /* Run by pit.drop, at most once; may be NULL. */
+    void (*drop)(pit_resource_t *self);
+    /* Run when the reference is collected; NULL frees the resource. */
+    void (*finalize)(pit_resource_t *self);
+};
+
+/* A guest resource, backed by the exports of the instance that constructed it. */
+typedef struct pit_wrapped {
+    pit_resource_t res;
+    pit_host_t *host;
+    /* The value the guest passed to the constructor. */
+    wasm_val_t base;
+    /* The `.drop` export and the method exports; missing ones are NULL. */
+    wasm_func_t *drop;
+    size_t nmethods;
+    wasm_func_t *methods[];
+} pit_wrapped_t;
+
+/* Returns a new reference owning a resource. */
+wasm_ref_t *pit_host_ref(pit_host_t *host, pit_resource_t *res);
+
+/* Returns the resource behind a reference if it implements `rid`, or NULL. */
+pit_resource_t *pit_host_resource(const wasm_ref_t *ref, const char *rid);
+
+/* Returns a new trap with a message. */
+wasm_trap_t *pit_host_trap(pit_host_t *host, const char *msg);
+
+/* Returns whether a wasm name equals a string. */
+int pit_host_name_eq(const wasm_name_t *name, const char *s);
+
+/*
+ * Defines the `pit.drop` import, writing it to the entries of `externs` matching
+ * `imports`. The caller owns the externs written.
+ */
+void pit_host_init(pit_host_t *host, const wasm_importtype_vec_t *imports, wasm_extern_t **externs);
+
+/* Creates a guest resource, looking up the exports of its constructor. */
+pit_wrapped_t *pit_wrapped_new(pit_host_t *host, const char *rid, const char *ctor, wasm_val_t base,
+                               const char *const *methods, size_t nmethods);
+
+/* Returns a `pit/{rid}.~{ctor}` import creating guest resources, given the name of the import. */
+wasm_func_t *pit_host_ctor(pit_host_t *host, const char *rid, const char *const *methods,
+                           size_t nmethods, const wasm_name_t *name);
+
+/* Returns a `pit/{rid}.{method}` import calling the resource passed as its first argument. */
+wasm_func_t *pit_host_method(pit_host_t *host, const char *rid, size_t method,
+                             const wasm_functype_t *type);
+
+#endif
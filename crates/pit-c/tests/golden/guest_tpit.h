@@ -0,0 +1,154 @@
+
+    #ifndef R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_H
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_H
+    #ifndef PIT_TPIT
+    #error "R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068.h was generated for TPIT; define PIT_TPIT"
+    #endif
+    #include <interface99.h>
+    #include <handle.h>
+    #include <stdint.h>
+    #include <stdlib.h>
+    #include <Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.h>
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_IFACE vfunc(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_res,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give,VSelf,Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd) vfunc(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_res,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read,VSelf,uint32_t) vfunc(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_res,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek,VSelf,uint64_t,float) vfunc(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_res,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split,VSelf) vfunc(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_res,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write,VSelf,handle_t)
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_EXTENDS (Droppable)
+    
+    typedef struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_res R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_res;
+    typedef void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_fres;
+    
+    typedef struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_res R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_res;
+    typedef uint32_t R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres;
+    
+    typedef struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_res R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_res;
+    typedef struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres{ uint64_t v0;double v1; } R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres;
+    
+    typedef struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_res R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_res;
+    typedef uint32_t R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres;
+    
+    typedef struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_res R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_res;
+    typedef void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_fres;
+    
+    interface(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068);
+    struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_res{
+    
+    };
+    struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_res{
+    handle_t v0;
+    };
+    struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_res{
+    uint64_t v0;double v1;
+    };
+    struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_res{
+    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 v0;
+    };
+    struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_res{
+    
+    };
+    declImplExtern(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068,handle_t);
+    #endif
+    #ifndef R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_H
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_H
+    extern uint32_t R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_ref(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 rid);
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_of(a) handle_new(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_ref(a))
+    #endif
+    #if defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_IMPORTS) && !defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_IMPORTS_H)
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_IMPORTS_H
+    
+                __attribute__((import_module("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("give"))) extern R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_impl(uint32_t self,uint32_t v0);
+                R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_res handle_t_R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give(VSelf,Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd v0){
+                    VSELF(handle_t);
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_impl(handle_borrow(*self),Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd_ref(v0));
+                    return (R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_res){
+                        
+                    };
+                }
+        
+
+                __attribute__((import_module("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("read"))) extern R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_impl(uint32_t self,uint32_t v0);
+                R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_res handle_t_R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read(VSelf,uint32_t v0){
+                    VSELF(handle_t);
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres fres = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_impl(handle_borrow(*self),v0);
+                    return (R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_res){
+                        handle_new(fres)
+                    };
+                }
+        
+
+                __attribute__((import_module("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("seek"))) extern R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_impl(uint32_t self,uint64_t v0,float v1);
+                R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_res handle_t_R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek(VSelf,uint64_t v0,float v1){
+                    VSELF(handle_t);
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres fres = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_impl(handle_borrow(*self),v0,v1);
+                    return (R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_res){
+                        fres.v0,fres.v1
+                    };
+                }
+        
+
+                __attribute__((import_module("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("split"))) extern R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_impl(uint32_t self);
+                R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_res handle_t_R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split(VSelf){
+                    VSELF(handle_t);
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres fres = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_impl(handle_borrow(*self));
+                    return (R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_res){
+                        DYN(handle_t,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068,handle_box(fres))
+                    };
+                }
+        
+
+                __attribute__((import_module("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("write"))) extern R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_impl(uint32_t self,uint32_t v0);
+                R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_res handle_t_R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write(VSelf,handle_t v0){
+                    VSELF(handle_t);
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_impl(handle_borrow(*self),handle_borrow(v0));
+                    return (R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_res){
+                        
+                    };
+                }
+        
+    implExtern(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068,handle_t);
+    #endif
+    #if defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_IMPL) && !defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_IMPL_H)
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_IMPL_H
+    __attribute__((import_module("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230"))) extern uint32_t R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_push(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *rid);
+    uint32_t R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_ref(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 rid){
+        R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *p = malloc(sizeof(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068));
+        if (!p) abort();
+        *p = rid;
+        return R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_push(p);
+    }
+    __attribute__((export_name("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230.drop"))) void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_drop(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *rid){
+        VCALL_SUPER(*rid,Droppable,drop);
+        free(rid->self);
+        free(rid);
+    }
+    
+                __attribute__((export_name("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/give"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me,uint32_t v0){
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_res res = VCALL(*me,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give,DYN(handle_t,Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd,handle_box(v0)));
+                    return;
+                }
+        
+
+                __attribute__((export_name("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/read"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me,uint32_t v0){
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_res res = VCALL(*me,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read,v0);
+                    return handle_borrow(res.v0);
+                }
+        
+
+                __attribute__((export_name("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/seek"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me,uint64_t v0,float v1){
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_res res = VCALL(*me,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek,v0,v1);
+                    return (R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres){ res.v0,res.v1 };
+                }
+        
+
+                __attribute__((export_name("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/split"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me){
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_res res = VCALL(*me,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split);
+                    return R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_ref(res.v0);
+                }
+        
+
+                __attribute__((export_name("tpit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/write"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_fres R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me,uint32_t v0){
+                    handle_t p0 = handle_new(v0);
+                    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_res res = VCALL(*me,R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write,p0);
+                    handle_release(p0);
+                    return;
+                }
+        
+    #endif
+    
\ No newline at end of file
@@ -0,0 +1,151 @@
+
+    #ifndef R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_HOST_H
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_HOST_H
+    #include <pit_host.h>
+    #include <stdint.h>
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID "5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"
+    void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_host_emit(pit_host_t *host, const wasm_importtype_vec_t *imports, wasm_extern_t **externs);
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_type(void);
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give(pit_host_t *host, wasm_ref_t *self, wasm_ref_t *v0);
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_type(void);
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read(pit_host_t *host, wasm_ref_t *self, uint32_t v0, wasm_ref_t **r0);
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_type(void);
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek(pit_host_t *host, wasm_ref_t *self, uint64_t v0, float v1, uint64_t *r0, double *r1);
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_type(void);
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split(pit_host_t *host, wasm_ref_t *self, wasm_ref_t **r0);
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_type(void);
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write(pit_host_t *host, wasm_ref_t *self, wasm_ref_t *v0);
+    #endif
+    #if defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_HOST_IMPL) && !defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_HOST_IMPL_H)
+    #define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_HOST_IMPL_H
+    /* Method names, in sorted order. */
+    static const char *const R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_methods[5] = {"give", "read", "seek", "split", "write"};
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_type(void){
+        wasm_valtype_vec_t params, results;
+        wasm_valtype_t *params_[] = {wasm_valtype_new(PIT_HOST_REF_KIND), wasm_valtype_new(PIT_HOST_REF_KIND)};
+        wasm_valtype_vec_new(&params, 2, params_);
+        wasm_valtype_vec_new_empty(&results);
+        return wasm_functype_new(&params, &results);
+    }
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give(pit_host_t *host, wasm_ref_t *self, wasm_ref_t *v0){
+        pit_resource_t *res = pit_host_resource(self, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID);
+        if (!res) return pit_host_trap(host, "pit: resource does not implement pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068");
+        wasm_val_t args_[] = {{.kind = PIT_HOST_REF_KIND, .of = {.ref = v0}}};
+        wasm_val_vec_t args = WASM_ARRAY_VEC(args_);
+        wasm_val_vec_t rets = WASM_EMPTY_VEC;
+        wasm_trap_t *trap = res->call(res, 0, &args, &rets);
+        if (trap) return trap;
+        
+        return NULL;
+    }
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_type(void){
+        wasm_valtype_vec_t params, results;
+        wasm_valtype_t *params_[] = {wasm_valtype_new(PIT_HOST_REF_KIND), wasm_valtype_new(WASM_I32)};
+        wasm_valtype_vec_new(&params, 2, params_);
+        wasm_valtype_t *results_[] = {wasm_valtype_new(PIT_HOST_REF_KIND)};
+        wasm_valtype_vec_new(&results, 1, results_);
+        return wasm_functype_new(&params, &results);
+    }
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read(pit_host_t *host, wasm_ref_t *self, uint32_t v0, wasm_ref_t **r0){
+        pit_resource_t *res = pit_host_resource(self, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID);
+        if (!res) return pit_host_trap(host, "pit: resource does not implement pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068");
+        wasm_val_t args_[] = {WASM_I32_VAL((int32_t)v0)};
+        wasm_val_vec_t args = WASM_ARRAY_VEC(args_);
+        wasm_val_t rets_[] = {WASM_INIT_VAL};
+        wasm_val_vec_t rets = WASM_ARRAY_VEC(rets_);
+        wasm_trap_t *trap = res->call(res, 1, &args, &rets);
+        if (trap) return trap;
+        if (r0) *r0 = rets_[0].of.ref;
+        return NULL;
+    }
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_type(void){
+        wasm_valtype_vec_t params, results;
+        wasm_valtype_t *params_[] = {wasm_valtype_new(PIT_HOST_REF_KIND), wasm_valtype_new(WASM_I64), wasm_valtype_new(WASM_F32)};
+        wasm_valtype_vec_new(&params, 3, params_);
+        wasm_valtype_t *results_[] = {wasm_valtype_new(WASM_I64), wasm_valtype_new(WASM_F64)};
+        wasm_valtype_vec_new(&results, 2, results_);
+        return wasm_functype_new(&params, &results);
+    }
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek(pit_host_t *host, wasm_ref_t *self, uint64_t v0, float v1, uint64_t *r0, double *r1){
+        pit_resource_t *res = pit_host_resource(self, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID);
+        if (!res) return pit_host_trap(host, "pit: resource does not implement pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068");
+        wasm_val_t args_[] = {WASM_I64_VAL((int64_t)v0), WASM_F32_VAL(v1)};
+        wasm_val_vec_t args = WASM_ARRAY_VEC(args_);
+        wasm_val_t rets_[] = {WASM_INIT_VAL, WASM_INIT_VAL};
+        wasm_val_vec_t rets = WASM_ARRAY_VEC(rets_);
+        wasm_trap_t *trap = res->call(res, 2, &args, &rets);
+        if (trap) return trap;
+        if (r0) *r0 = (uint64_t)rets_[0].of.i64;
+        if (r1) *r1 = rets_[1].of.f64;
+        return NULL;
+    }
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_type(void){
+        wasm_valtype_vec_t params, results;
+        wasm_valtype_t *params_[] = {wasm_valtype_new(PIT_HOST_REF_KIND)};
+        wasm_valtype_vec_new(&params, 1, params_);
+        wasm_valtype_t *results_[] = {wasm_valtype_new(PIT_HOST_REF_KIND)};
+        wasm_valtype_vec_new(&results, 1, results_);
+        return wasm_functype_new(&params, &results);
+    }
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split(pit_host_t *host, wasm_ref_t *self, wasm_ref_t **r0){
+        pit_resource_t *res = pit_host_resource(self, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID);
+        if (!res) return pit_host_trap(host, "pit: resource does not implement pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068");
+        wasm_val_vec_t args = WASM_EMPTY_VEC;
+        wasm_val_t rets_[] = {WASM_INIT_VAL};
+        wasm_val_vec_t rets = WASM_ARRAY_VEC(rets_);
+        wasm_trap_t *trap = res->call(res, 3, &args, &rets);
+        if (trap) return trap;
+        if (r0) *r0 = rets_[0].of.ref;
+        return NULL;
+    }
+    wasm_functype_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_type(void){
+        wasm_valtype_vec_t params, results;
+        wasm_valtype_t *params_[] = {wasm_valtype_new(PIT_HOST_REF_KIND), wasm_valtype_new(PIT_HOST_REF_KIND)};
+        wasm_valtype_vec_new(&params, 2, params_);
+        wasm_valtype_vec_new_empty(&results);
+        return wasm_functype_new(&params, &results);
+    }
+    wasm_trap_t *R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write(pit_host_t *host, wasm_ref_t *self, wasm_ref_t *v0){
+        pit_resource_t *res = pit_host_resource(self, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID);
+        if (!res) return pit_host_trap(host, "pit: resource does not implement pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068");
+        wasm_val_t args_[] = {{.kind = PIT_HOST_REF_KIND, .of = {.ref = v0}}};
+        wasm_val_vec_t args = WASM_ARRAY_VEC(args_);
+        wasm_val_vec_t rets = WASM_EMPTY_VEC;
+        wasm_trap_t *trap = res->call(res, 4, &args, &rets);
+        if (trap) return trap;
+        
+        return NULL;
+    }
+    void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_host_emit(pit_host_t *host, const wasm_importtype_vec_t *imports, wasm_extern_t **externs){
+        for (size_t i = 0; i < imports->size; i++) {
+            if (!pit_host_name_eq(wasm_importtype_module(imports->data[i]), "pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068")) continue;
+            const wasm_name_t *name = wasm_importtype_name(imports->data[i]);
+            wasm_func_t *f = NULL;
+            if (name->size && name->data[0] == '~') {
+                f = pit_host_ctor(host, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_methods, 5, name);
+            } else if (pit_host_name_eq(name, "give")) {
+                wasm_functype_t *type = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_type();
+                f = pit_host_method(host, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID, 0, type);
+                wasm_functype_delete(type);
+            } else if (pit_host_name_eq(name, "read")) {
+                wasm_functype_t *type = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_type();
+                f = pit_host_method(host, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID, 1, type);
+                wasm_functype_delete(type);
+            } else if (pit_host_name_eq(name, "seek")) {
+                wasm_functype_t *type = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_type();
+                f = pit_host_method(host, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID, 2, type);
+                wasm_functype_delete(type);
+            } else if (pit_host_name_eq(name, "split")) {
+                wasm_functype_t *type = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_type();
+                f = pit_host_method(host, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID, 3, type);
+                wasm_functype_delete(type);
+            } else if (pit_host_name_eq(name, "write")) {
+                wasm_functype_t *type = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_type();
+                f = pit_host_method(host, R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_RID, 4, type);
+                wasm_functype_delete(type);
+            }
+            if (f) externs[i] = wasm_func_as_extern(f);
+        }
+    }
+    #endif
+    
\ No newline at end of file
@@ -0,0 +1,18 @@
+/*
+ * Stand-in for interface99 in the tests of pit-c, which only check that generated
+ * code compiles. Each macro expands to a declaration or expression of the type the
+ * real one produces, without building any virtual tables.
+ */
+#ifndef INTERFACE99_H
+#define INTERFACE99_H
+
+#define VSelf void *self_
+#define VSELF(T) T *self = (T *)(self_)
+#define interface(I) typedef struct I { void *self; const void *vptr; } I
+#define declImplExtern(I, T) extern const int T##_##I##_impl
+#define implExtern(I, T) const int T##_##I##_impl = 0
+#define DYN(T, I, p) ((I){ .self = (p), .vptr = 0 })
+#define VCALL(obj, f, ...) ((f##_res(*)(void *, ...))0)((obj).self, ##__VA_ARGS__)
+#define VCALL_SUPER(obj, I, f) ((void (*)(void *))0)((obj).self)
+
+#endif
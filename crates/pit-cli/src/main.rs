@@ -10,10 +10,16 @@
 //! - `untpit` - Convert TPIT module to externref-based PIT
 //! - `jigger` - Generate unique IDs based on module content
 //! - `lower` - Lower externref types to table indices
+//! - `unmangle` - Rename mangled exports, as from AssemblyScript guests, to PIT export names
 //! - `embed` - Embed interface definitions in a module
 //! - `rust-guest` - Generate Rust guest bindings
-//! - `teavm` - Generate Scala/TeaVM bindings
-//! - `gen-c` - Generate C header files
+//! - `teavm` - Generate Scala/TeaVM bindings (`-java` for Java, `-binders <file>` to load binders)
+//! - `gen-c` - Generate C header files (`-tpit` for TPIT, `-salt`/`-name` for extra implementations), and optionally the C runtime (`-runtime <dir>`)
+//! - `gen-c-host` - Generate host-side C code over the wasm C API, and optionally its runtime (`-runtime <dir>`)
+//! - `gen-cpp` - Generate C++ header files, with the same options as `gen-c`
+//! - `gen-js` - Generate a JavaScript host module and its `.d.ts`, and optionally the JS runtime (`-runtime <dir>`)
+//! - `gen-as` - Generate AssemblyScript guest bindings (`-salt`/`-name` for extra implementations), and optionally the AssemblyScript runtime (`-runtime <dir>`)
+//! - `gen-go` - Generate Go guest bindings using TPIT handles (`-pkg`/`-rt` for the package and runtime import path, `-salt`/`-name` for extra implementations), and optionally the Go runtime (`-runtime <dir>`)
 //! - `package` - Generate a complete multi-language package
 //! - `hash` - Compute interface hash
 //!
@@ -71,6 +77,66 @@ fn extract_top_doc_comments(path: &str) -> Option<String> {
         Some(doc_lines.join("\n"))
     }
 }
+
+/// Loads TeaVM binders from a file.
+///
+/// Each non-empty line not starting with `#` declares one binder as
+/// `<expose|import> <name> <package> <signature> <instances>`, where the signature is in
+/// PIT syntax and the instances are the comma-separated instance specifications the
+/// binder is given for, as in `[instance=...]` annotations.
+fn load_binders(path: &str) -> anyhow::Result<pit_teavm::Binders> {
+    let src = std::fs::read_to_string(path)?;
+    let mut binders = pit_teavm::Binders::default();
+    for (n, line) in src.lines().enumerate() {
+        let n = n + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("#") {
+            continue;
+        }
+        let mut rest = line;
+        let mut word = || {
+            let (a, b) = rest.split_once(char::is_whitespace)?;
+            rest = b.trim_start();
+            Some(a)
+        };
+        let expected = || {
+            format!("{path}:{n}: expected <expose|import> <name> <package> <signature> <instances>")
+        };
+        let exp = match word().with_context(expected)? {
+            "expose" => pit_teavm::Exposition::Expose,
+            "import" => pit_teavm::Exposition::Import,
+            e => anyhow::bail!("{path}:{n}: invalid exposition {e} (expected expose or import)"),
+        };
+        let name = word().with_context(expected)?;
+        let pkg = word().with_context(expected)?;
+        let Ok((cfg, sig)) = pit_core::parse_sig(rest) else {
+            anyhow::bail!("{path}:{n}: invalid signature");
+        };
+        let cfg = cfg.trim();
+        if cfg.is_empty() {
+            anyhow::bail!(expected());
+        }
+        binders.insert(
+            (name.to_owned(), pkg.to_owned(), exp),
+            (sig, cfg.to_owned()),
+        );
+    }
+    Ok(binders)
+}
+
+/// Renders the binder traits of a set of binders, as `(package, name, source)`.
+fn binder_sigs(binders: &pit_teavm::Binders) -> BTreeSet<(String, String, String)> {
+    binders
+        .iter()
+        .map(|((name, pkg, _), (sig, _))| {
+            (
+                pkg.clone(),
+                name.clone(),
+                pit_teavm::emit_binder_sig(sig, name, pkg),
+            )
+        })
+        .collect()
+}
 fn main() -> anyhow::Result<()> {
     let mut args = std::env::args();
     args.next();
@@ -117,6 +183,14 @@ fn main() -> anyhow::Result<()> {
             tree_shake(&mut m)?;
             std::fs::write(b, m.to_wasm_bytes()?)?;
         }
+        "unmangle" => {
+            let a = args.next().context("in getting the input")?;
+            let a = std::fs::read(a)?;
+            let mut m = portal_pc_waffle::Module::from_wasm_bytes(&a, &Default::default())?;
+            pit_patch::canon::unmangle(&mut m)?;
+            let b = args.next().context("in getting the output")?;
+            std::fs::write(b, m.to_wasm_bytes()?)?;
+        }
         "embed" => {
             let a = args.next().context("in getting the input")?;
             let a = std::fs::read(a)?;
@@ -174,12 +248,7 @@ fn main() -> anyhow::Result<()> {
                     opts.tpit = false;
                 }
                 if c == "salt" {
-                    opts.salt.extend(
-                        args.next()
-                            .iter()
-                            .flat_map(|a| a.as_bytes().iter())
-                            .cloned(),
-                    );
+                    opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
                 }
                 if c == "root" {
                     opts.root = syn::parse_str(&args.next().context("in getting the root")?)?;
@@ -212,6 +281,7 @@ fn main() -> anyhow::Result<()> {
             };
             let mut pkg = format!("pc.portal.pit.guest");
             let mut binders = pit_teavm::Binders::default();
+            let mut java = false;
             let b = loop {
                 let b = args.next().context("in getting the output")?;
                 let Some(c) = b.strip_prefix("-") else {
@@ -220,10 +290,30 @@ fn main() -> anyhow::Result<()> {
                 if c == "pkg" {
                     pkg = args.next().context("in getting the package")?;
                 }
+                if c == "java" {
+                    java = true;
+                }
+                if c == "binders" {
+                    binders = load_binders(&args.next().context("in getting the binders")?)?;
+                }
+            };
+            let a = if java {
+                pit_teavm::java::emit(&a, &pkg)?
+            } else {
+                pit_teavm::emit(&a, &pkg, &binders)
             };
-            let a = pit_teavm::emit(&a, &pkg, &binders);
             // let a = syn::parse2(a)?;
-            std::fs::write(b, a)?;
+            std::fs::write(&b, a)?;
+            let dir = std::path::Path::new(&b)
+                .parent()
+                .map(|d| d.to_string_lossy().into_owned())
+                .filter(|d| !d.is_empty())
+                .unwrap_or(format!("."));
+            if !java {
+                for (_, name, src) in binder_sigs(&binders) {
+                    std::fs::write(format!("{dir}/{name}.scala"), src)?;
+                }
+            }
         }
         "gen-c" => {
             let a = args.next().context("in getting the input")?;
@@ -233,6 +323,12 @@ fn main() -> anyhow::Result<()> {
             };
             // let mut pkg = format!("pc.portal.pit.guest");
             // let mut binders = pit_teavm::Binders::default();
+            let mut runtime = None;
+            let mut c_opts = pit_c::Opts {
+                tpit: false,
+                salt: vec![],
+                name: None,
+            };
             let b = loop {
                 let b = args.next().context("in getting the output")?;
                 let Some(c) = b.strip_prefix("-") else {
@@ -241,10 +337,190 @@ fn main() -> anyhow::Result<()> {
                 // if c == "pkg" {
                 //     pkg = args.next().context("in getting the package")?;
                 // }
+                if c == "runtime" {
+                    runtime = Some(args.next().context("in getting the runtime directory")?);
+                }
+                if c == "tpit" {
+                    c_opts.tpit = true;
+                }
+                if c == "salt" {
+                    c_opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
+                }
+                if c == "name" {
+                    c_opts.name = Some(args.next().context("in getting the name")?);
+                }
             };
-            let a = pit_c::cify(&a);
+            let a = pit_c::cify(&c_opts, &a);
             // let a = syn::parse2(a)?;
             std::fs::write(b, a)?;
+            if let Some(r) = runtime {
+                for (name, src) in pit_c::runtime() {
+                    std::fs::write(format!("{r}/{name}"), src)?;
+                }
+            }
+        }
+        "gen-c-host" => {
+            let a = args.next().context("in getting the input")?;
+            let a = std::fs::read_to_string(a)?;
+            let Ok((_, a)) = pit_core::parse_interface(&a) else {
+                anyhow::bail!("invalid interface");
+            };
+            let mut runtime = None;
+            let b = loop {
+                let b = args.next().context("in getting the output")?;
+                let Some(c) = b.strip_prefix("-") else {
+                    break b;
+                };
+                if c == "runtime" {
+                    runtime = Some(args.next().context("in getting the runtime directory")?);
+                }
+            };
+            let a = pit_c::host::cify(&a);
+            std::fs::write(b, a)?;
+            if let Some(r) = runtime {
+                for (name, src) in pit_c::host::runtime() {
+                    std::fs::write(format!("{r}/{name}"), src)?;
+                }
+            }
+        }
+        "gen-cpp" => {
+            let a = args.next().context("in getting the input")?;
+            let a = std::fs::read_to_string(a)?;
+            let Ok((_, a)) = pit_core::parse_interface(&a) else {
+                anyhow::bail!("invalid interface");
+            };
+            let mut runtime = None;
+            let mut cpp_opts = pit_cpp::Opts {
+                tpit: false,
+                salt: vec![],
+                name: None,
+            };
+            let b = loop {
+                let b = args.next().context("in getting the output")?;
+                let Some(c) = b.strip_prefix("-") else {
+                    break b;
+                };
+                if c == "runtime" {
+                    runtime = Some(args.next().context("in getting the runtime directory")?);
+                }
+                if c == "tpit" {
+                    cpp_opts.tpit = true;
+                }
+                if c == "salt" {
+                    cpp_opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
+                }
+                if c == "name" {
+                    cpp_opts.name = Some(args.next().context("in getting the name")?);
+                }
+            };
+            let a = pit_cpp::cppify(&cpp_opts, &a);
+            std::fs::write(b, a)?;
+            if let Some(r) = runtime {
+                for (name, src) in pit_cpp::runtime() {
+                    std::fs::write(format!("{r}/{name}"), src)?;
+                }
+            }
+        }
+        "gen-js" => {
+            let a = args.next().context("in getting the input")?;
+            let a = std::fs::read_to_string(a)?;
+            let Ok((_, a)) = pit_core::parse_interface(&a) else {
+                anyhow::bail!("invalid interface");
+            };
+            let mut runtime = None;
+            let b = loop {
+                let b = args.next().context("in getting the output")?;
+                let Some(c) = b.strip_prefix("-") else {
+                    break b;
+                };
+                if c == "runtime" {
+                    runtime = Some(args.next().context("in getting the runtime directory")?);
+                }
+            };
+            std::fs::write(&b, pit_js::emit(&a))?;
+            let dts = format!("{}.d.ts", b.strip_suffix(".js").unwrap_or(&b));
+            std::fs::write(dts, pit_js::emit_dts(&a))?;
+            if let Some(r) = runtime {
+                for (name, src) in pit_js::runtime() {
+                    std::fs::write(format!("{r}/{name}"), src)?;
+                }
+            }
+        }
+        "gen-as" => {
+            let a = args.next().context("in getting the input")?;
+            let a = std::fs::read_to_string(a)?;
+            let Ok((_, a)) = pit_core::parse_interface(&a) else {
+                anyhow::bail!("invalid interface");
+            };
+            let mut runtime = None;
+            let mut as_opts = pit_as::Opts {
+                salt: vec![],
+                name: None,
+            };
+            let b = loop {
+                let b = args.next().context("in getting the output")?;
+                let Some(c) = b.strip_prefix("-") else {
+                    break b;
+                };
+                if c == "runtime" {
+                    runtime = Some(args.next().context("in getting the runtime directory")?);
+                }
+                if c == "salt" {
+                    as_opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
+                }
+                if c == "name" {
+                    as_opts.name = Some(args.next().context("in getting the name")?);
+                }
+            };
+            std::fs::write(b, pit_as::asify(&as_opts, &a)?)?;
+            if let Some(r) = runtime {
+                for (name, src) in pit_as::runtime() {
+                    std::fs::write(format!("{r}/{name}"), src)?;
+                }
+            }
+        }
+        "gen-go" => {
+            let a = args.next().context("in getting the input")?;
+            let a = std::fs::read_to_string(a)?;
+            let Ok((_, a)) = pit_core::parse_interface(&a) else {
+                anyhow::bail!("invalid interface");
+            };
+            let mut runtime = None;
+            let mut rt = None;
+            let mut go_opts = pit_go::Opts {
+                pkg: format!("guest"),
+                rt: String::new(),
+                salt: vec![],
+                name: None,
+            };
+            let b = loop {
+                let b = args.next().context("in getting the output")?;
+                let Some(c) = b.strip_prefix("-") else {
+                    break b;
+                };
+                if c == "runtime" {
+                    runtime = Some(args.next().context("in getting the runtime directory")?);
+                }
+                if c == "pkg" {
+                    go_opts.pkg = args.next().context("in getting the package")?;
+                }
+                if c == "rt" {
+                    rt = Some(args.next().context("in getting the runtime import path")?);
+                }
+                if c == "salt" {
+                    go_opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
+                }
+                if c == "name" {
+                    go_opts.name = Some(args.next().context("in getting the name")?);
+                }
+            };
+            go_opts.rt = rt.context("in getting the runtime import path (-rt <path>)")?;
+            std::fs::write(b, pit_go::goify(&go_opts, &a)?)?;
+            if let Some(r) = runtime {
+                for (name, src) in pit_go::runtime() {
+                    std::fs::write(format!("{r}/{name}"), src)?;
+                }
+            }
         }
         "package" => {
             let a = args.next().context("in getting the input")?;
@@ -261,6 +537,17 @@ fn main() -> anyhow::Result<()> {
                 salt: vec![],
                 tpit: true,
             };
+            let mut c_opts = pit_c::Opts {
+                tpit: false,
+                salt: vec![],
+                name: None,
+            };
+            let mut as_opts = pit_as::Opts {
+                salt: vec![],
+                name: None,
+            };
+            let mut go_module = None;
+            let mut go_salt = vec![];
             let b = loop {
                 let b = args.next().context("in getting the output")?;
                 let Some(c) = b.strip_prefix("-") else {
@@ -269,40 +556,129 @@ fn main() -> anyhow::Result<()> {
                 if c == "scala/pkg" {
                     pkg = args.next().context("in getting the package")?;
                 };
+                if c == "scala/binders" {
+                    binders = load_binders(&args.next().context("in getting the binders")?)?;
+                }
                 if c == "rust/extern-externref" {
                     opts.tpit = false;
                 }
                 if c == "rust/salt" {
-                    opts.salt.extend(
-                        args.next()
-                            .iter()
-                            .flat_map(|a| a.as_bytes().iter())
-                            .cloned(),
-                    );
+                    opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
                 }
                 if c == "rust/root" {
                     opts.root = syn::parse_str(&args.next().context("in getting the root")?)?;
                 }
+                if c == "c/tpit" {
+                    c_opts.tpit = true;
+                }
+                if c == "c/salt" {
+                    c_opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
+                }
+                if c == "as/salt" {
+                    as_opts.salt.extend(args.next().context("in getting the salt")?.into_bytes());
+                }
+                if c == "go/module" {
+                    go_module = Some(args.next().context("in getting the module path")?);
+                }
+                if c == "go/salt" {
+                    go_salt.extend(args.next().context("in getting the salt")?.into_bytes());
+                }
             };
             let rid = a.rid_str();
             let a2 = pit_rust_guest::render(&opts, &a);
             let a2 = syn::parse2(a2)?;
             std::fs::write(format!("{b}/src/lib.rs"), prettyplease::unparse(&a2))?;
             let a2 = pit_teavm::emit(&a, &pkg, &binders);
-            let scala_path = format!("{}/R{}.scala",a.rid_str(),pkg.replace(".", "/"));
+            let scala_path = format!("scala/{}/R{}.scala",pkg.replace(".", "/"),a.rid_str());
+            std::fs::create_dir_all(format!("{b}/scala/{}",pkg.replace(".", "/")))?;
             std::fs::write(format!("{b}/{}",scala_path), a2)?;
-            std::fs::write(format!("{b}/R{}.h",a.rid_str()), pit_c::cify(&a))?;
+            let mut scala_srcs = vec![scala_path];
+            for (bpkg, name, src) in binder_sigs(&binders) {
+                let dir = format!("scala/{}", bpkg.replace(".", "/"));
+                std::fs::create_dir_all(format!("{b}/{dir}"))?;
+                std::fs::write(format!("{b}/{dir}/{name}.scala"), src)?;
+                scala_srcs.push(format!("{dir}/{name}.scala"));
+            }
+            let scala_srcs = scala_srcs.iter().map(|s| format!("\"{s}\",")).collect::<Vec<_>>().join("\n                    ");
+            // Targets that cannot express the interface are left out of the package.
+            let java = match pit_teavm::java::emit(&a, &pkg) {
+                Ok(java) => Some(java),
+                Err(e) => {
+                    eprintln!("warning: skipping Java: {e}");
+                    None
+                }
+            };
+            if let Some(java) = &java {
+                let java_dir = format!("{b}/java/{}", pkg.replace(".", "/"));
+                std::fs::create_dir_all(&java_dir)?;
+                std::fs::write(format!("{java_dir}/R{rid}.java"), java)?;
+                for (name, src) in pit_teavm::java::runtime() {
+                    let path = format!("{b}/java/{name}");
+                    if let Some((dir, _)) = path.rsplit_once("/") {
+                        std::fs::create_dir_all(dir)?;
+                    }
+                    std::fs::write(path, src)?;
+                }
+            }
+            std::fs::write(format!("{b}/R{}.h",a.rid_str()), pit_c::cify(&c_opts, &a))?;
+            for (name, src) in pit_c::runtime() {
+                std::fs::write(format!("{b}/{name}"), src)?;
+            }
+            match pit_as::asify(&as_opts, &a) {
+                Ok(ts) => {
+                    std::fs::create_dir_all(format!("{b}/as"))?;
+                    std::fs::write(format!("{b}/as/R{rid}.ts"), ts)?;
+                    for (name, src) in pit_as::runtime() {
+                        std::fs::write(format!("{b}/as/{name}"), src)?;
+                    }
+                }
+                Err(e) => eprintln!("warning: skipping AssemblyScript: {e}"),
+            }
+            let go_module = go_module.unwrap_or(format!("r{rid}"));
+            let go_opts = pit_go::Opts {
+                pkg: format!("r{rid}"),
+                rt: format!("{go_module}/tpit"),
+                salt: go_salt,
+                name: None,
+            };
+            match pit_go::goify(&go_opts, &a) {
+                Ok(go) => {
+                    std::fs::create_dir_all(format!("{b}/go/tpit"))?;
+                    std::fs::write(format!("{b}/go/R{rid}.go"), go)?;
+                    for (name, src) in pit_go::runtime() {
+                        std::fs::write(format!("{b}/go/tpit/{name}"), src)?;
+                    }
+                    std::fs::write(format!("{b}/go/go.mod"), format!("module {go_module}\n\ngo 1.24\n"))?;
+                }
+                Err(e) => eprintln!("warning: skipping Go: {e}"),
+            }
+            let c_prefix = pit_c::prefix(&c_opts, &a);
             std::fs::write(format!("{b}/R{rid}.c"), format!(r#"
-            #define R{rid}_IMPL
+            #define R{rid}_IMPORTS
+            #define {c_prefix}_IMPL
             #include <R{rid}.h>
             "#))?;
+            let c_flag = if c_opts.tpit {
+                "-DPIT_TPIT"
+            } else {
+                "-mreference-types"
+            };
             let bazel_cc = format!(r#"
+            cc_library(
+                name = "wasm_handler",
+                srcs = ["handle.c"],
+                hdrs = ["handle.h"],
+                copts = ["{c_flag}"],
+                includes = ["."],
+                visibility = ["//visibility:public"],
+            )
             cc_library(
                 name = "r{rid}",
                 srcs = ["R{rid}.c"],
                 hdrs = ["R{rid}.h"],
+                copts = ["{c_flag}"],
                 visibility = ["//visibility:public"],
-                deps = ["@wasm_handler"]
+                deps = [":wasm_handler"]
             )
             "#);
             let crate_name = format!("pit-autogen-{}-{}",base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(a.rid()),if opts.tpit{
@@ -344,7 +720,7 @@ fn main() -> anyhow::Result<()> {
             scala_library(
                 name = "teavm-{rid}",
                 srcs = [
-                    "{scala_path}",
+                    {scala_srcs}
                 ],
                 deps = [
                     "@org-teavm-teavm-interop"
@@ -358,9 +734,46 @@ fn main() -> anyhow::Result<()> {
             std::fs::write(format!("{b}/build.sbt"), format!(r#"
             // https://mvnrepository.com/artifact/org.teavm/teavm-interop
             ThisBuild / scalaVersion := "3.3.3"
+            Compile / scalaSource := baseDirectory.value / "scala"
             libraryDependencies += "org.teavm" % "teavm-interop" % "{TEAVM_INTEROP_VER}"
             "#))?;
+            if java.is_some() {
+                std::fs::write(format!("{b}/settings.gradle"), format!(r#"
+                rootProject.name = "teavm-{rid}"
+                "#))?;
+                std::fs::write(format!("{b}/build.gradle"), format!(r#"
+                plugins {{
+                    id 'java-library'
+                }}
+                repositories {{
+                    mavenCentral()
+                }}
+                dependencies {{
+                    // https://mvnrepository.com/artifact/org.teavm/teavm-interop
+                    api 'org.teavm:teavm-interop:{TEAVM_INTEROP_VER}'
+                }}
+                sourceSets {{
+                    main {{
+                        java {{
+                            srcDirs = ['java']
+                        }}
+                    }}
+                }}
+                "#))?;
+            }
             std::fs::write(format!("{b}/CMakeLists.txt"), format!(r#"
+            if(NOT TARGET wasm_handler)
+                if(NOT DEFINED INTERFACE99_INCLUDE_DIRS)
+                    include(FetchContent)
+                    FetchContent_Declare(metalang99 GIT_REPOSITORY https://github.com/Hirrolot/metalang99.git)
+                    FetchContent_Declare(interface99 GIT_REPOSITORY https://github.com/Hirrolot/interface99.git)
+                    FetchContent_MakeAvailable(metalang99 interface99)
+                    set(INTERFACE99_INCLUDE_DIRS ${{interface99_SOURCE_DIR}} ${{metalang99_SOURCE_DIR}}/include)
+                endif()
+                add_library(wasm_handler STATIC handle.c)
+                target_include_directories(wasm_handler PUBLIC ${{CMAKE_CURRENT_SOURCE_DIR}} ${{INTERFACE99_INCLUDE_DIRS}})
+                target_compile_options(wasm_handler PUBLIC {c_flag})
+            endif()
             add_library(r{rid} STATIC R{rid}.c)
             target_include_directories(r{rid} PUBLIC ${{CMAKE_CURRENT_SOURCE_DIR}})
             target_link_libraries(r{rid} PUBLIC wasm_handler)
@@ -377,7 +790,7 @@ fn main() -> anyhow::Result<()> {
             }
         }
         _ => anyhow::bail!(
-            "invalid command (valid ones are: untpit, jigger, lower, rust-guest, teavm, embed, hash, gen-c)"
+            "invalid command (valid ones are: untpit, jigger, lower, unmangle, rust-guest, teavm, embed, hash, gen-c, gen-c-host, gen-cpp, gen-js, gen-as, gen-go, package)"
         ),
     };
     Ok(())
@@ -0,0 +1,493 @@
+//! # PIT C++ Code Generator
+//!
+//! Generates C++ header files from PIT interface definitions.
+//!
+//! This crate provides functionality to convert PIT interfaces into C++ headers that
+//! can be used to implement or consume PIT resources from C++ code.
+//!
+//! ## Generated Code
+//!
+//! For each PIT interface, this crate generates:
+//! - An abstract class `R{rid}` with a pure virtual function per method
+//! - A move-only `R{rid}Handle` implementing it over an imported resource
+//! - A CRTP helper, `R{rid}Export`, for exporting implementations
+//! - Import/export function declarations with proper WebAssembly attributes
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use pit_core::parse_interface;
+//! use pit_cpp::{cppify, Opts};
+//!
+//! let interface_str = "{ method(I32) -> (I64); }";
+//! let (_, interface) = parse_interface(interface_str).unwrap();
+//! let cpp_header = cppify(&Opts { tpit: false, salt: vec![], name: None }, &interface);
+//! ```
+//!
+//! ## Dependencies
+//!
+//! The generated C++ code requires C++17 and `pit.hpp`, provided by [`runtime`]. Build
+//! with clang for wasm32 with `-mreference-types`, or with `PIT_TPIT` defined for
+//! headers generated with [`Opts::tpit`], which are converted to PIT ABI v1 with
+//! `pit untpit` like Rust guests. Methods with more than one result need the
+//! multivalue ABI (`-mmultivalue -Xclang -target-abi -Xclang experimental-mv`), and
+//! outside of TPIT cannot return resources.
+//!
+//! ## Types
+//!
+//! Untyped resources are `pit::Handle`s, from the [`runtime`]: RAII handles dropping
+//! the resource through `pit.drop`. Resources of an interface are its `R{rid}Handle`s.
+//! Both are move-only. Nullable resources are `std::optional`s, borrowed ones references, and
+//! methods with several results return a `std::tuple`.
+//!
+//! ## Implementations
+//!
+//! Derive from `R{rid}Export<Impl>` and call `Impl::make(args...)` to construct an
+//! implementation and export it, getting a handle to it. Exporting needs the
+//! implementation's definitions, emitted by defining `R{rid}_IMPL` before including
+//! the header in one translation unit. As with `pit-c`, [`Opts::salt`] varies the
+//! constructor ID, and [`Opts::name`] the prefix of `{prefix}Export`, `{prefix}_ref`
+//! and `{prefix}_IMPL`, so that several implementations can be linked together.
+
+use itertools::Itertools;
+use pit_core::{Arg, Interface, ResTy};
+use pit_patch_core::names;
+use sha3::Digest;
+use std::iter::once;
+
+/// Options for C++ code generation.
+pub struct Opts {
+    /// Whether to use TPIT (`uint32_t` handles, converted by `pit untpit`) or native externref.
+    pub tpit: bool,
+    /// Additional bytes to include in the constructor ID hash for disambiguation.
+    pub salt: Vec<u8>,
+    /// Names the implementation, prefixing its symbols with `R{rid}_{name}` instead of `R{rid}`.
+    pub name: Option<String>,
+}
+
+/// Generates a C++ header file from a PIT interface definition.
+///
+/// # Arguments
+///
+/// * `opts` - Code generation options
+/// * `i` - The PIT interface to convert
+///
+/// # Returns
+///
+/// A string containing the complete C++ header file content.
+pub fn cppify(opts: &Opts, i: &Interface) -> String {
+    let rid = i.rid_str();
+    let (module, t, mode) = if opts.tpit {
+        (
+            names::tpit_module_name(&rid),
+            "t",
+            format!("#ifndef PIT_TPIT\n#error \"R{rid}.hpp was generated for TPIT; define PIT_TPIT\"\n#endif"),
+        )
+    } else {
+        (
+            names::pit_module_name(&rid),
+            "",
+            format!("#ifdef PIT_TPIT\n#error \"R{rid}.hpp was generated for externref; do not define PIT_TPIT\"\n#endif"),
+        )
+    };
+    let p = match &opts.name {
+        Some(n) => format!("R{rid}_{n}"),
+        None => format!("R{rid}"),
+    };
+    let mut ha = sha3::Sha3_256::default();
+    ha.update(format!("~{rid}"));
+    ha.update(&opts.salt);
+    let ha = hex::encode(ha.finalize());
+    let includes = i
+        .methods
+        .values()
+        .flat_map(|b| b.params.iter().chain(b.rets.iter()))
+        .filter_map(|a| match a {
+            Arg::Resource {
+                ty: ResTy::Of(a), ..
+            } => Some(format!("#include <R{}.hpp>", hex::encode(a))),
+            _ => None,
+        })
+        .unique()
+        .join("\n");
+    let params = |b: &pit_core::Sig| {
+        b.params
+            .iter()
+            .enumerate()
+            .map(|(idx, a)| format!("{} p{idx}", param_ty(i, a)))
+            .join(", ")
+    };
+    let ffi_params = |b: &pit_core::Sig| {
+        b.params
+            .iter()
+            .enumerate()
+            .map(|(idx, a)| format!(", {} v{idx}", ffi_ty(a)))
+            .join("")
+    };
+    let types = i
+        .methods
+        .iter()
+        .map(|(a, b)| match b.rets.len() {
+            0 => format!("using R{rid}_{a}_fres = void;\n"),
+            1 => format!("using R{rid}_{a}_fres = {};\n", ffi_ty(&b.rets[0])),
+            _ if !opts.tpit && b.rets.iter().any(|a| matches!(a, Arg::Resource { .. })) => format!(
+                "#error \"R{rid}.{a}: several results including resources cannot cross the C ABI\"\n"
+            ),
+            _ => format!(
+                "struct R{rid}_{a}_fres {{\n{}}};\n",
+                b.rets
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, a)| format!("    {} v{idx};\n", ffi_ty(a)))
+                    .join("")
+            ),
+        })
+        .join("");
+    let virtuals = i
+        .methods
+        .iter()
+        .map(|(a, b)| format!("    virtual {} {a}({}) = 0;\n", ret_ty(i, b), params(b)))
+        .join("");
+    let overrides = i
+        .methods
+        .iter()
+        .map(|(a, b)| format!("    {} {a}({}) override;\n", ret_ty(i, b), params(b)))
+        .join("");
+    let imports = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let call = format!(
+                "R{rid}_{a}_import({})",
+                once("handle.borrow()".to_owned())
+                    .chain(b.params.iter().enumerate().map(|(idx, a)| match a {
+                        Arg::Resource { take, .. } => to_ffi(a, &format!("p{idx}"), *take),
+                        _ => format!("p{idx}"),
+                    }))
+                    .join(", ")
+            );
+            let body = match b.rets.len() {
+                0 => format!("    {call};\n"),
+                1 => format!(
+                    "    R{rid}_{a}_fres r = {call};\n    return {};\n",
+                    to_cpp(i, &b.rets[0], "r")
+                ),
+                _ => format!(
+                    "    R{rid}_{a}_fres r = {call};\n    return {}{{{}}};\n",
+                    ret_ty(i, b),
+                    b.rets
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, a)| to_cpp(i, a, &format!("r.v{idx}")))
+                        .join(", ")
+                ),
+            };
+            format!(
+                r#"extern "C" __attribute__((import_module("{module}"), import_name("{a}"))) R{rid}_{a}_fres
+R{rid}_{a}_import(pit::ref_t self{});
+inline {} R{rid}Handle::{a}({}) {{
+{body}}}
+"#,
+                ffi_params(b),
+                ret_ty(i, b),
+                params(b),
+            )
+        })
+        .join("");
+    let exports = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let export_name = format!("{t}{}", names::method_export_name(&rid, &ha, a));
+            let wrap = b
+                .params
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| matches!(a, Arg::Resource { .. }))
+                .map(|(idx, a)| format!("    auto p{idx} = {};\n", to_cpp(i, a, &format!("v{idx}"))))
+                .join("");
+            let call = format!(
+                "me->{a}({})",
+                b.params
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, a)| match a {
+                        Arg::Resource { take: true, .. } => format!("std::move(p{idx})"),
+                        Arg::Resource { .. } => format!("p{idx}"),
+                        _ => format!("v{idx}"),
+                    })
+                    .join(", ")
+            );
+            // Borrowed resources stay owned by the caller, so give them back without
+            // dropping, releasing the table slot `pit untpit` allocated for them in TPIT.
+            let unwrap = b
+                .params
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| matches!(a, Arg::Resource { take: false, .. }))
+                .map(|(idx, a)| {
+                    let r = to_ffi(a, &format!("p{idx}"), true);
+                    if opts.tpit {
+                        format!("    pit::detail::void_ref({r});\n")
+                    } else {
+                        format!("    (void){r};\n")
+                    }
+                })
+                .join("");
+            let body = match b.rets.len() {
+                0 => format!("{wrap}    {call};\n{unwrap}"),
+                1 => format!(
+                    "{wrap}    auto res = {call};\n{unwrap}    return {};\n",
+                    to_ffi(&b.rets[0], "res", true)
+                ),
+                _ => format!(
+                    "{wrap}    auto res = {call};\n{unwrap}    return R{rid}_{a}_fres{{{}}};\n",
+                    b.rets
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, a)| to_ffi(a, &format!("std::get<{idx}>(res)"), true))
+                        .join(", ")
+                ),
+            };
+            format!(
+                r#"extern "C" __attribute__((export_name("{export_name}"))) R{rid}_{a}_fres
+{p}_{a}_export(R{rid} *me{}) {{
+{body}}}
+"#,
+                ffi_params(b),
+            )
+        })
+        .join("");
+    let push_module = &module;
+    let push_name = names::constructor_import_name(&ha);
+    let drop_name = format!("{t}{}", names::drop_export_name(&rid, &ha));
+    format!(
+        r#"#ifndef R{rid}_HPP
+#define R{rid}_HPP
+{mode}
+#include <cstdint>
+#include <memory>
+#include <optional>
+#include <pit.hpp>
+#include <tuple>
+{includes}
+{types}
+class R{rid}Handle;
+
+class R{rid} {{
+  public:
+    virtual ~R{rid}() = default;
+{virtuals}}};
+
+class R{rid}Handle final : public R{rid} {{
+  public:
+    pit::Handle handle;
+    explicit R{rid}Handle(pit::Handle handle) : handle(std::move(handle)) {{}}
+{overrides}}};
+
+{imports}#endif
+#ifndef {p}_CTOR_HPP
+#define {p}_CTOR_HPP
+R{rid}Handle {p}_ref(std::unique_ptr<R{rid}> impl);
+
+template <class Derived> class {p}Export : public R{rid} {{
+  public:
+    template <class... Args> static R{rid}Handle make(Args &&...args) {{
+        return {p}_ref(std::make_unique<Derived>(std::forward<Args>(args)...));
+    }}
+}};
+#endif
+#if defined({p}_IMPL) && !defined({p}_CTOR_IMPL_HPP)
+#define {p}_CTOR_IMPL_HPP
+extern "C" __attribute__((import_module("{push_module}"), import_name("{push_name}"))) pit::ref_t
+{p}_push(R{rid} *impl);
+R{rid}Handle {p}_ref(std::unique_ptr<R{rid}> impl) {{
+    return R{rid}Handle(pit::Handle({p}_push(impl.release())));
+}}
+extern "C" __attribute__((export_name("{drop_name}"))) void {p}_drop(R{rid} *impl) {{
+    delete impl;
+}}
+{exports}#endif
+"#
+    )
+}
+
+/// Converts a reference received across the FFI boundary into an owning C++ value.
+fn to_cpp(i: &Interface, t: &Arg, v: &str) -> String {
+    match t {
+        Arg::Resource { ty, nullable, .. } => {
+            let h = match ty {
+                ResTy::None => format!("pit::Handle({v})"),
+                _ => format!("{}(pit::Handle({v}))", handle_ty(i, ty)),
+            };
+            if *nullable {
+                let c = cpp_ty(i, t);
+                format!("(pit::is_null({v}) ? {c}() : {c}({h}))")
+            } else {
+                h
+            }
+        }
+        _ => v.to_owned(),
+    }
+}
+
+/// Converts a C++ value into a reference across the FFI boundary, giving it up if
+/// `take` is set and lending it otherwise.
+fn to_ffi(t: &Arg, v: &str, take: bool) -> String {
+    match t {
+        Arg::Resource { ty, nullable, .. } => {
+            let h = match ty {
+                ResTy::None => "",
+                _ => "handle.",
+            };
+            let m = if take { "release" } else { "borrow" };
+            if *nullable {
+                format!("({v} ? {v}->{h}{m}() : pit::null_ref())")
+            } else {
+                format!("{v}.{h}{m}()")
+            }
+        }
+        _ => v.to_owned(),
+    }
+}
+
+/// Returns the C++ handle class for a resource type.
+fn handle_ty(i: &Interface, ty: &ResTy) -> String {
+    match ty {
+        ResTy::None => "pit::Handle".to_owned(),
+        ResTy::Of(a) => format!("R{}Handle", hex::encode(a)),
+        ResTy::This => format!("R{}Handle", i.rid_str()),
+        _ => todo!(),
+    }
+}
+
+/// Returns the C++ return type of a method: `void`, its result, or a `std::tuple` of
+/// its results.
+fn ret_ty(i: &Interface, s: &pit_core::Sig) -> String {
+    match s.rets.len() {
+        0 => "void".to_owned(),
+        1 => cpp_ty(i, &s.rets[0]),
+        _ => format!("std::tuple<{}>", s.rets.iter().map(|a| cpp_ty(i, a)).join(", ")),
+    }
+}
+
+/// Returns the C++ type of a parameter, taking resources by value only if ownership
+/// is taken.
+fn param_ty(i: &Interface, t: &Arg) -> String {
+    match t {
+        Arg::Resource { take: false, .. } => format!("{} &", cpp_ty(i, t)),
+        _ => cpp_ty(i, t),
+    }
+}
+
+/// Returns the type of a PIT argument across the FFI boundary.
+pub fn ffi_ty(t: &Arg) -> String {
+    match t {
+        Arg::Resource { .. } => "pit::ref_t".to_owned(),
+        Arg::I32 => "uint32_t".to_owned(),
+        Arg::I64 => "uint64_t".to_owned(),
+        Arg::F32 => "float".to_owned(),
+        Arg::F64 => "double".to_owned(),
+        _ => todo!(),
+    }
+}
+
+/// Converts a PIT argument type to its owning C++ representation.
+///
+/// # Arguments
+///
+/// * `i` - The containing interface (used for `this` type resolution)
+/// * `t` - The PIT argument type to convert
+///
+/// # Returns
+///
+/// A string containing the C++ type name.
+pub fn cpp_ty(i: &Interface, t: &Arg) -> String {
+    match t {
+        Arg::Resource { ty, nullable, .. } => {
+            let h = handle_ty(i, ty);
+            if *nullable {
+                format!("std::optional<{h}>")
+            } else {
+                h
+            }
+        }
+        t => ffi_ty(t),
+    }
+}
+
+/// Returns the C++ runtime the generated headers depend on, as `(file name, contents)`
+/// pairs.
+///
+/// The runtime provides `pit.hpp`, declaring `pit::Handle` and `pit::ref_t`, and
+/// `pit.cpp`, implementing the handle table behind them. Build `pit.cpp` once per
+/// module, with the same flags as the generated headers.
+pub fn runtime() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("pit.hpp", include_str!("runtime/pit.hpp")),
+        ("pit.cpp", include_str!("runtime/pit.cpp")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pit_golden::{golden, iface, other};
+    use std::process::Command;
+
+    #[test]
+    fn header() {
+        let opts = Opts {
+            tpit: false,
+            salt: vec![],
+            name: None,
+        };
+        golden!("header.hpp", &cppify(&opts, &iface()));
+    }
+
+    #[test]
+    fn tpit_header() {
+        let opts = Opts {
+            tpit: true,
+            salt: vec![],
+            name: None,
+        };
+        golden!("header_tpit.hpp", &cppify(&opts, &iface()));
+    }
+
+    #[test]
+    fn named_header() {
+        let opts = Opts {
+            tpit: false,
+            salt: b"second".to_vec(),
+            name: Some("second".to_owned()),
+        };
+        golden!("header_named.hpp", &cppify(&opts, &iface()));
+    }
+
+    /// Checks the TPIT headers, with their implementations, and the runtime with the
+    /// C++ compiler.
+    #[test]
+    fn compiles_tpit_guest() {
+        let opts = Opts {
+            tpit: true,
+            salt: vec![],
+            name: None,
+        };
+        let dir = pit_golden::scratch("cpp");
+        let mut main = String::new();
+        for i in [iface(), other()] {
+            let rid = i.rid_str();
+            std::fs::write(dir.join(format!("R{rid}.hpp")), cppify(&opts, &i)).unwrap();
+            main += &format!("#define R{rid}_IMPL\n#include <R{rid}.hpp>\n");
+        }
+        std::fs::write(dir.join("main.cpp"), main).unwrap();
+        pit_golden::write_all(&dir, runtime());
+        pit_golden::run(
+            Command::new("c++")
+                .current_dir(&dir)
+                .args(["-std=c++17", "-fsyntax-only", "-Wno-attributes", "-DPIT_TPIT", "-I."])
+                .args(["main.cpp", "pit.cpp"]),
+        );
+    }
+}
@@ -0,0 +1,81 @@
+/*
+ * Runtime for C++ code generated by pit-cpp; see pit.hpp.
+ */
+#include <cstdlib>
+#include <pit.hpp>
+#include <vector>
+
+namespace pit {
+
+#ifdef PIT_TPIT
+
+extern "C" __attribute__((import_module("tpit"), import_name("drop"))) void
+pit_drop_ref(uint32_t ref);
+
+extern "C" __attribute__((import_module("tpit"), import_name("void"))) void
+pit_void_ref(uint32_t ref);
+
+ref_t null_ref() { return 0; }
+
+bool is_null(ref_t ref) { return ref == 0; }
+
+namespace detail {
+uint32_t table_new(ref_t ref) { return ref; }
+
+ref_t table_get(uint32_t index) { return index; }
+
+ref_t table_pop(uint32_t index) { return index; }
+
+void drop(ref_t ref) { pit_drop_ref(ref); }
+
+void void_ref(ref_t ref) {
+    if (ref)
+        pit_void_ref(ref);
+}
+} // namespace detail
+
+#else
+
+static __externref_t table[0];
+
+/* Free slots of the table, reused before growing it. */
+static std::vector<uint32_t> free_slots;
+
+extern "C" __attribute__((import_module("pit"), import_name("drop"))) void
+pit_drop_ref(__externref_t ref);
+
+ref_t null_ref() { return __builtin_wasm_ref_null_extern(); }
+
+bool is_null(ref_t ref) { return __builtin_wasm_ref_is_null_extern(ref); }
+
+namespace detail {
+uint32_t table_new(ref_t ref) {
+    uint32_t i;
+    if (!free_slots.empty()) {
+        i = free_slots.back();
+        free_slots.pop_back();
+        __builtin_wasm_table_set(table, i, ref);
+    } else {
+        int old = __builtin_wasm_table_grow(table, ref, 1);
+        if (old < 0)
+            std::abort();
+        i = static_cast<uint32_t>(old);
+    }
+    return i + 1;
+}
+
+ref_t table_get(uint32_t index) { return __builtin_wasm_table_get(table, index - 1); }
+
+ref_t table_pop(uint32_t index) {
+    __externref_t ref = __builtin_wasm_table_get(table, index - 1);
+    __builtin_wasm_table_set(table, index - 1, __builtin_wasm_ref_null_extern());
+    free_slots.push_back(index - 1);
+    return ref;
+}
+
+void drop(ref_t ref) { pit_drop_ref(ref); }
+} // namespace detail
+
+#endif
+
+} // namespace pit
@@ -0,0 +1,87 @@
+/*
+ * Runtime for C++ code generated by pit-cpp.
+ *
+ * Externrefs cannot be stored in linear memory, so handles keep them in a wasm
+ * table and hold indices into it. Requires clang targeting wasm32 with reference
+ * types (-mreference-types).
+ *
+ * When PIT_TPIT is defined, as needed by headers generated in TPIT mode, references
+ * are already u32 TPIT handles, which are used as-is, dropped through tpit.drop, and
+ * released without dropping their resource through tpit.void. This works with any
+ * wasm32 toolchain; run `pit untpit` on the resulting module.
+ */
+#ifndef PIT_HPP
+#define PIT_HPP
+#include <cstdint>
+#include <optional>
+#include <utility>
+
+namespace pit {
+
+/* A reference to a PIT resource, as passed across the FFI boundary. */
+#ifdef PIT_TPIT
+using ref_t = uint32_t;
+#else
+using ref_t = __externref_t;
+#endif
+
+/* Returns the null reference. */
+ref_t null_ref();
+
+/* Returns whether a reference is null. */
+bool is_null(ref_t ref);
+
+namespace detail {
+/* Stores a reference in the table, returning its index plus one. */
+uint32_t table_new(ref_t ref);
+/* Returns the reference at an index plus one, which keeps owning it. */
+ref_t table_get(uint32_t index);
+/* Returns the reference at an index plus one and frees the slot. */
+ref_t table_pop(uint32_t index);
+/* Drops a reference through the host. */
+void drop(ref_t ref);
+#ifdef PIT_TPIT
+/* Releases the TPIT table slot of a borrowed reference, without dropping it. */
+void void_ref(ref_t ref);
+#endif
+} // namespace detail
+
+/*
+ * An owned PIT resource, dropped when destroyed. Move-only; a default constructed
+ * or moved-from handle is null.
+ */
+class Handle {
+  public:
+    Handle() = default;
+    /* Takes ownership of a reference. */
+    explicit Handle(ref_t ref) : index(is_null(ref) ? 0 : detail::table_new(ref)) {}
+    Handle(Handle &&other) noexcept : index(std::exchange(other.index, 0)) {}
+    Handle &operator=(Handle &&other) noexcept {
+        if (this != &other) {
+            reset();
+            index = std::exchange(other.index, 0);
+        }
+        return *this;
+    }
+    Handle(const Handle &) = delete;
+    Handle &operator=(const Handle &) = delete;
+    ~Handle() { reset(); }
+
+    /* Returns the reference, which the handle keeps owning. */
+    ref_t borrow() const { return index ? detail::table_get(index) : null_ref(); }
+    /* Returns the reference and leaves the handle null, passing ownership on. */
+    ref_t release() { return index ? detail::table_pop(std::exchange(index, 0)) : null_ref(); }
+    /* Drops the resource, leaving the handle null. */
+    void reset() {
+        if (index)
+            detail::drop(release());
+    }
+    explicit operator bool() const { return index != 0; }
+
+  private:
+    uint32_t index = 0;
+};
+
+} // namespace pit
+
+#endif
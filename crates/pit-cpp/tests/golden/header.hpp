@@ -0,0 +1,120 @@
+#ifndef R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_HPP
+#define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_HPP
+#ifdef PIT_TPIT
+#error "R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068.hpp was generated for externref; do not define PIT_TPIT"
+#endif
+#include <cstdint>
+#include <memory>
+#include <optional>
+#include <pit.hpp>
+#include <tuple>
+#include <Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.hpp>
+using R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_fres = void;
+using R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres = pit::ref_t;
+struct R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres {
+    uint64_t v0;
+    double v1;
+};
+using R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres = pit::ref_t;
+using R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_fres = void;
+
+class R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle;
+
+class R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 {
+  public:
+    virtual ~R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068() = default;
+    virtual void give(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle p0) = 0;
+    virtual Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle read(uint32_t p0) = 0;
+    virtual std::tuple<uint64_t, double> seek(uint64_t p0, float p1) = 0;
+    virtual R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle split() = 0;
+    virtual void write(std::optional<Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle> & p0) = 0;
+};
+
+class R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle final : public R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 {
+  public:
+    pit::Handle handle;
+    explicit R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle(pit::Handle handle) : handle(std::move(handle)) {}
+    void give(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle p0) override;
+    Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle read(uint32_t p0) override;
+    std::tuple<uint64_t, double> seek(uint64_t p0, float p1) override;
+    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle split() override;
+    void write(std::optional<Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle> & p0) override;
+};
+
+extern "C" __attribute__((import_module("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("give"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_import(pit::ref_t self, pit::ref_t v0);
+inline void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle::give(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle p0) {
+    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_import(handle.borrow(), p0.handle.release());
+}
+extern "C" __attribute__((import_module("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("read"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_import(pit::ref_t self, uint32_t v0);
+inline Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle::read(uint32_t p0) {
+    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres r = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_import(handle.borrow(), p0);
+    return Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle(pit::Handle(r));
+}
+extern "C" __attribute__((import_module("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("seek"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_import(pit::ref_t self, uint64_t v0, float v1);
+inline std::tuple<uint64_t, double> R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle::seek(uint64_t p0, float p1) {
+    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres r = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_import(handle.borrow(), p0, p1);
+    return std::tuple<uint64_t, double>{r.v0, r.v1};
+}
+extern "C" __attribute__((import_module("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("split"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_import(pit::ref_t self);
+inline R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle::split() {
+    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres r = R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_import(handle.borrow());
+    return R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle(pit::Handle(r));
+}
+extern "C" __attribute__((import_module("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("write"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_import(pit::ref_t self, pit::ref_t v0);
+inline void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle::write(std::optional<Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle> & p0) {
+    R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_import(handle.borrow(), (p0 ? p0->handle.borrow() : pit::null_ref()));
+}
+#endif
+#ifndef R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_HPP
+#define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_HPP
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_ref(std::unique_ptr<R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068> impl);
+
+template <class Derived> class R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Export : public R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 {
+  public:
+    template <class... Args> static R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle make(Args &&...args) {
+        return R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_ref(std::make_unique<Derived>(std::forward<Args>(args)...));
+    }
+};
+#endif
+#if defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_IMPL) && !defined(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_IMPL_HPP)
+#define R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_CTOR_IMPL_HPP
+extern "C" __attribute__((import_module("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"), import_name("~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230"))) pit::ref_t
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_push(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *impl);
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_ref(std::unique_ptr<R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068> impl) {
+    return R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Handle(pit::Handle(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_push(impl.release())));
+}
+extern "C" __attribute__((export_name("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230.drop"))) void R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_drop(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *impl) {
+    delete impl;
+}
+extern "C" __attribute__((export_name("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/give"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_give_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me, pit::ref_t v0) {
+    auto p0 = Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle(pit::Handle(v0));
+    me->give(std::move(p0));
+}
+extern "C" __attribute__((export_name("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/read"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_read_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me, uint32_t v0) {
+    auto res = me->read(v0);
+    return res.handle.release();
+}
+extern "C" __attribute__((export_name("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/seek"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me, uint64_t v0, float v1) {
+    auto res = me->seek(v0, v1);
+    return R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_seek_fres{std::get<0>(res), std::get<1>(res)};
+}
+extern "C" __attribute__((export_name("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/split"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_split_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me) {
+    auto res = me->split();
+    return res.handle.release();
+}
+extern "C" __attribute__((export_name("pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~5610d9f5f40a09a2bf607ccf074a3f617da7238040314a00f10a4e9a2ebc7230/write"))) R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_fres
+R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068_write_export(R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 *me, pit::ref_t v0) {
+    auto p0 = (pit::is_null(v0) ? std::optional<Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle>() : std::optional<Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle>(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cdHandle(pit::Handle(v0))));
+    me->write(p0);
+    (void)(p0 ? p0->handle.release() : pit::null_ref());
+}
+#endif
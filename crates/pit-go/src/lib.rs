@@ -0,0 +1,410 @@
+//! # PIT Go Code Generator
+//!
+//! Generates Go guest bindings from PIT interface definitions.
+//!
+//! This crate emits a Go file per interface for implementing and using PIT resources
+//! from modules built with TinyGo, or Go 1.24 and later, for WebAssembly. Neither
+//! supports externrefs, so the generated code uses TPIT: `tpit/{rid}` imports and
+//! exports with `uint32` handles, like Rust guests generated in TPIT mode.
+//!
+//! ## Generated Code
+//!
+//! For each PIT interface, this crate generates, in `R{rid}.go`:
+//! - An interface `R{rid}` with all interface methods, capitalized
+//! - A handle type `R{rid}Handle`, implementing `R{rid}` through the
+//!   `//go:wasmimport tpit/{rid} {method}` imports
+//! - `{prefix}Of`, which exports an implementation of `R{rid}` as a new resource
+//!   through the `tpit/{rid}.~{ctor}` import, keeping it in an object table
+//! - The `//go:wasmexport tpit/{rid}/~{ctor}/...` functions, dispatching to the objects
+//!   in that table
+//!
+//! where the prefix is `R{rid}`, or `R{rid}_{name}` with [`Opts::name`]. Files generated
+//! for named implementations only contain the last two, and must be in the same
+//! package as the unnamed one. The constructor ID is derived from [`Opts::salt`] the
+//! same way as for Rust guests.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use pit_core::parse_interface;
+//!
+//! let (_, interface) = parse_interface("{ method(I32) -> (I64) }").unwrap();
+//! let opts = pit_go::Opts {
+//!     pkg: "guest".to_owned(),
+//!     rt: "example.com/guest/tpit".to_owned(),
+//!     salt: vec![],
+//!     name: None,
+//! };
+//! let go = pit_go::goify(&opts, &interface).unwrap();
+//! ```
+//!
+//! Modules are built as reactors, then given their interfaces with `pit embed` and
+//! converted to PIT ABI v1 with `pit untpit`.
+//!
+//! ## Resources
+//!
+//! Resources taking ownership (`&`) are `tpit.Handle`s, from the runtime package
+//! returned by [`runtime`], and others are borrowed `tpit.Lent`s. Handles received from
+//! imports or passed to exports are owned by the guest, which releases them with
+//! `Drop`, and are consumed when passed to imports or returned from exports. Lent
+//! handles keep the resource owned by the lender: those received from imports are
+//! released with `Release`, which frees their table slot through `tpit.void` without
+//! dropping the resource, and those passed to exports are released when the export
+//! returns. `Handle.Lend` lends an owned handle. Exported objects implementing
+//! `tpit.Dropper` are notified when their resource is dropped.
+//! Go wasm imports and exports return at most one value, so interfaces with methods
+//! returning more than one are rejected with [`Unsupported`].
+
+use itertools::Itertools;
+use pit_core::{Arg, Interface, Sig};
+use sha3::Digest;
+use std::fmt;
+use std::iter::once;
+
+/// Options for Go code generation.
+pub struct Opts {
+    /// The Go package name of the generated code.
+    pub pkg: String,
+    /// The import path of the runtime package returned by [`runtime`].
+    pub rt: String,
+    /// Additional bytes to include in the constructor ID hash for disambiguation.
+    pub salt: Vec<u8>,
+    /// Names the implementation, prefixing its symbols with `R{rid}_{name}` instead of `R{rid}`.
+    pub name: Option<String>,
+}
+
+/// An interface Go code cannot be generated for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    /// The method returning more than one value.
+    pub method: String,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} returns more than one value, which Go wasm imports and exports cannot express",
+            self.method
+        )
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// Generates Go code from a PIT interface definition.
+///
+/// # Arguments
+///
+/// * `opts` - Code generation options
+/// * `i` - The PIT interface to generate bindings for
+///
+/// # Returns
+///
+/// A string containing the Go source, to be saved as `R{rid}.go`, or [`Unsupported`]
+/// if a method returns more than one value.
+pub fn goify(opts: &Opts, i: &Interface) -> Result<String, Unsupported> {
+    if let Some((a, _)) = i.methods.iter().find(|(_, b)| b.rets.len() > 1) {
+        return Err(Unsupported { method: a.clone() });
+    }
+    let rid = i.rid_str();
+    let pkg = &opts.pkg;
+    let rt = &opts.rt;
+    let p = match &opts.name {
+        Some(n) => format!("R{rid}_{n}"),
+        None => format!("R{rid}"),
+    };
+    let lp = format!("r{}", &p[1..]);
+    let mut ha = sha3::Sha3_256::default();
+    ha.update(format!("~{rid}"));
+    ha.update(&opts.salt);
+    let ha = hex::encode(ha.finalize());
+    let decls = i
+        .methods
+        .iter()
+        .map(|(a, b)| format!("\n\t{}({}){}", go_name(a), params(b), ret(b, true)))
+        .join("");
+    let imports = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let r = if b.rets.is_empty() { "" } else { "return " };
+            let call = format!(
+                "r{rid}_{a}({})",
+                once("uint32(h)".to_owned())
+                    .chain(
+                        b.params
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, a)| to_ffi(a, &format!("p{idx}")))
+                    )
+                    .join(", ")
+            );
+            let call = match b.rets.first() {
+                Some(t) => from_ffi(t, &call),
+                None => call,
+            };
+            format!(
+                r#"
+
+func (h R{rid}Handle) {}({}){} {{
+	{r}{call}
+}}
+
+//go:wasmimport tpit/{rid} {a}
+func r{rid}_{a}({}){}"#,
+                go_name(a),
+                params(b),
+                ret(b, true),
+                once("self uint32".to_owned())
+                    .chain(once(ffi_params(b)).filter(|p| !p.is_empty()))
+                    .join(", "),
+                ret(b, false),
+            )
+        })
+        .join("");
+    let exports = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let r = if b.rets.is_empty() { "" } else { "return " };
+            // Slots `pit untpit` allocated for lent arguments are released on return.
+            let release = b
+                .params
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| matches!(a, Arg::Resource { take: false, .. }))
+                .map(|(idx, _)| format!("\n\tdefer tpit.Lent(p{idx}).Release()"))
+                .join("");
+            let call = format!(
+                "{lp}Table.Get(index).{}({})",
+                go_name(a),
+                b.params
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, a)| from_ffi(a, &format!("p{idx}")))
+                    .join(", ")
+            );
+            let call = match b.rets.first() {
+                Some(t) => to_ffi(t, &call),
+                None => call,
+            };
+            format!(
+                r#"
+
+//go:wasmexport tpit/{rid}/~{ha}/{a}
+func {lp}_export_{a}({}){} {{{release}
+	{r}{call}
+}}"#,
+                once("index uint32".to_owned())
+                    .chain(once(ffi_params(b)).filter(|p| !p.is_empty()))
+                    .join(", "),
+                ret(b, false),
+            )
+        })
+        .join("");
+    let head = match &opts.name {
+        None => format!(
+            r#"
+
+// R{rid} is implemented by resources of the interface.
+type R{rid} interface {{{decls}
+}}
+
+// R{rid}Handle is an owned handle to a resource, calling its methods through the
+// `tpit/{rid}` imports.
+type R{rid}Handle tpit.Handle
+
+// Drop releases the resource.
+func (h R{rid}Handle) Drop() {{
+	tpit.Handle(h).Drop()
+}}{imports}"#
+        ),
+        Some(_) => String::new(),
+    };
+    Ok(format!(
+        r#"// Code generated by pit-go. DO NOT EDIT.
+
+package {pkg}
+
+import "{rt}"{head}
+
+var {lp}Table tpit.Table[R{rid}]
+
+// {p}Of exports x as a new resource, returning an owned handle to it.
+func {p}Of(x R{rid}) tpit.Handle {{
+	return tpit.Handle({lp}_ctor({lp}Table.Push(x)))
+}}
+
+//go:wasmimport tpit/{rid} ~{ha}
+func {lp}_ctor(index uint32) uint32{exports}
+
+//go:wasmexport tpit/{rid}/~{ha}.drop
+func {lp}_drop(index uint32) {{
+	if d, ok := {lp}Table.Remove(index).(tpit.Dropper); ok {{
+		d.Drop()
+	}}
+}}
+"#
+    ))
+}
+
+/// Capitalizes a method name, so that types in other packages can implement it.
+pub fn go_name(a: &str) -> String {
+    let mut c = a.chars();
+    match c.next() {
+        Some(f) => f.to_uppercase().chain(c).collect(),
+        None => String::new(),
+    }
+}
+
+/// Renders the parameters of a method.
+fn params(b: &Sig) -> String {
+    b.params
+        .iter()
+        .enumerate()
+        .map(|(idx, a)| format!("p{idx} {}", go_ty(a, true)))
+        .join(", ")
+}
+
+/// Renders the parameters of a method at the WebAssembly boundary, without the
+/// leading handle or index.
+fn ffi_params(b: &Sig) -> String {
+    b.params
+        .iter()
+        .enumerate()
+        .map(|(idx, a)| format!("p{idx} {}", go_ty(a, false)))
+        .join(", ")
+}
+
+/// Renders the result of a method, which [`goify`] ensures has at most one, with a
+/// leading space unless there is none.
+fn ret(b: &Sig, high: bool) -> String {
+    b.rets
+        .first()
+        .map(|a| format!(" {}", go_ty(a, high)))
+        .unwrap_or_default()
+}
+
+/// Returns the Go type of a PIT argument, as `tpit.Handle`s for owned resources and
+/// `tpit.Lent`s for borrowed ones if `high`, and as the raw `uint32` at the WebAssembly
+/// boundary otherwise.
+pub fn go_ty(a: &Arg, high: bool) -> &'static str {
+    match a {
+        Arg::I32 => "uint32",
+        Arg::I64 => "uint64",
+        Arg::F32 => "float32",
+        Arg::F64 => "float64",
+        Arg::Resource { take: false, .. } if high => "tpit.Lent",
+        Arg::Resource { .. } if high => "tpit.Handle",
+        Arg::Resource { .. } => "uint32",
+        _ => todo!(),
+    }
+}
+
+/// Converts a Go value to its representation at the WebAssembly boundary.
+fn to_ffi(a: &Arg, v: &str) -> String {
+    match a {
+        Arg::Resource { .. } => format!("uint32({v})"),
+        _ => v.to_owned(),
+    }
+}
+
+/// Converts a value at the WebAssembly boundary to its Go representation.
+fn from_ffi(a: &Arg, v: &str) -> String {
+    match a {
+        Arg::Resource { take: false, .. } => format!("tpit.Lent({v})"),
+        Arg::Resource { .. } => format!("tpit.Handle({v})"),
+        _ => v.to_owned(),
+    }
+}
+
+/// Returns the Go runtime the generated code depends on, as `(file name, contents)`
+/// pairs.
+///
+/// The runtime provides the `tpit` package, defining `Handle`, which imports
+/// `tpit.drop`, `Lent`, which imports `tpit.void`, and the `Table` of exported objects. Place it in the directory of the
+/// [`Opts::rt`] import path.
+pub fn runtime() -> Vec<(&'static str, &'static str)> {
+    vec![("tpit.go", include_str!("runtime/tpit.go"))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pit_golden::{golden, iface, single_result_iface};
+    use std::process::Command;
+
+    #[test]
+    fn bindings() {
+        let opts = Opts {
+            pkg: "pitgen".to_owned(),
+            rt: "example.com/pit/tpit".to_owned(),
+            salt: vec![],
+            name: None,
+        };
+        golden!("iface.go", &goify(&opts, &single_result_iface()).unwrap());
+    }
+
+    #[test]
+    fn named_bindings() {
+        let opts = Opts {
+            pkg: "pitgen".to_owned(),
+            rt: "example.com/pit/tpit".to_owned(),
+            salt: b"second".to_vec(),
+            name: Some("second".to_owned()),
+        };
+        golden!("iface_named.go", &goify(&opts, &single_result_iface()).unwrap());
+    }
+
+    #[test]
+    fn rejects_multiple_results() {
+        let opts = Opts {
+            pkg: "pitgen".to_owned(),
+            rt: "example.com/pit/tpit".to_owned(),
+            salt: vec![],
+            name: None,
+        };
+        assert_eq!(
+            goify(&opts, &iface()),
+            Err(Unsupported {
+                method: "seek".to_owned()
+            })
+        );
+    }
+
+    /// Type-checks the unnamed and named bindings, in one package with the runtime,
+    /// with `go vet` for wasip1.
+    #[test]
+    fn vets_with_go() {
+        let i = single_result_iface();
+        let rid = i.rid_str();
+        let dir = pit_golden::scratch("go");
+        let mut opts = Opts {
+            pkg: "pitgen".to_owned(),
+            rt: "example.com/pit/tpit".to_owned(),
+            salt: vec![],
+            name: None,
+        };
+        let go = goify(&opts, &i).unwrap();
+        opts.salt = b"second".to_vec();
+        opts.name = Some("second".to_owned());
+        let named = goify(&opts, &i).unwrap();
+        pit_golden::write_all(
+            &dir,
+            [
+                ("go.mod", "module example.com/pit\n\ngo 1.24\n"),
+                (&format!("pitgen/R{rid}.go"), &go),
+                (&format!("pitgen/R{rid}_second.go"), &named),
+            ],
+        );
+        pit_golden::write_all(&dir.join("tpit"), runtime());
+        pit_golden::run(
+            Command::new("go")
+                .current_dir(&dir)
+                .env("GOOS", "wasip1")
+                .env("GOARCH", "wasm")
+                .args(["vet", "./..."]),
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// Package tpit is the runtime of Go guest code generated by pit-go.
+//
+// Resources are TPIT handles, indices into the table of externrefs that `pit untpit`
+// adds to a module, where 0 is the null handle. Every resource entering a module gets
+// a slot of its own, so borrowed ones must release their slot without dropping the
+// resource, which stays owned by its lender.
+package tpit
+
+// Handle is a TPIT resource handle; 0 is the null handle.
+type Handle uint32
+
+//go:wasmimport tpit drop
+func drop(handle uint32)
+
+// Drop releases a handle the guest owns. Dropping the null handle does nothing.
+func (h Handle) Drop() {
+	if h != 0 {
+		drop(uint32(h))
+	}
+}
+
+// Lend returns the handle as lent to a call, which leaves the guest owning it.
+func (h Handle) Lend() Lent {
+	return Lent(h)
+}
+
+// Lent is a borrowed TPIT handle, valid while its lender keeps the resource.
+type Lent uint32
+
+//go:wasmimport tpit void
+func voidHandle(handle uint32)
+
+// Release frees the slot of a lent handle without dropping its resource. Releasing
+// the null handle does nothing.
+func (h Lent) Release() {
+	if h != 0 {
+		voidHandle(uint32(h))
+	}
+}
+
+// Dropper is implemented by exported objects that release state when their
+// resource is dropped.
+type Dropper interface {
+	Drop()
+}
+
+// Table holds the objects exported by one implementation, by index.
+type Table[T any] struct {
+	objects []T
+	live    []bool
+	free    []uint32
+}
+
+// Push stores x, returning its index.
+func (t *Table[T]) Push(x T) uint32 {
+	if n := len(t.free); n > 0 {
+		i := t.free[n-1]
+		t.free = t.free[:n-1]
+		t.objects[i] = x
+		t.live[i] = true
+		return i
+	}
+	t.objects = append(t.objects, x)
+	t.live = append(t.live, true)
+	return uint32(len(t.objects) - 1)
+}
+
+// Get returns the object at index i.
+func (t *Table[T]) Get(i uint32) T {
+	if !t.live[i] {
+		panic("tpit: use of a dropped resource")
+	}
+	return t.objects[i]
+}
+
+// Remove removes and returns the object at index i, freeing the index for reuse.
+func (t *Table[T]) Remove(i uint32) T {
+	x := t.Get(i)
+	var zero T
+	t.objects[i] = zero
+	t.live[i] = false
+	t.free = append(t.free, i)
+	return x
+}
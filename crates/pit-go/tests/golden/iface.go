@@ -0,0 +1,88 @@
+// Code generated by pit-go. DO NOT EDIT.
+
+package pitgen
+
+import "example.com/pit/tpit"
+
+// R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 is implemented by resources of the interface.
+type R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 interface {
+	Give(p0 tpit.Handle)
+	Read(p0 uint32) tpit.Lent
+	Split() tpit.Handle
+	Write(p0 tpit.Lent)
+}
+
+// R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Handle is an owned handle to a resource, calling its methods through the
+// `tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526` imports.
+type R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Handle tpit.Handle
+
+// Drop releases the resource.
+func (h R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Handle) Drop() {
+	tpit.Handle(h).Drop()
+}
+
+func (h R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Handle) Give(p0 tpit.Handle) {
+	r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_give(uint32(h), uint32(p0))
+}
+
+//go:wasmimport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 give
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_give(self uint32, p0 uint32)
+
+func (h R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Handle) Read(p0 uint32) tpit.Lent {
+	return tpit.Lent(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_read(uint32(h), p0))
+}
+
+//go:wasmimport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 read
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_read(self uint32, p0 uint32) uint32
+
+func (h R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Handle) Split() tpit.Handle {
+	return tpit.Handle(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_split(uint32(h)))
+}
+
+//go:wasmimport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 split
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_split(self uint32) uint32
+
+func (h R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Handle) Write(p0 tpit.Lent) {
+	r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_write(uint32(h), uint32(p0))
+}
+
+//go:wasmimport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 write
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_write(self uint32, p0 uint32)
+
+var r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Table tpit.Table[R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526]
+
+// R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Of exports x as a new resource, returning an owned handle to it.
+func R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Of(x R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526) tpit.Handle {
+	return tpit.Handle(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_ctor(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Table.Push(x)))
+}
+
+//go:wasmimport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 ~c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_ctor(index uint32) uint32
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9/give
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_export_give(index uint32, p0 uint32) {
+	r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Table.Get(index).Give(tpit.Handle(p0))
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9/read
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_export_read(index uint32, p0 uint32) uint32 {
+	return uint32(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Table.Get(index).Read(p0))
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9/split
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_export_split(index uint32) uint32 {
+	return uint32(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Table.Get(index).Split())
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9/write
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_export_write(index uint32, p0 uint32) {
+	defer tpit.Lent(p0).Release()
+	r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Table.Get(index).Write(tpit.Lent(p0))
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~c863cf1c583f5bc16dc0ab43f1e4a434649bcd49312fed7565efada5af083aa9.drop
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_drop(index uint32) {
+	if d, ok := r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526Table.Remove(index).(tpit.Dropper); ok {
+		d.Drop()
+	}
+}
@@ -0,0 +1,43 @@
+// Code generated by pit-go. DO NOT EDIT.
+
+package pitgen
+
+import "example.com/pit/tpit"
+
+var r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondTable tpit.Table[R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526]
+
+// R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondOf exports x as a new resource, returning an owned handle to it.
+func R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondOf(x R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526) tpit.Handle {
+	return tpit.Handle(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_ctor(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondTable.Push(x)))
+}
+
+//go:wasmimport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 ~b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_ctor(index uint32) uint32
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b/give
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_export_give(index uint32, p0 uint32) {
+	r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondTable.Get(index).Give(tpit.Handle(p0))
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b/read
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_export_read(index uint32, p0 uint32) uint32 {
+	return uint32(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondTable.Get(index).Read(p0))
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b/split
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_export_split(index uint32) uint32 {
+	return uint32(r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondTable.Get(index).Split())
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b/write
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_export_write(index uint32, p0 uint32) {
+	defer tpit.Lent(p0).Release()
+	r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondTable.Get(index).Write(tpit.Lent(p0))
+}
+
+//go:wasmexport tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~b9701af7a35027bc6fee7a2104217267e8f6bb830cf4e06ccd440462e1de5b6b.drop
+func r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_second_drop(index uint32) {
+	if d, ok := r2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526_secondTable.Remove(index).(tpit.Dropper); ok {
+		d.Drop()
+	}
+}
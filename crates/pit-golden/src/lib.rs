@@ -0,0 +1,113 @@
+//! # PIT Generator Test Support
+//!
+//! Shared by the tests of the code generators, so that every target is tested against
+//! the same interface:
+//! - [`iface`], which passes owned, borrowed and nullable resources of its own type and
+//!   of [`other`], and has a method returning more than one value
+//! - [`golden!`], comparing generated code with golden files
+//! - [`run`], compiling or checking generated code with the target's toolchain, which
+//!   is skipped when that toolchain is not installed
+//!
+//! Targets that cannot express methods returning more than one value check that
+//! [`iface`] is rejected, and are tested with [`single_result_iface`].
+
+use pit_core::Interface;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// The source of [`other`].
+pub const OTHER: &str = "{
+    len() -> (I64)
+}";
+
+/// Returns the interface whose resources [`iface`] passes around.
+pub fn other() -> Interface {
+    pit_core::parse_interface(OTHER).unwrap().1
+}
+
+/// Returns the source of [`iface`].
+pub fn iface_src() -> String {
+    let o = other().rid_str();
+    format!(
+        "{{
+    give(R{o}&) -> ();
+    read(I32) -> (R{o});
+    seek(I64,F32) -> (I64,F64);
+    split() -> (Rthis&);
+    write(R{o}n) -> ()
+}}"
+    )
+}
+
+/// Returns the interface generators are tested with.
+pub fn iface() -> Interface {
+    pit_core::parse_interface(&iface_src()).unwrap().1
+}
+
+/// Returns [`iface`] without its methods returning more than one value.
+pub fn single_result_iface() -> Interface {
+    let mut i = iface();
+    i.methods.retain(|_, b| b.rets.len() <= 1);
+    i
+}
+
+/// Compares generated code with `tests/golden/{name}` in the calling crate, or
+/// rewrites that file when `BLESS` is set.
+#[macro_export]
+macro_rules! golden {
+    ($name:expr, $actual:expr) => {
+        $crate::check_golden(env!("CARGO_MANIFEST_DIR"), $name, $actual)
+    };
+}
+
+#[doc(hidden)]
+pub fn check_golden(dir: &str, name: &str, actual: &str) {
+    let path = PathBuf::from(dir).join("tests/golden").join(name);
+    if std::env::var_os("BLESS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        actual == expected,
+        "{name} changed; rerun with BLESS=1 to update it:\n{actual}"
+    );
+}
+
+/// Returns an empty scratch directory to write generated code to.
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pit-golden-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes `(file name, contents)` pairs to a directory, creating subdirectories.
+pub fn write_all<'a>(dir: &std::path::Path, files: impl IntoIterator<Item = (&'a str, &'a str)>) {
+    for (name, src) in files {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, src).unwrap();
+    }
+}
+
+/// Runs a toolchain command, panicking with its output if it fails.
+///
+/// Returns `false`, without failing, if the program is not installed.
+pub fn run(cmd: &mut Command) -> bool {
+    let out = match cmd.output() {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("skipping {:?}: not installed", cmd.get_program());
+            return false;
+        }
+        Err(e) => panic!("running {cmd:?}: {e}"),
+    };
+    assert!(
+        out.status.success(),
+        "{cmd:?} failed:\n{}{}",
+        String::from_utf8_lossy(&out.stdout),
+        String::from_utf8_lossy(&out.stderr)
+    );
+    true
+}
@@ -0,0 +1,278 @@
+//! # PIT JavaScript Code Generator
+//!
+//! Generates JavaScript host bindings from PIT interface definitions.
+//!
+//! This crate emits an ES module per interface, along with its TypeScript
+//! declarations, for hosting PIT modules with the WebAssembly JavaScript API in
+//! browsers and Node.
+//!
+//! ## Generated Code
+//!
+//! For each PIT interface, this crate generates, in `R{rid}.js`:
+//! - `emit(imports, module, host)`, defining the `pit/{rid}.{method}` and
+//!   `pit/{rid}.~{ctor}` imports of a module, like `pit_rust_host_lib::emit`
+//! - A class `R{rid}Wrapped`, wrapping resources implemented by a guest
+//! - `RID` and `methods`, the resource ID and method names
+//!
+//! and, in `R{rid}.d.ts`, an interface `R{rid}` typing the methods of resources.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use pit_core::parse_interface;
+//!
+//! let (_, interface) = parse_interface("{ method(I32) -> (I64) }").unwrap();
+//! let js = pit_js::emit(&interface);
+//! let dts = pit_js::emit_dts(&interface);
+//! ```
+//!
+//! The generated modules import the runtime returned by [`runtime`] from `./pit.js`:
+//!
+//! ```js
+//! import { init } from "./pit.js";
+//! import { emit } from "./R{rid}.js";
+//!
+//! const host = {};
+//! const imports = init({});
+//! emit(imports, module, host);
+//! host.instance = await WebAssembly.instantiate(module, imports);
+//! ```
+//!
+//! ## Resources
+//!
+//! Resources are JavaScript values passed as externrefs. Any object with the methods
+//! of an interface can be passed to a guest, which calls them through the
+//! `pit/{rid}.{method}` imports; objects with a method keyed by the runtime's `drop`
+//! symbol are notified when the guest drops them. `I64`s are `BigInt`s, and methods
+//! with several results return arrays.
+//!
+//! Batch imports are not defined.
+
+use itertools::Itertools;
+use pit_core::{Arg, Interface, ResTy, Sig};
+use std::{collections::BTreeSet, iter::once};
+
+/// Generates a JavaScript ES module from a PIT interface definition.
+///
+/// # Arguments
+///
+/// * `i` - The PIT interface to generate bindings for
+///
+/// # Returns
+///
+/// A string containing the module, to be saved as `R{rid}.js`.
+pub fn emit(i: &Interface) -> String {
+    let rid = i.rid_str();
+    let methods = i.methods.keys().map(|a| format!("\"{a}\"")).join(", ");
+    let wrapped = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let params = params(b);
+            let args = once(format!("\"{a}\"")).chain(params.clone()).join(", ");
+            format!(
+                r#"
+
+  {a}({}) {{
+    return this[call]({args});
+  }}"#,
+                params.join(", ")
+            )
+        })
+        .join("");
+    let imports = i
+        .methods
+        .keys()
+        .map(|a| format!("\n  m.{a} = (self, ...args) => self.{a}(...args);"))
+        .join("");
+    format!(
+        r#"import {{ Wrapped, call, ctors }} from "./pit.js";
+
+export const RID = "{rid}";
+
+/** Method names, in sorted order. */
+export const methods = Object.freeze([{methods}]);
+
+/** A resource implemented by a guest, through its `pit/{rid}/~{{ctor}}` exports. */
+export class R{rid}Wrapped extends Wrapped {{
+  constructor(exports, ctor, base) {{
+    super(exports, RID, ctor, base);
+  }}{wrapped}
+}}
+
+/**
+ * Defines the `pit/{rid}` imports of a module in an import object, and returns it.
+ * Constructors create resources backed by `host.instance`, which must be set once
+ * the module is instantiated.
+ */
+export function emit(imports, module, host) {{
+  const m = (imports["pit/{rid}"] ??= {{}});{imports}
+  for (const ctor of ctors(module, RID)) {{
+    m[`~${{ctor}}`] = (base) => {{
+      if (!host.instance) {{
+        throw new Error("pit: constructor called before instantiation");
+      }}
+      return new R{rid}Wrapped(host.instance.exports, ctor, base);
+    }};
+  }}
+  return imports;
+}}
+"#
+    )
+}
+
+/// Generates TypeScript declarations for the module generated by [`emit`].
+///
+/// # Arguments
+///
+/// * `i` - The PIT interface to generate declarations for
+///
+/// # Returns
+///
+/// A string containing the declarations, to be saved as `R{rid}.d.ts`.
+pub fn emit_dts(i: &Interface) -> String {
+    let rid = i.rid_str();
+    let deps = i
+        .methods
+        .values()
+        .flat_map(|b| b.params.iter().chain(b.rets.iter()))
+        .filter_map(|a| match a {
+            Arg::Resource {
+                ty: ResTy::Of(x), ..
+            } => Some(hex::encode(x)),
+            _ => None,
+        })
+        .filter(|x| *x != rid)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|x| format!("import type {{ R{x} }} from \"./R{x}.js\";\n"))
+        .join("");
+    let methods = i.methods.keys().map(|a| format!("\"{a}\"")).join(", ");
+    let sigs = i
+        .methods
+        .iter()
+        .map(|(a, b)| format!("\n  {a}{};", sig(&rid, b)))
+        .join("");
+    format!(
+        r#"import {{ Host, Wrapped }} from "./pit.js";
+{deps}
+export declare const RID: "{rid}";
+
+/** Method names, in sorted order. */
+export declare const methods: readonly [{methods}];
+
+/** The methods of a resource implementing the interface. */
+export interface R{rid} {{{sigs}
+}}
+
+/** A resource implemented by a guest, through its `pit/{rid}/~{{ctor}}` exports. */
+export declare class R{rid}Wrapped extends Wrapped implements R{rid} {{
+  constructor(exports: WebAssembly.Exports, ctor: string, base: number);{sigs}
+}}
+
+/**
+ * Defines the `pit/{rid}` imports of a module in an import object, and returns it.
+ * Constructors create resources backed by `host.instance`, which must be set once
+ * the module is instantiated.
+ */
+export declare function emit(
+  imports: WebAssembly.Imports,
+  module: WebAssembly.Module,
+  host: Host,
+): WebAssembly.Imports;
+"#
+    )
+}
+
+/// Returns the parameter names of a method.
+fn params(b: &Sig) -> Vec<String> {
+    (0..b.params.len()).map(|idx| format!("p{idx}")).collect()
+}
+
+/// Renders a method signature as TypeScript.
+fn sig(rid: &str, b: &Sig) -> String {
+    format!(
+        "({}): {}",
+        b.params
+            .iter()
+            .enumerate()
+            .map(|(idx, a)| format!("p{idx}: {}", ts_ty(rid, a)))
+            .join(", "),
+        match b.rets.as_slice() {
+            [] => "void".to_owned(),
+            [a] => ts_ty(rid, a),
+            rets => format!("[{}]", rets.iter().map(|a| ts_ty(rid, a)).join(", ")),
+        }
+    )
+}
+
+/// Returns the TypeScript type of a PIT argument.
+///
+/// Typed resources use the interface types of other generated modules, while
+/// untyped ones are `unknown`.
+pub fn ts_ty(rid: &str, a: &Arg) -> String {
+    match a {
+        Arg::I32 | Arg::F32 | Arg::F64 => "number".to_owned(),
+        Arg::I64 => "bigint".to_owned(),
+        Arg::Resource { ty, nullable, .. } => {
+            let t = match ty {
+                ResTy::Of(x) => format!("R{}", hex::encode(x)),
+                ResTy::This => format!("R{rid}"),
+                _ => return "unknown".to_owned(),
+            };
+            if *nullable {
+                format!("{t} | null")
+            } else {
+                t
+            }
+        }
+        _ => todo!(),
+    }
+}
+
+/// Returns the runtime the generated modules depend on, as `(file name, contents)`
+/// pairs.
+///
+/// The runtime provides `pit.js`, defining `init`, which adds the `pit.drop` import,
+/// and the `Wrapped` base class of guest resources, along with its declarations in
+/// `pit.d.ts`. Place it next to the generated modules.
+pub fn runtime() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("pit.js", include_str!("runtime/pit.js")),
+        ("pit.d.ts", include_str!("runtime/pit.d.ts")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pit_golden::{golden, iface, other};
+    use std::process::Command;
+
+    #[test]
+    fn module() {
+        golden!("iface.js", &emit(&iface()));
+    }
+
+    #[test]
+    fn declarations() {
+        golden!("iface.d.ts", &emit_dts(&iface()));
+    }
+
+    /// Loads the modules, with the runtime, in Node.js, which checks their syntax and
+    /// that their imports resolve.
+    #[test]
+    fn loads_in_node() {
+        let dir = pit_golden::scratch("js");
+        let mut main = String::new();
+        for i in [iface(), other()] {
+            let rid = i.rid_str();
+            std::fs::write(dir.join(format!("R{rid}.js")), emit(&i)).unwrap();
+            main += &format!("import \"./R{rid}.js\";\n");
+        }
+        std::fs::write(dir.join("main.js"), main).unwrap();
+        std::fs::write(dir.join("package.json"), r#"{ "type": "module" }"#).unwrap();
+        pit_golden::write_all(&dir, runtime());
+        pit_golden::run(Command::new("node").current_dir(&dir).arg("main.js"));
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * Types for the runtime of JavaScript host code generated by pit-js; see pit.js.
+ */
+
+/** Key of the method run when a guest drops a resource through `pit.drop`. */
+export declare const drop: unique symbol;
+
+/** Key of the method calling an export of a guest resource by method name. */
+export declare const call: unique symbol;
+
+/** Holds the instance implementing the constructors of a module, once instantiated. */
+export interface Host {
+  instance?: WebAssembly.Instance;
+}
+
+/** Defines the `pit.drop` import in an import object, and returns it. */
+export declare function init(imports: WebAssembly.Imports): WebAssembly.Imports;
+
+/** Returns the constructors a module imports from `pit/{rid}`, without the `~`. */
+export declare function ctors(module: WebAssembly.Module, rid: string): string[];
+
+/** A guest resource, backed by the exports of the instance that constructed it. */
+export declare class Wrapped {
+  constructor(exports: WebAssembly.Exports, rid: string, ctor: string, base: number);
+  [call](method: string, ...args: unknown[]): unknown;
+  [drop](): void;
+}
@@ -0,0 +1,70 @@
+/*
+ * Runtime for JavaScript host code generated by pit-js.
+ *
+ * Resources are plain JavaScript values passed to and from guests as externrefs.
+ * Objects implementing an interface's methods can be handed to guests directly;
+ * guest resources, created by `pit/{rid}.~{ctor}` imports, are Wrapped objects
+ * calling the `pit/{rid}/~{ctor}/...` exports of the instance that created them.
+ */
+
+/** Key of the method run when a guest drops a resource through `pit.drop`. */
+export const drop = Symbol("pit.drop");
+
+/** Key of the method calling an export of a guest resource by method name. */
+export const call = Symbol("pit.call");
+
+/** Defines the `pit.drop` import in an import object, and returns it. */
+export function init(imports) {
+  imports.pit = Object.assign(imports.pit ?? {}, {
+    drop(resource) {
+      if (resource === null || resource === undefined) {
+        throw new TypeError("pit.drop: null resource");
+      }
+      if (typeof resource[drop] === "function") {
+        resource[drop]();
+      }
+    },
+  });
+  return imports;
+}
+
+/** Returns the constructors a module imports from `pit/{rid}`, without the `~`. */
+export function ctors(module, rid) {
+  return WebAssembly.Module.imports(module)
+    .filter((i) => i.module === `pit/${rid}` && i.kind === "function" && i.name.startsWith("~"))
+    .map((i) => i.name.slice(1));
+}
+
+/** A guest resource, backed by the exports of the instance that constructed it. */
+export class Wrapped {
+  #exports;
+  #prefix;
+  #base;
+  #dropped = false;
+
+  constructor(exports, rid, ctor, base) {
+    this.#exports = exports;
+    this.#prefix = `pit/${rid}/~${ctor}`;
+    this.#base = base;
+  }
+
+  [call](method, ...args) {
+    const f = this.#exports[`${this.#prefix}/${method}`];
+    if (typeof f !== "function") {
+      throw new TypeError(`pit: missing export ${this.#prefix}/${method}`);
+    }
+    return f(this.#base, ...args);
+  }
+
+  /* Runs the `.drop` export at most once; it is optional. */
+  [drop]() {
+    if (this.#dropped) {
+      return;
+    }
+    this.#dropped = true;
+    const f = this.#exports[`${this.#prefix}.drop`];
+    if (typeof f === "function") {
+      f(this.#base);
+    }
+  }
+}
@@ -0,0 +1,37 @@
+import { Host, Wrapped } from "./pit.js";
+import type { Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd } from "./Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.js";
+
+export declare const RID: "5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068";
+
+/** Method names, in sorted order. */
+export declare const methods: readonly ["give", "read", "seek", "split", "write"];
+
+/** The methods of a resource implementing the interface. */
+export interface R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 {
+  give(p0: Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd): void;
+  read(p0: number): Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd;
+  seek(p0: bigint, p1: number): [bigint, number];
+  split(): R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068;
+  write(p0: Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd | null): void;
+}
+
+/** A resource implemented by a guest, through its `pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~{ctor}` exports. */
+export declare class R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Wrapped extends Wrapped implements R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068 {
+  constructor(exports: WebAssembly.Exports, ctor: string, base: number);
+  give(p0: Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd): void;
+  read(p0: number): Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd;
+  seek(p0: bigint, p1: number): [bigint, number];
+  split(): R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068;
+  write(p0: Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd | null): void;
+}
+
+/**
+ * Defines the `pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068` imports of a module in an import object, and returns it.
+ * Constructors create resources backed by `host.instance`, which must be set once
+ * the module is instantiated.
+ */
+export declare function emit(
+  imports: WebAssembly.Imports,
+  module: WebAssembly.Module,
+  host: Host,
+): WebAssembly.Imports;
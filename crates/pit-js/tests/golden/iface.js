@@ -0,0 +1,56 @@
+import { Wrapped, call, ctors } from "./pit.js";
+
+export const RID = "5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068";
+
+/** Method names, in sorted order. */
+export const methods = Object.freeze(["give", "read", "seek", "split", "write"]);
+
+/** A resource implemented by a guest, through its `pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068/~{ctor}` exports. */
+export class R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Wrapped extends Wrapped {
+  constructor(exports, ctor, base) {
+    super(exports, RID, ctor, base);
+  }
+
+  give(p0) {
+    return this[call]("give", p0);
+  }
+
+  read(p0) {
+    return this[call]("read", p0);
+  }
+
+  seek(p0, p1) {
+    return this[call]("seek", p0, p1);
+  }
+
+  split() {
+    return this[call]("split");
+  }
+
+  write(p0) {
+    return this[call]("write", p0);
+  }
+}
+
+/**
+ * Defines the `pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068` imports of a module in an import object, and returns it.
+ * Constructors create resources backed by `host.instance`, which must be set once
+ * the module is instantiated.
+ */
+export function emit(imports, module, host) {
+  const m = (imports["pit/5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068"] ??= {});
+  m.give = (self, ...args) => self.give(...args);
+  m.read = (self, ...args) => self.read(...args);
+  m.seek = (self, ...args) => self.seek(...args);
+  m.split = (self, ...args) => self.split(...args);
+  m.write = (self, ...args) => self.write(...args);
+  for (const ctor of ctors(module, RID)) {
+    m[`~${ctor}`] = (base) => {
+      if (!host.instance) {
+        throw new Error("pit: constructor called before instantiation");
+      }
+      return new R5c05162cee8ac4557177d16a283855997ea8538de4ea24205cea71948c6d9068Wrapped(host.instance.exports, ctor, base);
+    };
+  }
+  return imports;
+}
@@ -25,3 +25,86 @@ pub fn method_export_name(rid: &str, ctor: &str, method: &str) -> String {
 pub fn drop_export_name(rid: &str, ctor: &str) -> String {
     format!("pit/{rid}/~{ctor}.drop")
 }
+
+/// Returns `"pit_{rid}_{ctor}_{method}"` — the identifier-safe stand-in for
+/// [`method_export_name`], for languages that cannot export arbitrary names.
+pub fn mangled_method_export_name(rid: &str, ctor: &str, method: &str) -> String {
+    format!("pit_{rid}_{ctor}_{method}")
+}
+
+/// Returns `"pit_{rid}_{ctor}$drop"` — the identifier-safe stand-in for
+/// [`drop_export_name`].
+pub fn mangled_drop_export_name(rid: &str, ctor: &str) -> String {
+    format!("pit_{rid}_{ctor}$drop")
+}
+
+/// Maps a mangled export name back to the PIT export name it stands in for.
+///
+/// Returns `None` unless the name was produced by [`mangled_method_export_name`] or
+/// [`mangled_drop_export_name`] with 64-digit hexadecimal resource and constructor IDs.
+pub fn unmangle_export_name(name: &str) -> Option<String> {
+    let hex = |s: &str| s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit());
+    let rest = name.strip_prefix("pit_")?;
+    let (rid, rest) = (rest.get(..64)?, rest.get(64..)?);
+    let rest = rest.strip_prefix("_")?;
+    let (ctor, rest) = (rest.get(..64)?, rest.get(64..)?);
+    if !hex(rid) || !hex(ctor) {
+        return None;
+    }
+    if rest == "$drop" {
+        return Some(drop_export_name(rid, ctor));
+    }
+    match rest.strip_prefix("_")? {
+        "" => None,
+        method => Some(method_export_name(rid, ctor, method)),
+    }
+}
+
+/// The import name of the batch entry point of an interface, `"@batch"`.
+///
+/// Imported as `pit/{rid}.@batch` with the signature `(externref, i32, i32) -> ()`,
+/// it runs a queue of method calls stored in the caller's linear memory at the given
+/// pointer and length. The caller must export that memory as `memory`; modules
+/// without such an export cannot use batching. Each entry is the little-endian `u32`
+/// index of the method among the interface's methods sorted by name, followed by its
+/// arguments as little-endian bytes: four for `I32` and `F32`, eight for `I64` and
+/// `F64`. Only methods without results and without resource parameters can be batched.
+pub const BATCH_IMPORT_NAME: &str = "@batch";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RID: &str = "70918cf0520229db66dc283561c0ee529a4ce4d8a4a62431ade4aceb542cc0fa";
+    const CTOR: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+
+    #[test]
+    fn unmangles_methods_and_drops() {
+        assert_eq!(
+            unmangle_export_name(&mangled_method_export_name(RID, CTOR, "read")),
+            Some(method_export_name(RID, CTOR, "read"))
+        );
+        assert_eq!(
+            unmangle_export_name(&mangled_method_export_name(RID, CTOR, "read_all")),
+            Some(method_export_name(RID, CTOR, "read_all"))
+        );
+        assert_eq!(
+            unmangle_export_name(&mangled_drop_export_name(RID, CTOR)),
+            Some(drop_export_name(RID, CTOR))
+        );
+    }
+
+    #[test]
+    fn rejects_other_names() {
+        assert_eq!(unmangle_export_name("memory"), None);
+        assert_eq!(unmangle_export_name("pit_read"), None);
+        assert_eq!(unmangle_export_name(&format!("pit_{RID}_{CTOR}")), None);
+        assert_eq!(unmangle_export_name(&format!("pit_{RID}_{CTOR}_")), None);
+        assert_eq!(unmangle_export_name(&format!("pit_{RID}_{CTOR}$dropped")), None);
+        assert_eq!(unmangle_export_name(&format!("pit_{RID}{CTOR}_read")), None);
+        let short = &CTOR[1..];
+        assert_eq!(unmangle_export_name(&format!("pit_{RID}_{short}_read")), None);
+        let bad = CTOR.replace('a', "g");
+        assert_eq!(unmangle_export_name(&format!("pit_{RID}_{bad}_read")), None);
+    }
+}
@@ -447,3 +447,18 @@ pub fn jigger(m: &mut Module, seed: &[u8]) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+/// Renames mangled exports to the PIT export names they stand in for.
+///
+/// Guests written in languages that cannot export arbitrary names, such as
+/// AssemblyScript, export `pit/{rid}/~{ctor}/...` functions under the names built by
+/// [`pit_patch_core::names::mangled_method_export_name`] and
+/// [`pit_patch_core::names::mangled_drop_export_name`]; other exports are unchanged.
+pub fn unmangle(m: &mut Module) -> anyhow::Result<()> {
+    for x in m.exports.iter_mut() {
+        if let Some(a) = pit_patch_core::names::unmangle_export_name(&x.name) {
+            x.name = a;
+        }
+    }
+    Ok(())
+}
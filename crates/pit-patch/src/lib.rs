@@ -12,7 +12,7 @@
 //!
 //! ## Modules
 //!
-//! - [`canon`] - Interface canonicalization and jiggering
+//! - [`canon`] - Interface canonicalization, jiggering and export unmangling
 //! - [`lower`] - Externref lowering to table-based representation
 //! - [`tpit`] - TPIT wrapper generation
 //! - [`tutils`] - Table allocation/deallocation utilities
@@ -43,6 +43,7 @@ use portal_pc_waffle::{
 /// This module provides functions to:
 /// - Canonicalize interface implementations across multiple unique IDs
 /// - Generate unique IDs (jigger) based on module content
+/// - Rename mangled exports to PIT export names (unmangle)
 pub mod canon;
 
 /// Externref lowering to table-based representation.
@@ -11,7 +11,8 @@ use core::{
     iter::once,
     mem::{replace, take},
 };
-use pit_core::{Arg, ResTy};
+use pit_core::{Arg, ResTy, Sig};
+use pit_patch_core::names;
 use portal_pc_waffle::{
     util::*, Block, BlockTarget, Export, ExportKind, Func, FuncDecl, FunctionBody, ImportKind,
     Module, Operator, SignatureData, Table, TableData, Type, Value, WithNullable,
@@ -184,6 +185,15 @@ pub fn shim(
     );
     Ok((ep, end))
 }
+/// The signature of a `pit/{rid}.@batch` import, without the leading resource: the
+/// pointer and length of the queue.
+fn batch_sig() -> Sig {
+    Sig {
+        ann: vec![],
+        params: vec![Arg::I32, Arg::I32],
+        rets: vec![],
+    }
+}
 pub fn wrap(m: &mut Module) -> anyhow::Result<()> {
     let t = m.tables.push(TableData {
         ty: portal_pc_waffle::Type::Heap(WithNullable {
@@ -454,10 +464,15 @@ pub fn wrap(m: &mut Module) -> anyhow::Result<()> {
                         }
                     }
                     None => {
-                        let x = i
-                            .methods
-                            .get(&import.name)
-                            .context("in getting the method")?;
+                        let x = if import.name == names::BATCH_IMPORT_NAME {
+                            batch_sig()
+                        } else {
+                            i.methods
+                                .get(&import.name)
+                                .cloned()
+                                .context("in getting the method")?
+                        };
+                        let x = &x;
                         let p = to_waffle_sig(m, x, false);
                         let p = m.signatures[p].clone();
                         let SignatureData::Func {
@@ -625,6 +640,11 @@ pub fn wrap(m: &mut Module) -> anyhow::Result<()> {
                         }
                     }
                     None => {
+                        // Drop exports take the constructor's arguments, which are unchanged.
+                        if a.ends_with(".drop") {
+                            m.exports.push(export);
+                            continue;
+                        }
                         let (a, b) = a.split_once("/").context("in getting the stuff")?;
                         let x = i.methods.get(b).context("in getting the method")?;
                         let p = to_waffle_sig(m, x, false);
@@ -2,8 +2,8 @@
 //!
 //! Standalone executable for generating Rust code from PIT interface files.
 //!
-//! This is a command-line wrapper around [`pit_rust_guest`] that reads a PIT
-//! interface file, generates Rust code, and writes it to a file or stdout.
+//! This is a command-line wrapper around [`pit_rust_guest`] and [`pit_rust_host_core`]
+//! that reads a PIT interface file, generates Rust code, and writes it to a file or stdout.
 //!
 //! ## Usage
 //!
@@ -14,13 +14,17 @@
 //! # Generate to file, preserving existing doc comments
 //! pit-rust-generator input.pit --preserve-docs output.rs
 //!
+//! # Generate host bindings over `pit-rust-host-lib`
+//! pit-rust-generator input.pit --host output.rs
+//!
 //! # Generate to stdout
 //! pit-rust-generator input.pit
 //! ```
 //!
 //! ## Options
 //!
-//! - `--preserve-docs` - Preserve doc comments (//!) at the top of the output file if it already exists
+//! - `--preserve-docs` - Preserve doc comments (//!) and `use` items at the top of the output file if it already exists
+//! - `--host` - Generate host bindings, with `::pit_rust_host_lib` as their root, instead of guest bindings
 //!
 //! ## Environment Variables
 //!
@@ -31,15 +35,15 @@ use pit_rust_guest::Opts;
 use quote::quote;
 use syn::parse_quote;
 
-/// Extracts doc comments from the top of a Rust source file.
-/// Returns the doc comments as a string, or None if the file doesn't exist or has no doc comments.
+/// Extracts doc comments and `use` items from the top of a Rust source file.
+/// Returns them as a string, or None if the file doesn't exist or starts with neither.
 fn extract_top_doc_comments(path: &str) -> Option<String> {
     let content = std::fs::read_to_string(path).ok()?;
     let mut doc_lines = Vec::new();
     
     for line in content.lines() {
         let trimmed = line.trim();
-        if trimmed.starts_with("//!") {
+        if trimmed.starts_with("//!") || trimmed.starts_with("use ") {
             doc_lines.push(line.to_string());
         } else if trimmed.is_empty() {
             // Empty lines are okay between doc comments
@@ -77,18 +81,25 @@ fn main() -> anyhow::Result<()> {
     };
     
     let mut preserve_docs = false;
+    let mut host = false;
     let dst = loop {
         let Some(arg) = a.next() else {
             break None;
         };
         if arg == "--preserve-docs" {
             preserve_docs = true;
+        } else if arg == "--host" {
+            host = true;
         } else {
             break Some(arg);
         }
     };
     
-    let v = pit_rust_guest::render(&x, &src);
+    let v = if host {
+        pit_rust_host_core::render(&quote! {::pit_rust_host_lib}, &src, &Default::default())
+    } else {
+        pit_rust_guest::render(&x, &src)
+    };
     let v = prettyplease::unparse(&parse_quote!(#v));
     
     let Some(dst) = dst else {
@@ -14,6 +14,15 @@
 //! - Export functions for implementing the interface
 //! - Conversion implementations for boxing trait objects
 //! - A static table for managing live objects
+//! - A `Batch{rid}` wrapper implementing the same trait, which queues calls to
+//!   [`batchable`] methods and sends them to the host in one crossing on `flush`,
+//!   before any other call, and on drop. The queue lives in the module's linear
+//!   memory, which must be exported as `memory`
+//!
+//! Only calls whose results nobody waits for can be queued. A `read8` loop still
+//! crosses once per byte. Interfaces with block methods, such as `common/bulk.pit`,
+//! move many bytes in one call, but their pointers are not translated between
+//! modules, so they only work on buffers sharing the caller's memory.
 //!
 //! ## Usage
 //!
@@ -36,6 +45,16 @@
 //! - `root` - The crate path prefix for runtime types
 //! - `salt` - Additional bytes to include in the unique ID hash
 //! - `tpit` - Whether to use TPIT (table-based) or externref
+//!
+//! ## TPIT resources
+//!
+//! In TPIT mode, resources are `Tpit` handles, passed as `u32`s at the boundary:
+//! - Arguments taking ownership (`&`) are passed as owned `Tpit`s, and given up by
+//!   the caller
+//! - Other arguments are passed as `&mut Tpit`s, and stay with the caller
+//! - Results transferring ownership are returned as owned `Tpit`s
+//! - Other results are returned as `Lent` handles borrowing from `self`; the callee
+//!   keeps the resource alive, and the caller releases only its table slot
 
 use pit_core::{Arg, Interface, ResTy, Sig};
 use proc_macro2::TokenStream;
@@ -125,9 +144,13 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
                 };
                 if let Arg::Resource { ty, nullable, take, ann } = b{
                     if opts.tpit{
-                        if !*take{
+                        if *take{
+                            c = quote!{
+                                #c.forget_to_ptr()
+                            }
+                        }else{
                             c = quote!{
-                                #root::tpit_rt::Tpit::summon(&mut #c)
+                                #c.ptr()
                             }
                         }
                     }
@@ -138,6 +161,26 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
                 #(#params),*
             }
         };
+        let call = quote!{
+            unsafe{go(#self_arg, #params_tokens)}
+        };
+        let body = match tpit_rets(opts, b, |r, take| if take{
+            quote!{
+                unsafe{#root::tpit_rt::Tpit::new(#r)}
+            }
+        }else{
+            quote!{
+                unsafe{#root::tpit_rt::Lent::new(#r)}
+            }
+        }){
+            None => quote!{
+                return #call;
+            },
+            Some(rets) => quote!{
+                let r = #call;
+                return #rets;
+            },
+        };
         quote! {
             fn #method_name #sig {
                 #xref
@@ -146,10 +189,43 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
                     #[link_name = #a]
                     fn go #sig_go;
                 }
-                return unsafe{go(#self_arg, #params_tokens)};
+                #body
             }
         }
     });
+    let batch_id = format_ident!("Batch{}", i.rid_str());
+    let batch_impls = i.methods.iter().enumerate().map(|(j, (a, b))| {
+        let method_name = format_ident!("{a}");
+        let sig = render_sig(opts, root, i, b, &quote! {&mut self}, false);
+        let params = (0..b.params.len())
+            .map(|k| format_ident!("p{k}"))
+            .collect::<Vec<_>>();
+        let j = j as u32;
+        if batchable(b) {
+            quote! {
+                fn #method_name #sig {
+                    self.queue.extend_from_slice(&#j.to_le_bytes());
+                    #(self.queue.extend_from_slice(&#params.to_le_bytes());)*
+                    if self.queue.len() >= BATCH_LIMIT {
+                        self.flush();
+                    }
+                }
+            }
+        } else {
+            quote! {
+                fn #method_name #sig {
+                    self.flush();
+                    return #id2::#method_name(&mut self.inner, #(#params),*);
+                }
+            }
+        }
+    });
+    let batch_self_arg = if opts.tpit {
+        quote! { self.inner.ptr() }
+    } else {
+        quote! { &mut self.inner }
+    };
+    let batch_module = format!("{t}pit/{}", i.rid_str());
     let chains2 = i.methods.iter().map(|(a,b)| {
         let export_name = format!("{t}pit/{id}/~{ha}/{a}");
         let method_name = format_ident!("{a}");
@@ -162,9 +238,13 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
                 };
                 if let Arg::Resource { ty, nullable, take, ann } = b{
                     if opts.tpit{
-                        if !*take{
+                        if *take{
                             c = quote!{
-                                #c.ptr()
+                                unsafe{#root::tpit_rt::Tpit::new(#c)}
+                            }
+                        }else{
+                            c = quote!{
+                                &mut *unsafe{#root::tpit_rt::Lent::new(#c)}
                             }
                         }
                     }
@@ -175,11 +255,31 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
                 #(#params),*
             }
         };
+        let call = quote!{
+            unsafe{&mut *(TABLE.all.get())}.get_mut(&id).unwrap().#method_name(#params_tokens)
+        };
+        let body = match tpit_rets(opts, b, |r, take| if take{
+            quote!{
+                #r.forget_to_ptr()
+            }
+        }else{
+            quote!{
+                #r.ptr()
+            }
+        }){
+            None => quote!{
+                return #call;
+            },
+            Some(rets) => quote!{
+                let r = #call;
+                return #rets;
+            },
+        };
         quote! {
            #xref
             #[export_name = #export_name]
             extern "C" fn #method_name #sig {
-                return unsafe{&mut *(TABLE.all.get())}.get_mut(&id).unwrap().#method_name(#params_tokens);
+                #body
             }
         }
     });
@@ -198,6 +298,10 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
     quote! {
         pub trait #id2{
             #(#methods)*
+        }
+        pub struct #batch_id{
+            pub inner: #res<Box<dyn #id2>>,
+            queue: Vec<u8>,
         }
             const _: () = {
                 #[link_section = ".pit-types"]
@@ -228,6 +332,38 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
                     };
                 }
                 #(#chains2)*
+                const BATCH_LIMIT: usize = 4096;
+                impl #batch_id{
+                    pub fn new(inner: #res<Box<dyn #id2>>) -> Self{
+                        Self{
+                            inner,
+                            queue: vec![],
+                        }
+                    }
+                    pub fn flush(&mut self){
+                        if self.queue.is_empty(){
+                            return;
+                        }
+                        #xref
+                        #[link(wasm_import_module = #batch_module)]
+                        extern "C"{
+                            #[link_name = "@batch"]
+                            fn go(this: #rx, p0: u32, p1: u32);
+                        }
+                        unsafe{
+                            go(#batch_self_arg, self.queue.as_ptr() as u32, self.queue.len() as u32)
+                        };
+                        self.queue.clear();
+                    }
+                }
+                impl Drop for #batch_id{
+                    fn drop(&mut self){
+                        self.flush();
+                    }
+                }
+                impl #id2 for #batch_id{
+                    #(#batch_impls)*
+                }
                 impl From<Box<dyn #id2>> for #res<Box<dyn #id2>>{
                     fn from(a: Box<dyn #id2>) -> Self{
                         #xref
@@ -252,6 +388,46 @@ pub fn render(opts: &Opts, i: &Interface) -> TokenStream {
             // }
     }
 }
+/// Renders the return value `r` of a call crossing the TPIT boundary, applying `conv`
+/// to each resource it contains, along with whether that resource is taken.
+///
+/// Returns `None` outside of TPIT mode and for methods returning no resources, whose
+/// results need no conversion.
+fn tpit_rets(
+    opts: &Opts,
+    s: &Sig,
+    conv: impl Fn(TokenStream, bool) -> TokenStream,
+) -> Option<TokenStream> {
+    if !opts.tpit || !s.rets.iter().any(|a| matches!(a, Arg::Resource { .. })) {
+        return None;
+    }
+    let one = s.rets.len() == 1;
+    let rets = s.rets.iter().enumerate().map(|(k, a)| {
+        let r = if one {
+            quote! { r }
+        } else {
+            let k = proc_macro2::Literal::usize_unsuffixed(k);
+            quote! { r.#k }
+        };
+        match a {
+            Arg::Resource { take, .. } => conv(r, *take),
+            _ => r,
+        }
+    });
+    Some(if one {
+        quote! { #(#rets)* }
+    } else {
+        quote! { (#(#rets),*) }
+    })
+}
+/// Returns whether calls to a method can be queued by the generated `Batch{rid}`
+/// wrapper: it has no results and takes no resources.
+///
+/// Such calls are sent through the `pit/{rid}.@batch` import in one crossing. Methods
+/// with results are not batched, since the caller needs each result before it goes on.
+pub fn batchable(s: &Sig) -> bool {
+    s.rets.is_empty() && !s.params.iter().any(|a| matches!(a, Arg::Resource { .. }))
+}
 /// Renders a method signature as Rust code.
 ///
 /// # Arguments
@@ -284,7 +460,7 @@ pub fn render_sig(
             quote!(#name : #b)
         });
     let params = once(self_).cloned().chain(params);
-    let rets = s.rets.iter().map(|a| render_ty(opts, root, base, a, ffi));
+    let rets = s.rets.iter().map(|a| render_ret_ty(opts, root, base, a, ffi));
     quote! {
         (#(#params),*) -> (#(#rets),*)
     }
@@ -359,30 +535,14 @@ pub fn render_ty(
                 };
                 ty
             } else {
-                let ty = match ty {
-                    ResTy::Of(a) => {
-                        let trait_name = format_ident!("R{}", hex::encode(a));
-                        quote! {
-                            #root::tpit_rt::Tpit<Box<dyn #trait_name>>
-                        }
-                    },
-                    ResTy::None => quote! {
-                        #root::tpit_rt::Tpit<()>
-                    },
-                    ResTy::This => {
-                        let trait_name = format_ident!("R{}", base.rid_str());
-                        quote! {
-                            #root::tpit_rt::Tpit<Box<dyn #trait_name>>
-                        }
-                    },
-                    _ => todo!(),
+                let ty = tpit_target(base, ty);
+                let mut ty = quote! {
+                    #root::tpit_rt::Tpit<#ty>
                 };
-                let mut ty = if *take {
-                    ty
-                } else {
-                    quote! {&mut #ty}
-                };
-                if *take && ffi {
+                if !*take {
+                    ty = quote! {&mut #ty}
+                }
+                if ffi {
                     ty = quote! {u32}
                 }
                 ty
@@ -391,3 +551,171 @@ pub fn render_ty(
         _ => todo!(), // Arg::Func(_) => todo!()
     }
 }
+/// Renders a PIT result type as a Rust type.
+///
+/// Like [`render_ty`], except that in TPIT mode, resources returned without ownership
+/// transfer are rendered as `Lent` handles borrowing from `self`.
+pub fn render_ret_ty(
+    opts: &Opts,
+    root: &TokenStream,
+    base: &Interface,
+    p: &Arg,
+    ffi: bool,
+) -> TokenStream {
+    match p {
+        Arg::Resource {
+            ty, take: false, ..
+        } if opts.tpit && !ffi => {
+            let ty = tpit_target(base, ty);
+            quote! {
+                #root::tpit_rt::Lent<'_, #ty>
+            }
+        }
+        _ => render_ty(opts, root, base, p, ffi),
+    }
+}
+/// Renders the type parameter of the `Tpit` handle for a resource type.
+fn tpit_target(base: &Interface, ty: &ResTy) -> TokenStream {
+    match ty {
+        ResTy::Of(a) => {
+            let trait_name = format_ident!("R{}", hex::encode(a));
+            quote! {
+                Box<dyn #trait_name>
+            }
+        }
+        ResTy::None => quote! {
+            ()
+        },
+        ResTy::This => {
+            let trait_name = format_ident!("R{}", base.rid_str());
+            quote! {
+                Box<dyn #trait_name>
+            }
+        }
+        _ => todo!(),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IFACE: &str = "{
+    give(R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5&) -> ();
+    lend(R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5) -> ();
+    take() -> (R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5&);
+    borrow() -> (R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5)
+}";
+
+    /// Removes whitespace, whose placement in rendered tokens depends on how they
+    /// were spliced together.
+    fn squash(t: TokenStream) -> String {
+        t.to_string().split_whitespace().collect()
+    }
+
+    fn tpit() -> String {
+        let (_, i) = pit_core::parse_interface(IFACE).unwrap();
+        let opts = Opts {
+            root: quote! {},
+            salt: vec![],
+            tpit: true,
+        };
+        squash(render(&opts, &i))
+    }
+
+    fn has(s: &str, t: TokenStream) {
+        let t = squash(t);
+        assert!(s.contains(&t), "missing `{t}`");
+    }
+
+    #[test]
+    fn tpit_trait_types() {
+        let s = tpit();
+        has(&s, quote! {
+            fn give(&mut self, p0: ::tpit_rt::Tpit<Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>) -> ();
+        });
+        has(&s, quote! {
+            fn lend(&mut self, p0: &mut ::tpit_rt::Tpit<Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>) -> ();
+        });
+        has(&s, quote! {
+            fn take(&mut self) -> (::tpit_rt::Tpit<Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>);
+        });
+        has(&s, quote! {
+            fn borrow(&mut self) -> (::tpit_rt::Lent<'_, Box<dyn R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5>>);
+        });
+    }
+
+    #[test]
+    fn tpit_imports() {
+        let s = tpit();
+        has(&s, quote! { fn go(this: u32, p0: u32) -> (); });
+        has(&s, quote! { fn go(this: u32) -> (u32); });
+        has(&s, quote! { return unsafe { go(self.ptr(), p0.forget_to_ptr()) }; });
+        has(&s, quote! { return unsafe { go(self.ptr(), p0.ptr()) }; });
+        has(&s, quote! { return unsafe { ::tpit_rt::Tpit::new(r) }; });
+        has(&s, quote! { return unsafe { ::tpit_rt::Lent::new(r) }; });
+    }
+
+    #[test]
+    fn tpit_exports() {
+        let s = tpit();
+        has(&s, quote! { .give(unsafe { ::tpit_rt::Tpit::new(p0) }) });
+        has(&s, quote! { .lend(&mut *unsafe { ::tpit_rt::Lent::new(p0) }) });
+        has(&s, quote! { return r.forget_to_ptr(); });
+        has(&s, quote! { return r.ptr(); });
+    }
+
+    /// Type-checks the TPIT bindings of `IFACE`, together with those of the buffer
+    /// interface it refers to, with `rustc` against `tpit-rt`.
+    #[test]
+    fn renders_well_typed_tpit_rust() {
+        let opts = Opts {
+            root: quote! {},
+            salt: vec![],
+            tpit: true,
+        };
+        let code = [
+            "{\n    read8(I32) -> (I32);\n    size() -> (I32);\n    write8(I32,I32) -> ()\n}",
+            IFACE,
+        ]
+        .map(|i| render(&opts, &pit_core::parse_interface(i).unwrap().1).to_string())
+        .join("\n");
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let out = std::env::temp_dir().join(format!("pit-rust-guest-{}", std::process::id()));
+        std::fs::create_dir_all(&out).unwrap();
+        let src = out.join("bindings.rs");
+        std::fs::write(&src, code).unwrap();
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+        let run = |args: &[&std::ffi::OsStr]| {
+            let o = std::process::Command::new(&rustc)
+                .args(["--edition", "2021", "--out-dir"])
+                .arg(&out)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+        };
+        run(&[
+            "--crate-type=rlib".as_ref(),
+            "--crate-name=tpit_rt".as_ref(),
+            dir.join("../tpit-rt/src/lib.rs").as_os_str(),
+        ]);
+        let rt = out.join("libtpit_rt.rlib");
+        run(&[
+            "--crate-type=lib".as_ref(),
+            "--emit=metadata".as_ref(),
+            "--extern".as_ref(),
+            format!("tpit_rt={}", rt.display()).as_ref(),
+            src.as_os_str(),
+        ]);
+        std::fs::remove_dir_all(&out).unwrap();
+    }
+
+    #[test]
+    fn batchable_methods() {
+        let (_, i) = pit_core::parse_interface(IFACE).unwrap();
+        assert!(i.methods.values().all(|s| !batchable(s)));
+        let (_, i) = pit_core::parse_interface("{\n    write8(I32,I32) -> ();\n    size() -> (I32)\n}").unwrap();
+        assert!(batchable(&i.methods["write8"]));
+        assert!(!batchable(&i.methods["size"]));
+    }
+}
@@ -11,6 +11,8 @@
 //! For each PIT interface, this crate generates:
 //! - A trait definition with all interface methods
 //! - Method dispatch implementations for wrapped resources
+//! - An `IntoWrapped` implementation so host objects implementing the trait
+//!   can be passed to guests with `pit_rust_host_lib::register`
 //! - Value conversion between Rust types and WebAssembly values
 //!
 //! ## Usage
@@ -56,7 +58,7 @@ pub fn render(root: &TokenStream, i: &Interface, opts: &Opts) -> TokenStream {
             ctx: #root::wasm_runtime_layer::StoreContextMut<'_,U,E>
         });
         quote! {
-            fn #method_name #sig
+            fn #method_name #sig;
         }
     });
     let impls = i.methods.iter().enumerate().map(|(c, (a, b))| {
@@ -68,38 +70,72 @@ pub fn render(root: &TokenStream, i: &Interface, opts: &Opts) -> TokenStream {
                 let param = format_ident!("p{pi}");
                 render_new_val(root, a, quote! {#param})
             });
-        let init =
-            once(quote! {#root::wasm_runtime_layer::Value::I32(self.base as #root::core::primitive::i32)}).chain(init);
         let fini = b.rets.iter().enumerate().map(|(ri, r)| {
-            render_base_val(root, r, quote! {&rets[#ri]})
+            render_base_val(root, r, quote! {#root::core::clone::Clone::clone(&rets[#ri])})
         });
         let method_name = format_ident!("{a}");
         let sig = render_sig(root, b, &quote! {&self}, quote! {
             ctx: #root::wasm_runtime_layer::StoreContextMut<'_,U,E>
         });
         let c1 = c + 1;
+        // methods without results leave `rets` unused
+        let rets = if b.rets.is_empty() {
+            quote! {_}
+        } else {
+            quote! {rets}
+        };
         quote! {
             fn #method_name #sig {
+                let mut ctx = ctx;
                 let a = #root::core::clone::Clone::clone(&self.all[#c1]);
                 let args = #root::alloc::vec![#(#init),*];
-                let mut rets = a(ctx,args);
+                let #rets = a(#root::wasm_runtime_layer::AsContextMut::as_context_mut(&mut ctx),args)?;
                 return Ok((#(#fini),*))
             }
         }
     });
     let injects = i.methods.iter().map(|(a,b)|{
-        let init = b.params.iter().enumerate().map(|(pi,a)| render_base_val(root, a, quote! {&args[#pi + 1]}));
+        let init = b.params.iter().enumerate().map(|(pi,a)| {
+            let param = format_ident!("p{pi}");
+            let v = render_base_val(root, a, quote! {#root::core::clone::Clone::clone(&args[#pi])});
+            quote! {
+                let #param = #v;
+            }
+        });
+        let params = (0..b.params.len()).map(|pi| format_ident!("p{pi}"));
         let fini = b.rets.iter().enumerate().map(|(ri,r)|{
             let idx = Index{index: ri as u32, span: root.span()};
             render_new_val(root, r, quote! {r . #idx})
         });
+        // a single return value is not wrapped in a tuple by `render_sig`
+        let fini = match &b.rets[..] {
+            [r] => {
+                let f = render_new_val(root, r, quote! {r});
+                quote! {#f}
+            }
+            _ => quote! {#(#fini),*},
+        };
         let method_name = format_ident!("{a}");
+        // methods without params or results leave `args` or `r` unused
+        let args = if b.params.is_empty() {
+            format_ident!("_args")
+        } else {
+            format_ident!("args")
+        };
+        let r = if b.rets.is_empty() {
+            quote! {_}
+        } else {
+            quote! {r}
+        };
         quote!{
-            let r = a.clone();
-            #root::alloc::sync::Arc::new(ctx,move|ctx,args|{
-                let r = r.#method_name(ctx,#(#init),*)
-                Ok(#root::alloc::vec![#(#fini),*])
-            })
+            {
+                let r = #root::alloc::sync::Arc::clone(&self);
+                #root::alloc::sync::Arc::new(move|mut ctx: #root::wasm_runtime_layer::StoreContextMut<'_,U,E>,#args: #root::alloc::vec::Vec<#root::wasm_runtime_layer::Value>| -> #root::anyhow::Result<#root::alloc::vec::Vec<#root::wasm_runtime_layer::Value>>{
+                    #(#init)*
+                    let #r = r.#method_name(#root::wasm_runtime_layer::AsContextMut::as_context_mut(&mut ctx),#(#params),*)?;
+                    Ok(#root::alloc::vec![#fini])
+                }) as #root::WrappedFn<U,E>
+            }
         }
     });
     // let p = match opts.guest.as_ref() {
@@ -109,9 +145,14 @@ pub fn render(root: &TokenStream, i: &Interface, opts: &Opts) -> TokenStream {
     let i_str = i.to_string();
     let all_items = {
         let finalize_item = quote! {
-            let r = #root::core::clone::Clone::clone(&a);
-            unsafe{
-                #root::alloc::sync::Arc::new(move|ctx,args|{r.finalize(ctx);Ok(vec![])})
+            {
+                let r = #root::alloc::sync::Arc::clone(&self);
+                #root::alloc::sync::Arc::new(move|ctx: #root::wasm_runtime_layer::StoreContextMut<'_,U,E>,_args: #root::alloc::vec::Vec<#root::wasm_runtime_layer::Value>| -> #root::anyhow::Result<#root::alloc::vec::Vec<#root::wasm_runtime_layer::Value>>{
+                    unsafe{
+                        r.finalize(ctx)?;
+                    }
+                    Ok(#root::alloc::vec![])
+                }) as #root::WrappedFn<U,E>
             }
         };
         let all = once(finalize_item).chain(injects);
@@ -132,15 +173,22 @@ pub fn render(root: &TokenStream, i: &Interface, opts: &Opts) -> TokenStream {
                     Ok(())
                 }
             }
-            impl<U: 'static,E: #root::wasm_runtime_layer::backend::WasmEngine> From<#root::alloc::sync::Arc<dyn $id<U,E>> for #root::RWrapped<U,E>{
-                fn from(a: #root::alloc::sync::Arc<dyn $id<U,E>>) -> Self{
-                    Self{
-                        rid: #root::alloc::sync::Arc::new(#root::pit_core::Interface::parse_interface(#i_str).ok().unwrap()),
-                        all: #root::alloc::vec![#all_items]
+            impl<U: 'static,E: #root::wasm_runtime_layer::backend::WasmEngine> #root::IntoWrapped<U,E> for dyn #id<U,E> + Send + Sync{
+                fn into_wrapped(self: #root::alloc::sync::Arc<Self>) -> #root::Wrapped<U,E>{
+                    static RID: #root::spin::Once<#root::alloc::sync::Arc<#root::pit_core::Interface>> = #root::spin::Once::new();
+                    let rid = RID.call_once(||{
+                        let (_, i) = #root::pit_core::parse_interface(#i_str).expect("rendered interfaces parse");
+                        #root::alloc::sync::Arc::new(i)
+                    });
+                    #root::Wrapped{
+                        rid: #root::alloc::sync::Arc::clone(rid),
+                        all: #root::alloc::vec![#all_items],
+                        ctor: #root::core::option::Option::None,
+                        base: #root::alloc::vec![],
                     }
                 }
             }
-        }
+        };
     }
 }
 /// Renders a method signature as Rust code for host bindings.
@@ -289,27 +337,19 @@ pub fn render_ty(root: &TokenStream, p: &Arg) -> TokenStream {
 ///
 /// A `TokenStream` containing the extraction code.
 pub fn render_base_val(root: &TokenStream, p: &Arg, x: TokenStream) -> TokenStream {
-    let v = match p {
-        Arg::I32 => quote! {
-            let #root::wasm_runtime_layer::Value::I32(t) = #x else{
-                #root::anyhow::bail!("invalid param")
-            }
-        },
-        Arg::I64 => quote! {
-            let #root::wasm_runtime_layer::Value::I64(t) = #x else{
-                #root::anyhow::bail!("invalid param")
-            }
-        },
-        Arg::F32 => quote! {
-            let #root::wasm_runtime_layer::Value::F32(t) = #x else{
-                #root::anyhow::bail!("invalid param")
-            }
-        },
-        Arg::F64 => quote! {
-            let #root::wasm_runtime_layer::Value::F64(t) = #x else{
-                #root::anyhow::bail!("invalid param")
+    let num = |variant: TokenStream, expected: &str, cast: TokenStream| {
+        quote! {
+            match #x{
+                #root::wasm_runtime_layer::Value::#variant(t) => t #cast,
+                v => return #root::core::result::Result::Err(#root::PitError::mismatch(#expected, &v).into())
             }
-        },
+        }
+    };
+    match p {
+        Arg::I32 => num(quote! {I32}, "i32", quote! {as #root::core::primitive::u32}),
+        Arg::I64 => num(quote! {I64}, "i64", quote! {as #root::core::primitive::u64}),
+        Arg::F32 => num(quote! {F32}, "f32", quote! {}),
+        Arg::F64 => num(quote! {F64}, "f64", quote! {}),
         Arg::Resource {
             ty,
             nullable,
@@ -317,35 +357,42 @@ pub fn render_base_val(root: &TokenStream, p: &Arg, x: TokenStream) -> TokenStre
             ann,
         } => {
             let mut a = quote! {
-                let #root::wasm_runtime_layer::Value::ExternRef(t) = #x else{
-                    #root::anyhow::bail!("invalid param")
-                }
+                let t = match #x{
+                    #root::wasm_runtime_layer::Value::ExternRef(t) => t,
+                    v => return #root::core::result::Result::Err(#root::PitError::mismatch("externref", &v).into())
+                };
             };
             if !matches!(ty, ResTy::None) {
                 quote!{
-                    let t = match t.downcast::<'_,'_,                #root::alloc::sync::Arc<#root::Wrapped<U,E>>,U,E>(ctx){
-                        #root::core::result::Result::Ok(t) => Arc::new(t.clone()),
-                        #root::core::result::Result::Err(_) =>                     #root::anyhow::bail!("invalid param")
+                    let t = match t{
+                        #root::core::option::Option::None => #root::core::option::Option::None,
+                        #root::core::option::Option::Some(t) => match t.downcast::<'_,'_,#root::Wrapped<U,E>,U,E>(#root::wasm_runtime_layer::AsContext::as_context(&ctx)){
+                            #root::core::result::Result::Ok(t) => #root::core::option::Option::Some(#root::alloc::sync::Arc::new(#root::core::clone::Clone::clone(t))),
+                            #root::core::result::Result::Err(_) => return #root::core::result::Result::Err(#root::PitError::mismatch(
+                                "PIT resource",
+                                &#root::wasm_runtime_layer::Value::ExternRef(#root::core::option::Option::Some(#root::core::clone::Clone::clone(&t))),
+                            ).into())
                         }
+                    };
                 }.to_tokens(&mut a);
             }
             if !*nullable {
                 quote! {
                     let t = match t{
-                        Some(a) => a,
-                        None => #root::anyhow::bail!("invalid param")
-                    }
+                        #root::core::option::Option::Some(t) => t,
+                        #root::core::option::Option::None => return #root::core::result::Result::Err(#root::PitError::NullResource.into())
+                    };
                 }
                 .to_tokens(&mut a)
             }
-            a
+            quote! {
+                {
+                    #a
+                    t
+                }
+            }
         }
         _ => todo!(),
-    };
-    quote! {
-        {
-            #v;t
-        }
     }
 }
 /// Renders code to create a `wasm_runtime_layer::Value` from a Rust value.
@@ -362,10 +409,10 @@ pub fn render_base_val(root: &TokenStream, p: &Arg, x: TokenStream) -> TokenStre
 pub fn render_new_val(root: &TokenStream, p: &Arg, t: TokenStream) -> TokenStream {
     match p {
         Arg::I32 => quote! {
-            #root::wasm_runtime_layer::Value::I32(#t)
+            #root::wasm_runtime_layer::Value::I32((#t) as #root::core::primitive::i32)
         },
         Arg::I64 => quote! {
-            #root::wasm_runtime_layer::Value::I64(#t)
+            #root::wasm_runtime_layer::Value::I64((#t) as #root::core::primitive::i64)
         },
         Arg::F32 => quote! {
             #root::wasm_runtime_layer::Value::F32(#t)
@@ -385,10 +432,7 @@ pub fn render_new_val(root: &TokenStream, p: &Arg, t: TokenStream) -> TokenStrea
                         #root::wasm_runtime_layer::ExternRef::new(ctx,t)
                     },
                     _ => quote! {
-                        match t.to_any().downcast_ref::<#root::alloc::sync::Arc<#root::Wrapped<U,E>>>().cloned(){
-                            #root::core::option::Option::None =>                     #root::wasm_runtime_layer::ExternRef::new(ctx,t),
-                            #root::core::option::Option::Some(t) => #root::wasm_runtime_layer::ExternRef::new(ctx,t),
-                        }
+                        #root::wasm_runtime_layer::ExternRef::new(#root::wasm_runtime_layer::AsContextMut::as_context_mut(&mut ctx),#root::Wrapped::clone(&t))
                     },
                 };
                 quote! {
@@ -422,3 +466,97 @@ pub fn render_new_val(root: &TokenStream, p: &Arg, t: TokenStream) -> TokenStrea
         _ => todo!(),
     }
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IFACE: &str = "{
+    read8(I32) -> (I32);
+    write8(I32,I32) -> ();
+    size() -> (I32);
+    seek(I64,F64) -> (I64,F32);
+    pass(R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5) -> (R867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5n);
+    raw(R_) -> ()
+}";
+
+    /// Removes whitespace, whose placement in rendered tokens depends on how they
+    /// were spliced together.
+    fn squash(t: TokenStream) -> String {
+        t.to_string().split_whitespace().collect()
+    }
+
+    fn rendered() -> TokenStream {
+        let (_, i) = pit_core::parse_interface(IFACE).unwrap();
+        render(&quote! { ::pit_rust_host_lib }, &i, &Opts::default())
+    }
+
+    /// Type-checks rendered bindings with `rustc` against `tests/stub/root.rs`, denying
+    /// warnings other than the parentheses `render_sig` puts around single results.
+    #[test]
+    fn renders_well_typed_rust() {
+        let (_, i) = pit_core::parse_interface(IFACE).unwrap();
+        let code = render(&quote! { crate::root }, &i, &Opts::default());
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let stub = std::fs::read_to_string(dir.join("tests/stub/root.rs")).unwrap();
+        let out = std::env::temp_dir().join(format!("pit-rust-host-core-{}", std::process::id()));
+        std::fs::create_dir_all(&out).unwrap();
+        let src = out.join("bindings.rs");
+        std::fs::write(&src, format!("{stub}\n{code}\n")).unwrap();
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+        let o = std::process::Command::new(rustc)
+            .args(["--edition", "2021", "--crate-type", "lib", "--emit=metadata"])
+            .args(["-D", "warnings", "-A", "unused_parens", "--out-dir"])
+            .arg(&out)
+            .arg(&src)
+            .output()
+            .unwrap();
+        std::fs::remove_dir_all(&out).unwrap();
+        assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+    }
+
+    #[test]
+    fn reports_typed_errors() {
+        let s = squash(rendered());
+        assert!(!s.contains("bail"));
+        assert!(s.contains(&squash(quote! {
+            v => return ::pit_rust_host_lib::core::result::Result::Err(
+                ::pit_rust_host_lib::PitError::mismatch("i32", &v).into()
+            )
+        })));
+        assert!(s.contains(&squash(quote! {
+            ::pit_rust_host_lib::PitError::NullResource.into()
+        })));
+    }
+
+    #[test]
+    fn parses_interface_once() {
+        let s = squash(rendered());
+        assert_eq!(s.matches("parse_interface").count(), 1);
+        assert!(s.contains(&squash(quote! {
+            static RID: ::pit_rust_host_lib::spin::Once<
+                ::pit_rust_host_lib::alloc::sync::Arc<::pit_rust_host_lib::pit_core::Interface>
+            > = ::pit_rust_host_lib::spin::Once::new();
+        })));
+    }
+
+    #[test]
+    fn casts_integers() {
+        let root = quote! { r };
+        let s = squash(render_new_val(&root, &Arg::I32, quote! { p0 }));
+        assert_eq!(s, squash(quote! {
+            r::wasm_runtime_layer::Value::I32((p0) as r::core::primitive::i32)
+        }));
+        let s = squash(render_new_val(&root, &Arg::I64, quote! { p0 }));
+        assert_eq!(s, squash(quote! {
+            r::wasm_runtime_layer::Value::I64((p0) as r::core::primitive::i64)
+        }));
+        let s = squash(render_base_val(&root, &Arg::I32, quote! { x }));
+        assert!(s.contains(&squash(quote! {
+            r::wasm_runtime_layer::Value::I32(t) => t as r::core::primitive::u32
+        })));
+        let s = squash(render_base_val(&root, &Arg::I64, quote! { x }));
+        assert!(s.contains(&squash(quote! {
+            r::wasm_runtime_layer::Value::I64(t) => t as r::core::primitive::u64
+        })));
+    }
+}
@@ -0,0 +1,128 @@
+//! A stand-in for the parts of `pit_rust_host_lib` that rendered bindings use, so
+//! they can be type-checked with plain `rustc`.
+
+extern crate alloc;
+
+pub mod root {
+    pub extern crate alloc;
+    pub use core;
+
+    pub mod anyhow {
+        pub type Error = alloc::boxed::Box<dyn core::error::Error + Send + Sync>;
+        pub type Result<T, E = Error> = core::result::Result<T, E>;
+    }
+
+    pub mod spin {
+        pub struct Once<T>(core::marker::PhantomData<T>);
+        impl<T> Once<T> {
+            pub const fn new() -> Self {
+                Once(core::marker::PhantomData)
+            }
+            pub fn call_once(&self, _f: impl FnOnce() -> T) -> &T {
+                unimplemented!()
+            }
+        }
+    }
+
+    pub mod pit_core {
+        pub struct Interface;
+        pub fn parse_interface(_s: &str) -> Result<(&str, Interface), ()> {
+            unimplemented!()
+        }
+    }
+
+    pub mod wasm_runtime_layer {
+        use core::marker::PhantomData;
+
+        pub mod backend {
+            pub trait WasmEngine: 'static {}
+        }
+
+        #[derive(Clone)]
+        pub struct ExternRef;
+        impl ExternRef {
+            pub fn new<T: 'static + Send + Sync>(_ctx: impl AsContextMut, _t: T) -> Self {
+                ExternRef
+            }
+            pub fn downcast<'a, 's: 'a, T: 'static, S: 's, E: backend::WasmEngine>(
+                &'a self,
+                _ctx: StoreContext<'s, S, E>,
+            ) -> super::anyhow::Result<&'a T> {
+                unimplemented!()
+            }
+        }
+
+        #[derive(Clone)]
+        pub enum Value {
+            I32(i32),
+            I64(i64),
+            F32(f32),
+            F64(f64),
+            ExternRef(Option<ExternRef>),
+        }
+
+        pub struct StoreContext<'a, U, E>(PhantomData<&'a (U, E)>);
+        pub struct StoreContextMut<'a, U, E>(PhantomData<&'a mut (U, E)>);
+
+        pub trait AsContext {
+            type UserState;
+            type Engine: backend::WasmEngine;
+            fn as_context(&self) -> StoreContext<'_, Self::UserState, Self::Engine>;
+        }
+        pub trait AsContextMut: AsContext {
+            fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState, Self::Engine>;
+        }
+        impl<U, E: backend::WasmEngine> AsContext for StoreContextMut<'_, U, E> {
+            type UserState = U;
+            type Engine = E;
+            fn as_context(&self) -> StoreContext<'_, U, E> {
+                StoreContext(PhantomData)
+            }
+        }
+        impl<U, E: backend::WasmEngine> AsContextMut for StoreContextMut<'_, U, E> {
+            fn as_context_mut(&mut self) -> StoreContextMut<'_, U, E> {
+                StoreContextMut(PhantomData)
+            }
+        }
+    }
+
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use wasm_runtime_layer::{backend::WasmEngine, StoreContextMut, Value};
+
+    #[derive(Debug)]
+    pub enum PitError {
+        NullResource,
+        TypeMismatch,
+    }
+    impl PitError {
+        pub fn mismatch(_expected: impl Into<alloc::string::String>, _actual: &Value) -> Self {
+            PitError::TypeMismatch
+        }
+    }
+    impl core::fmt::Display for PitError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{self:?}")
+        }
+    }
+    impl core::error::Error for PitError {}
+
+    pub type WrappedFn<U, E> = Arc<
+        dyn Fn(StoreContextMut<'_, U, E>, Vec<Value>) -> anyhow::Result<Vec<Value>> + Send + Sync,
+    >;
+    pub struct Wrapped<U: 'static, E: WasmEngine> {
+        pub rid: Arc<pit_core::Interface>,
+        pub all: Vec<WrappedFn<U, E>>,
+        pub ctor: Option<alloc::string::String>,
+        pub base: Vec<Value>,
+    }
+    impl<U: 'static, E: WasmEngine> Clone for Wrapped<U, E> {
+        fn clone(&self) -> Self {
+            unimplemented!()
+        }
+    }
+    pub type RWrapped<U, E> = Arc<Wrapped<U, E>>;
+    pub trait IntoWrapped<U: 'static, E: WasmEngine> {
+        fn into_wrapped(self: Arc<Self>) -> Wrapped<U, E>;
+    }
+}
@@ -0,0 +1,258 @@
+//! # Batched calls
+//!
+//! Calling `write8` a million times costs a million boundary crossings. Guests can
+//! instead queue calls to methods without results in their own memory and run the
+//! whole queue through a single `pit/{rid}.@batch` import, which [`emit`](crate::emit)
+//! and [`Linker`](crate::linker::Linker) define when a module imports it. The queue
+//! is read from the calling instance's `memory` export.
+//!
+//! Calls with results, such as `read8`, cannot be queued. Interfaces with block
+//! methods, such as `common/bulk.pit`, move many bytes in one call, but only between
+//! a buffer and callers sharing its memory: their pointers are not translated, and
+//! nothing copies between the memories of separate instances.
+//!
+//! The queue format is described at
+//! [`BATCH_IMPORT_NAME`](pit_patch_core::names::BATCH_IMPORT_NAME). Each queued call
+//! is dispatched like a regular call, so interceptors and policies still see every
+//! one of them.
+
+use crate::{PitError, Wrapped};
+use alloc::format;
+use alloc::vec;
+use core::iter::empty;
+use pit_core::{Arg, Interface, Sig};
+use wasm_runtime_layer::{
+    backend::WasmEngine, AsContext, AsContextMut, Extern, Func, FuncType, Instance, StoreContext,
+    Value, ValueType,
+};
+
+/// Returns whether calls to a method can be batched: it has no results and takes no
+/// resources.
+pub fn batchable(sig: &Sig) -> bool {
+    sig.rets.is_empty() && !sig.params.iter().any(|p| matches!(p, Arg::Resource { .. }))
+}
+
+/// Runs a queue of encoded method calls on a resource, stopping at the first failure.
+///
+/// # Arguments
+///
+/// * `w` - The resource to call
+/// * `ctx` - A mutable store context
+/// * `data` - The encoded queue
+///
+/// # Returns
+///
+/// The number of calls made.
+///
+/// # Errors
+///
+/// Returns [`PitError::MissingMethod`] for an out-of-range method index,
+/// [`PitError::TypeMismatch`] for a method that cannot be batched or a truncated
+/// queue, or the error a call failed with.
+pub fn run<U: 'static, E: WasmEngine>(
+    w: &Wrapped<U, E>,
+    mut ctx: impl AsContextMut<UserState = U, Engine = E>,
+    mut data: &[u8],
+) -> Result<usize, PitError> {
+    let mut n = 0;
+    while !data.is_empty() {
+        let j = u32::from_le_bytes(take(&mut data)?) as usize;
+        let Some((name, sig)) = w.rid.methods.iter().nth(j) else {
+            return Err(PitError::MissingMethod {
+                rid: w.rid.rid_str(),
+                method: format!("#{j}"),
+            });
+        };
+        if !batchable(sig) {
+            return Err(PitError::TypeMismatch {
+                expected: "a method without results or resources".into(),
+                actual: name.clone(),
+            });
+        }
+        let mut args = vec![];
+        for p in sig.params.iter() {
+            args.push(match p {
+                Arg::I32 => Value::I32(i32::from_le_bytes(take(&mut data)?)),
+                Arg::I64 => Value::I64(i64::from_le_bytes(take(&mut data)?)),
+                Arg::F32 => Value::F32(f32::from_le_bytes(take(&mut data)?)),
+                Arg::F64 => Value::F64(f64::from_le_bytes(take(&mut data)?)),
+                _ => unreachable!(),
+            });
+        }
+        let f = w.all[j + 1].clone();
+        f(ctx.as_context_mut(), args)?;
+        n += 1;
+    }
+    Ok(n)
+}
+
+fn take<const N: usize>(data: &mut &[u8]) -> Result<[u8; N], PitError> {
+    if data.len() < N {
+        return Err(PitError::TypeMismatch {
+            expected: format!("{N} more bytes in the batch"),
+            actual: format!("{}", data.len()),
+        });
+    }
+    let mut a = [0u8; N];
+    a.copy_from_slice(&data[..N]);
+    *data = &data[N..];
+    Ok(a)
+}
+
+/// The size of a WebAssembly memory page, in bytes.
+const PAGE_SIZE: usize = 0x10000;
+
+/// Creates a `pit/{rid}.@batch` import function.
+///
+/// `instance` selects the calling instance, whose `memory` export holds the queue.
+/// The queue is checked against the size of that memory before it is copied out.
+pub(crate) fn batch_func<U: 'static, E: WasmEngine>(
+    ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+    rid: &Interface,
+    instance: impl Fn(StoreContext<'_, U, E>) -> anyhow::Result<Instance> + Send + Sync + 'static,
+) -> Func {
+    let rid = rid.rid_str();
+    Func::new(
+        &mut *ctx,
+        FuncType::new(
+            [ValueType::ExternRef, ValueType::I32, ValueType::I32],
+            empty(),
+        ),
+        move |mut ctx, args, _| {
+            let Value::ExternRef(Some(a)) = args[0].clone() else {
+                return Err(PitError::NullResource.into());
+            };
+            let (Value::I32(ptr), Value::I32(len)) = (&args[1], &args[2]) else {
+                return Err(PitError::mismatch("i32", &args[1]).into());
+            };
+            let i = instance(ctx.as_context())?;
+            let Some(Extern::Memory(mem)) = i.get_export(ctx.as_context_mut(), "memory") else {
+                return Err(PitError::MissingExport("memory".into()).into());
+            };
+            let (offset, len) = (*ptr as u32 as usize, *len as u32 as usize);
+            let size = mem.current_pages(ctx.as_context()) * PAGE_SIZE;
+            if !matches!(offset.checked_add(len), Some(end) if end <= size) {
+                return Err(PitError::OutOfBounds { offset, len, size }.into());
+            }
+            let mut data = vec![0u8; len];
+            mem.read(ctx.as_context(), offset, &mut data)?;
+            let w = match a.downcast::<'_, '_, Wrapped<U, E>, U, E>(ctx.as_context()) {
+                Ok(w) if w.implements(&rid) => w.clone(),
+                _ => return Err(PitError::mismatch(format!("pit/{rid} resource"), &args[0]).into()),
+            };
+            run(&w, ctx.as_context_mut(), &data)?;
+            Ok(())
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linker::Linker;
+    use crate::testing::{call, iface, module, store, E};
+    use crate::WrappedFn;
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use wasm_runtime_layer::{ExternRef, StoreContextMut};
+
+    const IFACE: &str = "{
+    read8(I32) -> (I32);
+    write8(I32,I32) -> ()
+}";
+
+    /// A host resource logging its `write8` calls.
+    fn sink(rid: Arc<Interface>, log: Arc<spin::Mutex<Vec<(i32, i32)>>>) -> Wrapped<(), E> {
+        let nop = Arc::new(|_: StoreContextMut<'_, (), E>, _: Vec<Value>| Ok(vec![Value::I32(0)]))
+            as WrappedFn<(), E>;
+        let write8 = Arc::new(move |_: StoreContextMut<'_, (), E>, a: Vec<Value>| {
+            let [Value::I32(x), Value::I32(y)] = a[..] else {
+                anyhow::bail!("bad arguments")
+            };
+            log.lock().push((x, y));
+            Ok(vec![])
+        }) as WrappedFn<(), E>;
+        Wrapped {
+            rid,
+            all: vec![nop.clone(), nop, write8],
+            ctor: None,
+            base: vec![],
+        }
+    }
+
+    #[test]
+    fn runs_queued_calls() {
+        let mut s = store(());
+        let i = iface(IFACE);
+        let rid = i.rid_str();
+        let log = Arc::new(spin::Mutex::new(Vec::new()));
+        let w = sink(i.clone(), log.clone());
+        let mut queue = |data: &[u8]| run(&w, &mut s, data);
+
+        let mut data = vec![];
+        for (x, y) in [(5i32, 7i32), (-1, 2)] {
+            data.extend(1u32.to_le_bytes());
+            data.extend(x.to_le_bytes());
+            data.extend(y.to_le_bytes());
+        }
+        assert_eq!(queue(&data).unwrap(), 2);
+        assert_eq!(*log.lock(), [(5, 7), (-1, 2)]);
+
+        // read8 has a result, there is no third method, and the queue is truncated.
+        assert!(matches!(
+            queue(&[0, 0, 0, 0, 1, 0, 0, 0]),
+            Err(PitError::TypeMismatch { actual, .. }) if actual == "read8"
+        ));
+        assert!(matches!(
+            queue(&2u32.to_le_bytes()),
+            Err(PitError::MissingMethod { rid: r, .. }) if r == rid
+        ));
+        assert!(matches!(
+            queue(&data[..data.len() - 1]),
+            Err(PitError::TypeMismatch { .. })
+        ));
+        assert_eq!(log.lock().len(), 3);
+    }
+
+    #[test]
+    fn checks_the_queue_against_memory() {
+        let mut s = store(());
+        let i = iface(IFACE);
+        let rid = i.rid_str();
+        let log = Arc::new(spin::Mutex::new(Vec::new()));
+        let mut l = Linker::new(&mut s);
+        l.interface(i.clone(), &mut s);
+        let m = module(
+            &s,
+            &format!(
+                r#"(module
+    (import "pit/{rid}" "@batch" (func $batch (param externref i32 i32)))
+    (memory (export "memory") 1)
+    (data (i32.const 16) "\01\00\00\00\05\00\00\00\07\00\00\00")
+    (func (export "run") (param externref i32 i32)
+        local.get 0 local.get 1 local.get 2 call $batch)
+)"#
+            ),
+        );
+        let c = l.instantiate(&mut s, &m).unwrap();
+        let w = ExternRef::new(&mut s, sink(i, log.clone()));
+        let mut batch = |ptr: i32, len: i32| {
+            let args = [
+                Value::ExternRef(Some(w.clone())),
+                Value::I32(ptr),
+                Value::I32(len),
+            ];
+            call(&mut s, &c.instance, "run", &args, 0)
+        };
+        batch(16, 12).unwrap();
+        assert_eq!(*log.lock(), [(5, 7)]);
+
+        // Ranges past the end of memory, wrapping around, or too large to allocate
+        // are rejected before anything is copied.
+        for (ptr, len) in [(0x10000 - 8, 12), (-1, 2), (0, i32::MAX)] {
+            let e = batch(ptr, len).unwrap_err();
+            assert!(format!("{e:#}").contains("bytes of memory"), "{e:#}");
+        }
+        assert_eq!(log.lock().len(), 1);
+    }
+}
@@ -0,0 +1,171 @@
+//! # Errors
+//!
+//! [`PitError`] describes the ways a PIT crossing can fail, so hosts can tell a
+//! misbehaving guest from a denied call or a trap.
+//!
+//! The import functions handed to the runtime still fail with [`anyhow::Error`], as
+//! `wasm_runtime_layer` requires. `PitError` implements [`core::error::Error`], so it
+//! converts into an `anyhow::Error` with `?`, and converting back with
+//! [`PitError::from`] recovers the original variant:
+//!
+//! ```ignore
+//! match wrapped.call(&mut store, "read", &args) {
+//!     Err(PitError::Trap(e)) => eprintln!("guest trapped: {e}"),
+//!     Err(PitError::Denied(what)) => eprintln!("not allowed: {what}"),
+//!     Err(e) => return Err(e.into()),
+//!     Ok(rets) => { /* ... */ }
+//! }
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+use wasm_runtime_layer::Value;
+
+/// An error raised by the PIT host runtime.
+#[derive(Debug)]
+pub enum PitError {
+    /// An instance lacks a PIT export it is expected to have.
+    MissingExport(String),
+    /// An interface has no method of the given name.
+    MissingMethod {
+        /// The resource ID of the interface.
+        rid: String,
+        /// The method name.
+        method: String,
+    },
+    /// A value does not have the expected type.
+    TypeMismatch {
+        /// A description of the expected type.
+        expected: String,
+        /// A description of the value found instead.
+        actual: String,
+    },
+    /// A null reference was passed where a resource is required.
+    NullResource,
+    /// A guest passed a range outside its linear memory.
+    OutOfBounds {
+        /// The offset of the range.
+        offset: usize,
+        /// The length of the range.
+        len: usize,
+        /// The size of the memory, in bytes.
+        size: usize,
+    },
+    /// An interface or constructor is not known to the [`Linker`](crate::linker::Linker).
+    Unlinked(String),
+    /// A [`Policy`](crate::policy::Policy) denied an import or crossing.
+    Denied(String),
+    /// The guest trapped, or another error occurred while running it.
+    Trap(anyhow::Error),
+}
+
+/// A `Result` with [`PitError`] as the default error type.
+pub type Result<T, E = PitError> = core::result::Result<T, E>;
+
+impl PitError {
+    /// Creates a [`PitError::TypeMismatch`] describing the value found.
+    pub fn mismatch(expected: impl Into<String>, actual: &Value) -> Self {
+        PitError::TypeMismatch {
+            expected: expected.into(),
+            actual: describe(actual),
+        }
+    }
+}
+
+/// Describes the type of a value, as used by [`PitError::TypeMismatch`].
+pub fn describe(v: &Value) -> String {
+    match v {
+        Value::I32(_) => "i32".into(),
+        Value::I64(_) => "i64".into(),
+        Value::F32(_) => "f32".into(),
+        Value::F64(_) => "f64".into(),
+        Value::ExternRef(None) => "null externref".into(),
+        Value::ExternRef(Some(_)) => "externref".into(),
+        Value::FuncRef(None) => "null funcref".into(),
+        Value::FuncRef(Some(_)) => "funcref".into(),
+    }
+}
+
+impl fmt::Display for PitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PitError::MissingExport(name) => write!(f, "missing export {name}"),
+            PitError::MissingMethod { rid, method } => {
+                write!(f, "no method {method} in interface {rid}")
+            }
+            PitError::TypeMismatch { expected, actual } => {
+                write!(f, "type mismatch: expected {expected}, found {actual}")
+            }
+            PitError::NullResource => write!(f, "null resource"),
+            PitError::OutOfBounds { offset, len, size } => {
+                write!(
+                    f,
+                    "{len} bytes at {offset} exceed the {size} bytes of memory"
+                )
+            }
+            PitError::Unlinked(what) => write!(f, "{what} is not linked"),
+            PitError::Denied(what) => write!(f, "policy denies {what}"),
+            PitError::Trap(e) => write!(f, "guest trapped: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for PitError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            PitError::Trap(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+/// Recovers a `PitError` passed through an `anyhow::Error`, treating anything else
+/// as a [`PitError::Trap`].
+impl From<anyhow::Error> for PitError {
+    fn from(e: anyhow::Error) -> Self {
+        match e.downcast::<PitError>() {
+            Ok(e) => e,
+            Err(e) => PitError::Trap(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn describes_values() {
+        assert_eq!(describe(&Value::I32(1)), "i32");
+        assert_eq!(describe(&Value::F64(1.0)), "f64");
+        assert_eq!(describe(&Value::ExternRef(None)), "null externref");
+        assert_eq!(describe(&Value::FuncRef(None)), "null funcref");
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let e = PitError::mismatch("i32", &Value::I64(3));
+        let PitError::TypeMismatch { expected, actual } = &e else {
+            panic!("{e:?}");
+        };
+        assert_eq!((expected.as_str(), actual.as_str()), ("i32", "i64"));
+        assert_eq!(e.to_string(), "type mismatch: expected i32, found i64");
+    }
+
+    #[test]
+    fn recovers_errors_through_anyhow() {
+        let e: anyhow::Error = PitError::Denied("pit/x.y".into()).into();
+        assert!(matches!(PitError::from(e), PitError::Denied(w) if w == "pit/x.y"));
+        let e: anyhow::Error = PitError::NullResource.into();
+        assert!(matches!(PitError::from(e), PitError::NullResource));
+    }
+
+    #[test]
+    fn treats_other_errors_as_traps() {
+        let e = PitError::from(anyhow::anyhow!("unreachable"));
+        assert!(matches!(&e, PitError::Trap(t) if t.to_string() == "unreachable"));
+        assert_eq!(e.to_string(), "guest trapped: unreachable");
+        assert!(core::error::Error::source(&e).is_some());
+    }
+}
@@ -0,0 +1,453 @@
+//! # Call interception
+//!
+//! An [`Interceptor`] is invoked around every PIT boundary crossing made through
+//! [`emit_with`](crate::emit_with), a [`Linker`](crate::linker::Linker) or an
+//! intercepted [`Wrapped`]: constructor calls, method calls and drops.
+//!
+//! Two ready-made interceptors are provided:
+//! - [`Tracing`] formats each crossing as a line of text and hands it to a sink
+//! - [`Counting`] keeps per-method call and error counts
+//!
+//! ```ignore
+//! let counts = Arc::new(Counting::default());
+//! emit_with(&mut imports, interface.into(), &module, &mut store, Some(counts.clone()));
+//! // ...
+//! for ((rid, method), c) in counts.snapshot() {
+//!     println!("{rid} {method}: {} calls, {} errors", c.calls, c.errors);
+//! }
+//! ```
+
+use crate::{Wrapped, WrappedFn};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::iter::once;
+use pit_core::Interface;
+use wasm_runtime_layer::{backend::WasmEngine, StoreContextMut, Value};
+
+/// A PIT boundary crossing.
+pub enum Event<'a> {
+    /// A guest wrapped one of its objects through a `pit/{rid}.~{ctor}` import.
+    Ctor {
+        /// The interface of the new resource.
+        rid: &'a Interface,
+        /// The unique ID of the implementation.
+        ctor: &'a str,
+        /// The values passed to the constructor (the guest's handle).
+        base: &'a [Value],
+    },
+    /// A method was called on a resource.
+    Call {
+        /// The interface of the resource.
+        rid: &'a Interface,
+        /// The unique ID of the implementation, or `None` for host-implemented resources.
+        ctor: Option<&'a str>,
+        /// The values the resource was constructed with.
+        base: &'a [Value],
+        /// The method name.
+        method: &'a str,
+        /// The method arguments, without the resource itself.
+        ///
+        /// Empty in [`Interceptor::after`], as the arguments were moved into the call.
+        args: &'a [Value],
+    },
+    /// A resource was dropped.
+    Drop {
+        /// The interface of the resource.
+        rid: &'a Interface,
+        /// The unique ID of the implementation, or `None` for host-implemented resources.
+        ctor: Option<&'a str>,
+        /// The values the resource was constructed with.
+        base: &'a [Value],
+    },
+}
+
+impl<'a> Event<'a> {
+    /// Creates the event for a call of `method`, or for a drop if it is `None`.
+    fn of(
+        rid: &'a Interface,
+        ctor: Option<&'a str>,
+        base: &'a [Value],
+        method: Option<&'a str>,
+        args: &'a [Value],
+    ) -> Self {
+        match method {
+            None => Event::Drop { rid, ctor, base },
+            Some(method) => Event::Call {
+                rid,
+                ctor,
+                base,
+                method,
+                args,
+            },
+        }
+    }
+
+    /// Returns the interface of the resource involved.
+    pub fn rid(&self) -> &Interface {
+        match self {
+            Event::Ctor { rid, .. } | Event::Call { rid, .. } | Event::Drop { rid, .. } => rid,
+        }
+    }
+
+    /// Returns the unique ID of the implementation involved, if any.
+    pub fn ctor(&self) -> Option<&str> {
+        match self {
+            Event::Ctor { ctor, .. } => Some(ctor),
+            Event::Call { ctor, .. } | Event::Drop { ctor, .. } => *ctor,
+        }
+    }
+
+    /// Returns the values the resource involved was constructed with.
+    pub fn base(&self) -> &[Value] {
+        match self {
+            Event::Ctor { base, .. } | Event::Call { base, .. } | Event::Drop { base, .. } => base,
+        }
+    }
+
+    /// Returns the ABI name of the crossing: the method name, `~{ctor}` or `.drop`.
+    pub fn name(&self) -> String {
+        match self {
+            Event::Ctor { ctor, .. } => format!("~{ctor}"),
+            Event::Call { method, .. } => (*method).to_owned(),
+            Event::Drop { .. } => ".drop".to_owned(),
+        }
+    }
+}
+
+/// Observes PIT boundary crossings.
+///
+/// Both methods have no-op defaults, so implementors only override what they need.
+pub trait Interceptor: Send + Sync {
+    /// Called before a crossing.
+    ///
+    /// Returning an error aborts the crossing, which then fails with that error.
+    fn before(&self, _ev: &Event<'_>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called after a crossing with its results, or the error it failed with.
+    fn after(&self, _ev: &Event<'_>, _rets: Result<&[Value], &anyhow::Error>) {}
+}
+
+/// Runs several interceptors in order.
+///
+/// `after` runs in reverse order. If one `before` fails, the interceptors that already
+/// ran see the failure in their `after`, so that paired bookkeeping stays balanced.
+impl Interceptor for Vec<Arc<dyn Interceptor>> {
+    fn before(&self, ev: &Event<'_>) -> anyhow::Result<()> {
+        for (k, i) in self.iter().enumerate() {
+            if let Err(e) = i.before(ev) {
+                for j in self[..k].iter().rev() {
+                    j.after(ev, Err(&e));
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn after(&self, ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+        for i in self.iter().rev() {
+            i.after(ev, rets);
+        }
+    }
+}
+
+impl<U: 'static, E: WasmEngine> Wrapped<U, E> {
+    /// Routes every method call and the drop of this resource through an interceptor.
+    pub fn intercept(self, i: Arc<dyn Interceptor>) -> Self {
+        let names = once(None).chain(self.rid.methods.keys().cloned().map(Some));
+        let all = self
+            .all
+            .iter()
+            .cloned()
+            .zip(names)
+            .map(|(f, m)| {
+                let i = i.clone();
+                let rid = self.rid.clone();
+                let ctor = self.ctor.clone();
+                let base = self.base.clone();
+                Arc::new(
+                    move |ctx: StoreContextMut<'_, U, E>,
+                          vals: Vec<Value>|
+                          -> anyhow::Result<Vec<Value>> {
+                        let (ctor, m) = (ctor.as_deref(), m.as_deref());
+                        i.before(&Event::of(&rid, ctor, &base, m, &vals))?;
+                        let r = f(ctx, vals);
+                        i.after(&Event::of(&rid, ctor, &base, m, &[]), r.as_deref());
+                        r
+                    },
+                ) as WrappedFn<U, E>
+            })
+            .collect();
+        Self { all, ..self }
+    }
+}
+
+/// Formats a list of values for logging.
+pub fn fmt_vals(v: &[Value]) -> String {
+    let mut s = String::new();
+    for (i, v) in v.iter().enumerate() {
+        if i != 0 {
+            s.push_str(", ");
+        }
+        let _ = match v {
+            Value::I32(a) => write!(s, "{a}"),
+            Value::I64(a) => write!(s, "{a}i64"),
+            Value::F32(a) => write!(s, "{a}f32"),
+            Value::F64(a) => write!(s, "{a}f64"),
+            Value::ExternRef(None) => write!(s, "null"),
+            Value::ExternRef(Some(_)) => write!(s, "externref"),
+            Value::FuncRef(None) => write!(s, "null func"),
+            Value::FuncRef(Some(_)) => write!(s, "funcref"),
+        };
+    }
+    s
+}
+
+/// An interceptor that logs every crossing.
+///
+/// Each line names the resource as `pit/{rid}/~{ctor}[{base}]`, followed by the
+/// method, its arguments, and then its results or error. Host-implemented resources
+/// are shown with `~host`.
+pub struct Tracing<F> {
+    /// Receives each formatted line.
+    pub sink: F,
+}
+
+impl<F: Fn(String) + Send + Sync> Tracing<F> {
+    fn prefix(&self, ev: &Event<'_>) -> String {
+        format!(
+            "pit/{}/~{}[{}]",
+            ev.rid().rid_str(),
+            ev.ctor().unwrap_or("host"),
+            fmt_vals(ev.base())
+        )
+    }
+}
+
+impl<F: Fn(String) + Send + Sync> Interceptor for Tracing<F> {
+    fn before(&self, ev: &Event<'_>) -> anyhow::Result<()> {
+        let p = self.prefix(ev);
+        (self.sink)(match ev {
+            Event::Ctor { .. } => format!("-> {p} ctor"),
+            Event::Call { method, args, .. } => format!("-> {p}/{method}({})", fmt_vals(args)),
+            Event::Drop { .. } => format!("-> {p}.drop"),
+        });
+        Ok(())
+    }
+
+    fn after(&self, ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+        let p = self.prefix(ev);
+        let n = match ev {
+            Event::Ctor { .. } => " ctor".to_owned(),
+            Event::Call { method, .. } => format!("/{method}"),
+            Event::Drop { .. } => ".drop".to_owned(),
+        };
+        (self.sink)(match rets {
+            Ok(r) => format!("<- {p}{n} = ({})", fmt_vals(r)),
+            Err(e) => format!("<- {p}{n} failed: {e}"),
+        });
+    }
+}
+
+/// Call and error counts for one method of one interface.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Counts {
+    /// The number of crossings started.
+    pub calls: u64,
+    /// The number of crossings that failed.
+    pub errors: u64,
+}
+
+/// An interceptor that counts crossings per interface and method.
+///
+/// Counts are keyed by `(rid, name)`, where `name` is the method name, `~{ctor}`
+/// for constructors, or `.drop` for drops.
+#[derive(Default)]
+pub struct Counting {
+    counts: spin::Mutex<BTreeMap<(String, String), Counts>>,
+}
+
+impl Counting {
+    /// Returns the counts for one method, or zeroes if it was never called.
+    pub fn get(&self, rid: &str, name: &str) -> Counts {
+        self.counts
+            .lock()
+            .get(&(rid.to_owned(), name.to_owned()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns a copy of all counts.
+    pub fn snapshot(&self) -> BTreeMap<(String, String), Counts> {
+        self.counts.lock().clone()
+    }
+
+    /// Resets all counts to zero.
+    pub fn reset(&self) {
+        self.counts.lock().clear();
+    }
+}
+
+impl Interceptor for Counting {
+    fn before(&self, ev: &Event<'_>) -> anyhow::Result<()> {
+        self.counts
+            .lock()
+            .entry((ev.rid().rid_str(), ev.name()))
+            .or_default()
+            .calls += 1;
+        Ok(())
+    }
+
+    fn after(&self, ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+        if rets.is_err() {
+            self.counts
+                .lock()
+                .entry((ev.rid().rid_str(), ev.name()))
+                .or_default()
+                .errors += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{iface, module, store};
+    use alloc::string::ToString;
+    use alloc::vec;
+    use wasm_runtime_layer::{AsContextMut, Imports, Instance};
+
+    const IFACE: &str = "{
+    read8(I32) -> (I32);
+    fail() -> ()
+}";
+
+    /// Records the name of each interceptor hook as it runs.
+    struct Log(&'static str, Arc<spin::Mutex<Vec<String>>>, bool);
+
+    impl Interceptor for Log {
+        fn before(&self, _ev: &Event<'_>) -> anyhow::Result<()> {
+            self.1.lock().push(format!("{} before", self.0));
+            match self.2 {
+                true => Err(anyhow::anyhow!("denied")),
+                false => Ok(()),
+            }
+        }
+
+        fn after(&self, _ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+            let r = rets.map_or_else(|e| e.to_string(), |_| "ok".to_owned());
+            self.1.lock().push(format!("{} after {r}", self.0));
+        }
+    }
+
+    #[test]
+    fn unwinds_interceptors_in_reverse() {
+        let i = iface(IFACE);
+        let log = Arc::new(spin::Mutex::new(Vec::new()));
+        let all: Vec<Arc<dyn Interceptor>> = vec![
+            Arc::new(Log("a", log.clone(), false)),
+            Arc::new(Log("b", log.clone(), false)),
+            Arc::new(Log("c", log.clone(), true)),
+        ];
+        let ev = Event::of(&i, None, &[], Some("fail"), &[]);
+        assert!(all.before(&ev).is_err());
+        assert_eq!(
+            *log.lock(),
+            [
+                "a before",
+                "b before",
+                "c before",
+                "b after denied",
+                "a after denied"
+            ]
+        );
+        log.lock().clear();
+        all[..2].to_vec().after(&ev, Ok(&[]));
+        assert_eq!(*log.lock(), ["b after ok", "a after ok"]);
+    }
+
+    #[test]
+    fn traces_and_counts_wrapped_calls() {
+        let mut s = store(());
+        let i = iface(IFACE);
+        let rid = i.rid_str();
+        let m = module(
+            &s,
+            &format!(
+                r#"(module
+    (func (export "pit/{rid}/~a/read8") (param i32 i32) (result i32)
+        local.get 0 local.get 1 i32.add)
+    (func (export "pit/{rid}/~a/fail") (param i32) unreachable)
+    (func (export "pit/{rid}/~a.drop") (param i32))
+)"#
+            ),
+        );
+        let instance = Instance::new(&mut s, &m, &Imports::new()).unwrap();
+        let lines = Arc::new(spin::Mutex::new(Vec::new()));
+        let sink = lines.clone();
+        let counts = Arc::new(Counting::default());
+        let all: Vec<Arc<dyn Interceptor>> = vec![
+            Arc::new(Tracing {
+                sink: move |l| sink.lock().push(l),
+            }),
+            counts.clone(),
+        ];
+        let w = Wrapped::new(
+            vec![Value::I32(1)],
+            i,
+            "a".to_owned(),
+            instance,
+            s.as_context_mut(),
+        )
+        .intercept(Arc::new(all));
+
+        let r = w.call(&mut s, "read8", &[Value::I32(2)]).unwrap();
+        assert!(matches!(r[..], [Value::I32(3)]));
+        assert!(w.call(&mut s, "fail", &[]).is_err());
+        (w.all[0])(s.as_context_mut(), vec![]).unwrap();
+
+        let lines = lines.lock();
+        let p = format!("pit/{rid}/~a[1]");
+        assert_eq!(lines[0], format!("-> {p}/read8(2)"));
+        assert_eq!(lines[1], format!("<- {p}/read8 = (3)"));
+        assert_eq!(lines[2], format!("-> {p}/fail()"));
+        assert!(lines[3].starts_with(&format!("<- {p}/fail failed: ")));
+        assert_eq!(
+            lines[4..],
+            [format!("-> {p}.drop"), format!("<- {p}.drop = ()")]
+        );
+
+        let c = |n: &str| counts.get(&rid, n);
+        assert_eq!(
+            c("read8"),
+            Counts {
+                calls: 1,
+                errors: 0
+            }
+        );
+        assert_eq!(
+            c("fail"),
+            Counts {
+                calls: 1,
+                errors: 1
+            }
+        );
+        assert_eq!(
+            c(".drop"),
+            Counts {
+                calls: 1,
+                errors: 0
+            }
+        );
+        assert_eq!(c("write8"), Counts::default());
+        counts.reset();
+        assert!(counts.snapshot().is_empty());
+    }
+}
@@ -0,0 +1,403 @@
+//! # Leak tracking
+//!
+//! [`Leaks`] is an interceptor counting resource creations and drops per interface and
+//! implementation. It remembers every live resource along with the PIT crossings that
+//! were in progress when it was created, so resources that are never dropped can be
+//! traced back to the calls that created them.
+//!
+//! These "stacks" are built from the [`Event`]s the interceptor observes: each frame
+//! is a constructor, method call or drop crossing the PIT boundary. They are not
+//! native or WebAssembly backtraces, and show nothing of the code between crossings.
+//!
+//! Installed on a [`Linker`](crate::linker::Linker) with
+//! [`Linker::track`](crate::linker::Linker::track), the leaks of an instance are
+//! reported by [`Linker::teardown`](crate::linker::Linker::teardown).
+//!
+//! ```ignore
+//! let leaks = Arc::new(Leaks::default());
+//! linker.track(leaks.clone());
+//! let i = linker.instantiate(&mut store, &module)?;
+//! // ...
+//! for l in linker.teardown(&i) {
+//!     eprintln!("leaked {l}");
+//! }
+//! ```
+
+use crate::intercept::{Event, Interceptor};
+use crate::trace::Val;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use wasm_runtime_layer::Value;
+
+/// A crossing in progress, one frame of a [`Live::stack`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    /// The resource ID of the interface involved.
+    pub rid: String,
+    /// The unique ID of the implementation, or `None` for host-implemented resources.
+    pub ctor: Option<String>,
+    /// The ABI name of the crossing, see [`Event::name`].
+    pub name: String,
+}
+
+/// A live resource.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Live {
+    /// The resource ID of its interface.
+    pub rid: String,
+    /// The unique ID of its implementation.
+    pub ctor: String,
+    /// The values it was constructed with.
+    pub base: Vec<Val>,
+    /// The [`Linked::id`](crate::linker::Linked::id) of the instance implementing it,
+    /// or `None` if it was not created through a [`Linker`](crate::linker::Linker).
+    pub instance: Option<u64>,
+    /// The PIT crossings in progress when it was created, outermost first. This is
+    /// not a backtrace: guest and host code between crossings does not appear.
+    pub stack: Vec<Frame>,
+}
+
+impl fmt::Display for Live {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pit/{}/~{}{:?}", self.rid, self.ctor, self.base)?;
+        for fr in self.stack.iter().rev() {
+            write!(
+                f,
+                "\n    in pit/{}/~{}/{}",
+                fr.rid,
+                fr.ctor.as_deref().unwrap_or("host"),
+                fr.name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Creation and drop counts for one implementation of one interface.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of resources created.
+    pub created: u64,
+    /// The number of resources dropped.
+    pub dropped: u64,
+}
+
+impl Stats {
+    /// Returns the number of resources created but not yet dropped.
+    pub fn live(&self) -> u64 {
+        self.created.saturating_sub(self.dropped)
+    }
+}
+
+#[derive(Default)]
+struct LeakState {
+    stack: Vec<Frame>,
+    stats: BTreeMap<(String, Option<String>), Stats>,
+    live: Vec<Live>,
+}
+
+/// An interceptor tracking live resources.
+///
+/// Stats are keyed by `(rid, ctor)`, with `None` for host-implemented resources, whose
+/// creation is not observed, so only their drops are counted.
+#[derive(Default)]
+pub struct Leaks {
+    state: spin::Mutex<LeakState>,
+}
+
+impl Leaks {
+    /// Returns the counts for one implementation, or zeroes if none was seen.
+    pub fn get(&self, rid: &str, ctor: Option<&str>) -> Stats {
+        self.state
+            .lock()
+            .stats
+            .get(&(rid.to_owned(), ctor.map(|a| a.to_owned())))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns a copy of all counts.
+    pub fn stats(&self) -> BTreeMap<(String, Option<String>), Stats> {
+        self.state.lock().stats.clone()
+    }
+
+    /// Returns the live resources, oldest first.
+    pub fn snapshot(&self) -> Vec<Live> {
+        self.state.lock().live.clone()
+    }
+
+    /// Forgets the live resources of the given implementations and returns them.
+    ///
+    /// Meant for when the instance implementing them is torn down, after which they
+    /// can no longer be dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctors` - `(rid, ctor)` pairs, with the unique ID without the leading `~`
+    pub fn teardown(&self, ctors: &[(String, String)]) -> Vec<Live> {
+        let mut s = self.state.lock();
+        let (leaked, live) = core::mem::take(&mut s.live)
+            .into_iter()
+            .partition(|l| ctors.iter().any(|(r, c)| *r == l.rid && *c == l.ctor));
+        s.live = live;
+        leaked
+    }
+
+    /// Forgets the live resources implemented by an instance and returns them.
+    pub(crate) fn teardown_instance(&self, instance: u64) -> Vec<Live> {
+        let mut s = self.state.lock();
+        let (leaked, live) = core::mem::take(&mut s.live)
+            .into_iter()
+            .partition(|l| l.instance == Some(instance));
+        s.live = live;
+        leaked
+    }
+
+    /// Returns an interceptor recording the resources it sees as implemented by the
+    /// instance numbered `instance`, so they are told apart from those of other
+    /// instances of the same module.
+    pub(crate) fn scoped(self: &Arc<Self>, instance: u64) -> Arc<dyn Interceptor> {
+        Arc::new(Scoped {
+            leaks: self.clone(),
+            instance,
+        })
+    }
+
+    /// Discards all counts and live resources.
+    pub fn reset(&self) {
+        let mut s = self.state.lock();
+        s.stats.clear();
+        s.live.clear();
+    }
+
+    /// Enters a crossing.
+    fn push(&self, ev: &Event<'_>) {
+        self.state.lock().stack.push(Frame {
+            rid: ev.rid().rid_str(),
+            ctor: ev.ctor().map(|a| a.to_owned()),
+            name: ev.name(),
+        });
+    }
+
+    /// Leaves a crossing, recording the resource it created or dropped, if any.
+    fn pop(&self, ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>, instance: Option<u64>) {
+        let mut s = self.state.lock();
+        s.stack.pop();
+        if rets.is_err() {
+            return;
+        }
+        let rid = ev.rid().rid_str();
+        let base = ev.base().iter().map(Val::of).collect::<Vec<_>>();
+        match ev {
+            Event::Ctor { ctor, .. } => {
+                s.stats
+                    .entry((rid.clone(), Some((*ctor).to_owned())))
+                    .or_default()
+                    .created += 1;
+                let stack = s.stack.clone();
+                s.live.push(Live {
+                    rid,
+                    ctor: (*ctor).to_owned(),
+                    base,
+                    instance,
+                    stack,
+                });
+            }
+            Event::Drop { ctor, .. } => {
+                s.stats
+                    .entry((rid.clone(), ctor.map(|a| a.to_owned())))
+                    .or_default()
+                    .dropped += 1;
+                if let Some(i) = s.live.iter().position(|l| {
+                    l.rid == rid
+                        && Some(l.ctor.as_str()) == *ctor
+                        && l.base == base
+                        && l.instance == instance
+                }) {
+                    s.live.remove(i);
+                }
+            }
+            Event::Call { .. } => {}
+        }
+    }
+}
+
+impl Interceptor for Leaks {
+    fn before(&self, ev: &Event<'_>) -> anyhow::Result<()> {
+        self.push(ev);
+        Ok(())
+    }
+
+    fn after(&self, ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+        self.pop(ev, rets, None)
+    }
+}
+
+/// A [`Leaks`] attributing the resources it sees to one instance.
+struct Scoped {
+    leaks: Arc<Leaks>,
+    instance: u64,
+}
+
+impl Interceptor for Scoped {
+    fn before(&self, ev: &Event<'_>) -> anyhow::Result<()> {
+        self.leaks.push(ev);
+        Ok(())
+    }
+
+    fn after(&self, ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+        self.leaks.pop(ev, rets, Some(self.instance))
+    }
+}
+
+/// Formats a list of live resources as a leak report, one resource per paragraph.
+pub fn report(leaks: &[Live]) -> String {
+    let mut s = String::new();
+    for l in leaks {
+        s.push_str(&alloc::format!("leaked {l}\n"));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::iface;
+    use alloc::vec;
+
+    const IFACE: &str = "{
+    read8(I32) -> (I32)
+}";
+
+    #[test]
+    fn tracks_live_resources() {
+        let i = iface(IFACE);
+        let rid = i.rid_str();
+        let l = Leaks::default();
+        let ctor = |base| Event::Ctor {
+            rid: &i,
+            ctor: "a",
+            base,
+        };
+        let drop = |ctor, base| Event::Drop {
+            rid: &i,
+            ctor,
+            base,
+        };
+        let (one, two) = ([Value::I32(1)], [Value::I32(2)]);
+        for b in [&one, &two] {
+            l.before(&ctor(b)).unwrap();
+            l.after(&ctor(b), Ok(&[]));
+        }
+        // Failed constructors and drops change nothing.
+        l.before(&ctor(&one)).unwrap();
+        l.after(&ctor(&one), Err(&anyhow::anyhow!("trap")));
+        l.before(&drop(Some("a"), &two)).unwrap();
+        l.after(&drop(Some("a"), &two), Err(&anyhow::anyhow!("trap")));
+        assert_eq!(
+            l.get(&rid, Some("a")),
+            Stats {
+                created: 2,
+                dropped: 0
+            }
+        );
+
+        l.before(&drop(Some("a"), &two)).unwrap();
+        l.after(&drop(Some("a"), &two), Ok(&[]));
+        // Only drops of host-implemented resources are seen.
+        l.before(&drop(None, &[])).unwrap();
+        l.after(&drop(None, &[]), Ok(&[]));
+        assert_eq!(l.get(&rid, Some("a")).live(), 1);
+        assert_eq!(
+            l.get(&rid, None),
+            Stats {
+                created: 0,
+                dropped: 1
+            }
+        );
+        let live = l.snapshot();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].base, [Val::I32(1)]);
+
+        assert!(l.teardown(&[(rid.clone(), "b".to_owned())]).is_empty());
+        assert_eq!(l.teardown(&[(rid.clone(), "a".to_owned())]), live);
+        assert!(l.snapshot().is_empty());
+        l.reset();
+        assert!(l.stats().is_empty());
+    }
+
+    #[test]
+    fn records_crossings_in_progress() {
+        let i = iface(IFACE);
+        let rid = i.rid_str();
+        let l = Leaks::default();
+        let call = Event::Call {
+            rid: &i,
+            ctor: None,
+            base: &[],
+            method: "read8",
+            args: &[],
+        };
+        let base = [Value::I32(7)];
+        let ctor = Event::Ctor {
+            rid: &i,
+            ctor: "a",
+            base: &base,
+        };
+        l.before(&call).unwrap();
+        l.before(&ctor).unwrap();
+        l.after(&ctor, Ok(&[]));
+        l.after(&call, Ok(&[]));
+        let live = l.snapshot();
+        assert_eq!(
+            live[0].stack,
+            [Frame {
+                rid: rid.clone(),
+                ctor: None,
+                name: "read8".to_owned(),
+            }]
+        );
+        assert_eq!(
+            report(&live),
+            alloc::format!("leaked pit/{rid}/~a[I32(7)]\n    in pit/{rid}/~host/read8\n")
+        );
+    }
+
+    #[test]
+    fn tells_instances_apart() {
+        let i = iface(IFACE);
+        let leaks = Arc::new(Leaks::default());
+        let (a, b) = (leaks.scoped(1), leaks.scoped(2));
+        let base = [Value::I32(1)];
+        let ctor = Event::Ctor {
+            rid: &i,
+            ctor: "a",
+            base: &base,
+        };
+        let drop = Event::Drop {
+            rid: &i,
+            ctor: Some("a"),
+            base: &base,
+        };
+        for x in [&a, &b] {
+            x.before(&ctor).unwrap();
+            x.after(&ctor, Ok(&[]));
+        }
+        b.before(&drop).unwrap();
+        b.after(&drop, Ok(&[]));
+        assert_eq!(
+            leaks
+                .snapshot()
+                .iter()
+                .map(|l| l.instance)
+                .collect::<Vec<_>>(),
+            vec![Some(1)]
+        );
+        assert!(leaks.teardown_instance(2).is_empty());
+        assert_eq!(leaks.teardown_instance(1).len(), 1);
+    }
+}
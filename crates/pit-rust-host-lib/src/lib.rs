@@ -20,6 +20,65 @@
 //! emit(&mut imports, interface.into(), &module, &mut store);
 //! ```
 //!
+//! ## Host-implemented resources
+//!
+//! Host code can hand its own implementation of an interface to a guest.
+//! Any `Arc<dyn B{rid}<U, E>>` produced by the generated host bindings
+//! implements [`IntoWrapped`], and [`register`] turns it into an `ExternRef`
+//! whose methods dispatch through the same `pit/{rid}.{method}` imports
+//! that [`emit`] defines:
+//!
+//! ```ignore
+//! let object: Arc<dyn BMyInterface<U, E> + Send + Sync> = Arc::new(MyImpl);
+//! let r = register(&mut store, object);
+//! // pass `Value::ExternRef(Some(r))` to a guest export
+//! ```
+//!
+//! ## Multiple modules
+//!
+//! [`linker::Linker`] registers interfaces once and instantiates several modules
+//! against them, binding each `~ctor` import to the instance that implements it.
+//!
+//! ## Reflection
+//!
+//! [`Wrapped::call`], [`Wrapped::methods`], [`Wrapped::signature`] and
+//! [`Wrapped::implements`] give hosts without generated bindings access to
+//! resources by method name.
+//!
+//! ## Interception
+//!
+//! An [`intercept::Interceptor`] passed to [`emit_with`], [`linker::Linker::intercept`]
+//! or [`Wrapped::intercept`] observes every constructor, method call and drop.
+//! [`intercept::Tracing`] logs them and [`intercept::Counting`] keeps per-method counts.
+//!
+//! ## Sandboxing
+//!
+//! A [`policy::Policy`] installed with [`linker::Linker::policy`] allows or denies
+//! interfaces, methods and constructors, and limits the number of live resources,
+//! both when imports are defined and on every call.
+//!
+//! ## Batching
+//!
+//! Guests importing `pit/{rid}.@batch` can run a queue of calls to methods without
+//! results in one crossing; see [`batch`].
+//!
+//! ## Errors
+//!
+//! Failures are reported as [`PitError`], which converts to and from `anyhow::Error`
+//! so that it survives the trip through the WebAssembly runtime.
+//!
+//! ## Leak tracking
+//!
+//! [`leak::Leaks`] counts creations and drops per interface and implementation and
+//! remembers the PIT call stack each live resource was created under.
+//! [`linker::Linker::teardown`] reports the resources an instance leaked.
+//!
+//! ## Record and replay
+//!
+//! [`trace::Recorder`] is an interceptor that records every crossing to a compact
+//! trace, and [`trace::Replayer`] drives a single guest module from such a trace
+//! with stub resources standing in for the rest of the system.
+//!
 //! ## no_std
 //!
 //! This crate is `no_std` compatible, using `alloc` for dynamic allocation
@@ -36,11 +95,57 @@ use alloc::vec;
 use alloc::vec::Vec;
 #[doc(hidden)]
 pub use core;
+
+/// Instantiating several PIT modules against shared interfaces.
+///
+/// See [`Linker`](linker::Linker).
+pub mod linker;
+
+/// Calling resources by method name with signature checks.
+///
+/// See [`Wrapped::call`].
+pub mod reflect;
+
+/// Observing PIT boundary crossings.
+///
+/// See [`Interceptor`](intercept::Interceptor).
+pub mod intercept;
+use intercept::{Event, Interceptor};
+
+/// Running queued method calls in a single crossing.
+///
+/// See [`batch::run`].
+pub mod batch;
+
+/// Structured errors for PIT crossings.
+///
+/// See [`PitError`].
+pub mod error;
+pub use error::PitError;
+
+/// Capability policies restricting PIT imports and calls.
+///
+/// See [`Policy`](policy::Policy).
+pub mod policy;
+
+/// Tracking live resources and reporting leaks.
+///
+/// See [`Leaks`](leak::Leaks).
+pub mod leak;
+
+/// Recording PIT traces and replaying them against a single module.
+///
+/// See [`Recorder`](trace::Recorder) and [`Replayer`](trace::Replayer).
+pub mod trace;
+
+#[cfg(test)]
+mod testing;
 use core::{
     cell::UnsafeCell,
     iter::{empty, once},
 };
 use pit_core::{Arg, Interface};
+use pit_patch_core::names;
 use wasm_runtime_layer::{
     backend::WasmEngine, AsContext, AsContextMut, Extern, ExternRef, Func, FuncType, Imports,
     Instance, Module, Store, StoreContext, StoreContextMut, Value, ValueType,
@@ -59,27 +164,29 @@ pub fn init<U: AsRef<Instance> + 'static, E: WasmEngine>(
     l: &mut Imports,
     ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
 ) {
-    l.define(
-        "pit",
-        "drop",
-        Extern::Func(Func::new(
-            &mut *ctx,
-            FuncType::new(once(ValueType::ExternRef), empty()),
-            move |mut ctx, args, rets| {
-                let Value::ExternRef(Some(a)) = args[0].clone() else {
-                    anyhow::bail!("invalid type")
-                };
-                let Ok(x): Result<&Wrapped<U, E>, anyhow::Error> =
-                    a.downcast::<'_, '_, Wrapped<U, E>, U, E>(ctx.as_context())
-                else {
-                    return Ok(());
-                };
-                let f = x.all[0].clone();
-                f(ctx.as_context_mut(), vec![])?;
-                Ok(())
-            },
-        )),
-    );
+    l.define("pit", "drop", Extern::Func(drop_func(ctx)));
+}
+/// Creates the `pit.drop` function, which runs the drop handler of a [`Wrapped`] resource.
+pub(crate) fn drop_func<U: 'static, E: WasmEngine>(
+    ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+) -> Func {
+    Func::new(
+        &mut *ctx,
+        FuncType::new(once(ValueType::ExternRef), empty()),
+        move |mut ctx, args, _rets| {
+            let Value::ExternRef(Some(a)) = args[0].clone() else {
+                return Err(PitError::NullResource.into());
+            };
+            let Ok(x): Result<&Wrapped<U, E>, anyhow::Error> =
+                a.downcast::<'_, '_, Wrapped<U, E>, U, E>(ctx.as_context())
+            else {
+                return Ok(());
+            };
+            let f = x.all[0].clone();
+            f(ctx.as_context_mut(), vec![])?;
+            Ok(())
+        },
+    )
 }
 /// Converts a PIT argument type to a WebAssembly value type.
 ///
@@ -123,9 +230,66 @@ pub fn emit<U: AsRef<Instance> + 'static, E: WasmEngine>(
     rid: Arc<Interface>,
     m: &Module,
     ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+) {
+    emit_with(l, rid, m, ctx, None)
+}
+/// Emits import functions for a PIT interface, observing calls with an [`Interceptor`].
+///
+/// Like [`emit`], but `interceptor` sees every constructor call, and every method call
+/// and drop on the resources those constructors create.
+///
+/// Passing a [`Policy`](policy::Policy) as the interceptor enforces it on those calls,
+/// but unlike [`Linker::instantiate`](linker::Linker::instantiate) this does not check
+/// the module's imports; call [`Policy::check_imports`](policy::Policy::check_imports)
+/// before instantiating.
+///
+/// # Arguments
+///
+/// * `l` - The imports table to add functions to
+/// * `rid` - The interface definition
+/// * `m` - The WebAssembly module being instantiated
+/// * `ctx` - A mutable store context
+/// * `interceptor` - The interceptor to install, if any
+pub fn emit_with<U: AsRef<Instance> + 'static, E: WasmEngine>(
+    l: &mut Imports,
+    rid: Arc<Interface>,
+    m: &Module,
+    ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+    interceptor: Option<Arc<dyn Interceptor>>,
+) {
+    let n = format!("pit/{}", rid.rid_str());
+    emit_methods(l, &rid, ctx);
+    let i = m
+        .imports(ctx.as_context().engine())
+        .map(|a| (a.module.to_owned(), a.name.to_owned()))
+        .collect::<Vec<_>>();
+    for i in i {
+        if i.0 == n {
+            if i.1 == names::BATCH_IMPORT_NAME {
+                let f = batch::batch_func(ctx, &rid, |ctx| Ok(ctx.data().as_ref().clone()));
+                l.define(&n, &i.1, Extern::Func(f));
+            } else if let Some(t) = i.1.strip_prefix("~") {
+                let f = ctor_func(
+                    ctx,
+                    rid.clone(),
+                    t.to_owned(),
+                    |ctx| Ok(ctx.data().as_ref().clone()),
+                    interceptor.clone(),
+                );
+                l.define(&n, &i.1, Extern::Func(f))
+            };
+        };
+    }
+}
+/// Defines the `pit/{rid}.{method}` dispatch imports for every method of an interface.
+pub(crate) fn emit_methods<U: 'static, E: WasmEngine>(
+    l: &mut Imports,
+    rid: &Interface,
+    ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
 ) {
     let n = format!("pit/{}", rid.rid_str());
     for (j, (i, m)) in rid.methods.iter().enumerate() {
+        let expected = format!("pit/{} resource", rid.rid_str());
         l.define(
             &n,
             i.as_str(),
@@ -137,9 +301,11 @@ pub fn emit<U: AsRef<Instance> + 'static, E: WasmEngine>(
                 ),
                 move |mut ctx, args, rets| {
                     let Value::ExternRef(Some(a)) = args[0].clone() else {
-                        anyhow::bail!("invalid type")
+                        return Err(PitError::NullResource.into());
+                    };
+                    let Ok(x) = a.downcast::<'_, '_, Wrapped<U, E>, U, E>(ctx.as_context()) else {
+                        return Err(PitError::mismatch(expected.clone(), &args[0]).into());
                     };
-                    let x: &Wrapped<U, E> = a.downcast(ctx.as_context())?;
                     let t = x.all[j + 1].clone();
                     let rets2 = t(ctx.as_context_mut(), args[1..].iter().cloned().collect())?;
                     for (r, s) in rets2.into_iter().zip(rets.iter_mut()) {
@@ -150,44 +316,65 @@ pub fn emit<U: AsRef<Instance> + 'static, E: WasmEngine>(
             )),
         )
     }
-    let i = m
-        .imports(ctx.as_context().engine())
-        .map(|a| (a.module.to_owned(), a.name.to_owned()))
-        .collect::<Vec<_>>();
-    for i in i {
-        if i.0 == n {
-            if let Some(t) = i.1.strip_prefix("~") {
-                let t = t.to_owned();
-                let rid = rid.clone();
-                l.define(
-                    &n,
-                    &i.1,
-                    Extern::Func(Func::new(
-                        &mut *ctx,
-                        FuncType::new(once(ValueType::I32), once(ValueType::ExternRef)),
-                        move |mut ctx, args, rets| {
-                            let i = ctx.data().as_ref().clone();
-                            let object = Wrapped::new(
-                                args.to_owned(),
-                                rid.clone(),
-                                t.to_owned(),
-                                i,
-                                ctx.as_context_mut(),
-                            );
-                            rets[0] = Value::ExternRef(Some(ExternRef::new(ctx, object)));
-                            Ok(())
-                        },
-                    )),
-                )
+}
+/// Creates a `pit/{rid}.~{ctor}` import function.
+///
+/// `instance` selects the instance whose `pit/{rid}/~{ctor}/...` exports back the
+/// created resource. If an interceptor is given, it observes the constructor call
+/// and every later call on the created resource.
+pub(crate) fn ctor_func<U: 'static, E: WasmEngine>(
+    ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+    rid: Arc<Interface>,
+    t: String,
+    instance: impl Fn(StoreContext<'_, U, E>) -> anyhow::Result<Instance> + Send + Sync + 'static,
+    interceptor: Option<Arc<dyn Interceptor>>,
+) -> Func {
+    Func::new(
+        &mut *ctx,
+        FuncType::new(once(ValueType::I32), once(ValueType::ExternRef)),
+        move |mut ctx, args, rets| {
+            let ev = Event::Ctor {
+                rid: &rid,
+                ctor: &t,
+                base: args,
             };
-        };
-    }
+            if let Some(x) = interceptor.as_ref() {
+                x.before(&ev)?;
+            }
+            let r = instance(ctx.as_context()).map(|i| {
+                let object = Wrapped::new(
+                    args.to_owned(),
+                    rid.clone(),
+                    t.to_owned(),
+                    i,
+                    ctx.as_context_mut(),
+                );
+                let object = match interceptor.as_ref() {
+                    Some(x) => object.intercept(x.clone()),
+                    None => object,
+                };
+                vec![Value::ExternRef(Some(ExternRef::new(
+                    ctx.as_context_mut(),
+                    object,
+                )))]
+            });
+            if let Some(x) = interceptor.as_ref() {
+                x.after(&ev, r.as_deref());
+            }
+            rets[0] = r?.remove(0);
+            Ok(())
+        },
+    )
 }
 /// A wrapped PIT resource.
 ///
 /// This struct holds a PIT interface reference along with the closure functions
 /// needed to implement each method and the drop handler.
 ///
+/// Code building a `Wrapped` with a struct literal must also set `ctor` and `base`,
+/// which identify the resource to interceptors; host-implemented resources use
+/// `ctor: None, base: vec![]`, as the generated `IntoWrapped` impls do.
+///
 /// # Type Parameters
 ///
 /// * `U` - The user state type for the store
@@ -196,14 +383,11 @@ pub struct Wrapped<U: 'static, E: wasm_runtime_layer::backend::WasmEngine> {
     /// The interface definition this resource implements.
     pub rid: Arc<Interface>,
     /// Method implementations: index 0 is the drop handler, followed by each method.
-    pub all: Vec<
-        Arc<
-            dyn Fn(StoreContextMut<'_, U, E>, Vec<Value>) -> anyhow::Result<Vec<Value>>
-                + Send
-                + Sync,
-        >,
-    >,
-    // },
+    pub all: Vec<WrappedFn<U, E>>,
+    /// The unique ID of the guest implementation, or `None` for host-implemented resources.
+    pub ctor: Option<String>,
+    /// The values the resource was constructed with (the guest's handle).
+    pub base: Vec<Value>,
 }
 impl<U: 'static, E: wasm_runtime_layer::backend::WasmEngine> Wrapped<U, E> {
     /// Creates a new wrapped resource from a WebAssembly instance.
@@ -240,6 +424,8 @@ impl<U: 'static, E: wasm_runtime_layer::backend::WasmEngine> Wrapped<U, E> {
         let base2 = base.clone();
         return Self {
             rid: rid.clone(),
+            ctor: Some(rs.clone()),
+            base: base.clone(),
             all: once(Arc::new(
                 move |mut ctx: StoreContextMut<'_, U, E>,
                       vals: Vec<Value>|
@@ -253,17 +439,18 @@ impl<U: 'static, E: wasm_runtime_layer::backend::WasmEngine> Wrapped<U, E> {
                         &format!("pit/{}/~{rs2}.drop", rid2.rid_str()),
                     );
                     let Some(Extern::Func(f)) = f else {
-                        anyhow::bail!("invalid func")
+                        return Err(PitError::MissingExport(format!(
+                            "pit/{}/~{rs2}.drop",
+                            rid2.rid_str()
+                        ))
+                        .into());
                     };
-                    f.call(ctx.as_context_mut(), &vals, &mut rets)?;
+                    f.call(ctx.as_context_mut(), &vals, &mut rets)
+                        .map_err(PitError::from)?;
                     Ok(rets)
                 },
             )
-                as Arc<
-                    dyn Fn(StoreContextMut<'_, U, E>, Vec<Value>) -> anyhow::Result<Vec<Value>>
-                        + Send
-                        + Sync,
-                >)
+                as WrappedFn<U, E>)
             .chain(rid.methods.iter().map(|(a, b)| {
                 let rid = rid.clone();
                 let a = a.clone();
@@ -286,17 +473,18 @@ impl<U: 'static, E: wasm_runtime_layer::backend::WasmEngine> Wrapped<U, E> {
                             &format!("pit/{}/~{rs}/{a}", rid.rid_str()),
                         );
                         let Some(Extern::Func(f)) = f else {
-                            anyhow::bail!("invalid func")
+                            return Err(PitError::MissingExport(format!(
+                                "pit/{}/~{rs}/{a}",
+                                rid.rid_str()
+                            ))
+                            .into());
                         };
-                        f.call(ctx.as_context_mut(), &vals, &mut rets)?;
+                        f.call(ctx.as_context_mut(), &vals, &mut rets)
+                            .map_err(PitError::from)?;
                         Ok(rets)
                     },
                 )
-                    as Arc<
-                        dyn Fn(StoreContextMut<'_, U, E>, Vec<Value>) -> anyhow::Result<Vec<Value>>
-                            + Send
-                            + Sync,
-                    >)
+                    as WrappedFn<U, E>)
             }))
             .collect(),
         };
@@ -308,6 +496,82 @@ impl<U: 'static, E: wasm_runtime_layer::backend::WasmEngine> Wrapped<U, E> {
 //     }
 // }
 
+impl<U: 'static, E: WasmEngine> Clone for Wrapped<U, E> {
+    fn clone(&self) -> Self {
+        Self {
+            rid: self.rid.clone(),
+            all: self.all.clone(),
+            ctor: self.ctor.clone(),
+            base: self.base.clone(),
+        }
+    }
+}
+
+/// A single entry of [`Wrapped::all`].
+///
+/// Receives the method arguments without the leading resource argument and
+/// returns the method results.
+pub type WrappedFn<U, E> = Arc<
+    dyn Fn(StoreContextMut<'_, U, E>, Vec<Value>) -> anyhow::Result<Vec<Value>> + Send + Sync,
+>;
+
+/// Conversion of a host-side implementation into a [`Wrapped`] resource.
+///
+/// The generated host bindings implement this for `dyn B{rid}<U, E> + Send + Sync`,
+/// so any host object implementing an interface can be passed to a guest via [`register`].
+pub trait IntoWrapped<U: 'static, E: WasmEngine> {
+    /// Builds the dispatch table for this object.
+    ///
+    /// Index 0 of [`Wrapped::all`] calls `finalize`, the remaining entries call
+    /// the interface methods sorted by name, as in [`Interface::methods`].
+    fn into_wrapped(self: Arc<Self>) -> Wrapped<U, E>;
+}
+
+/// Registers a host-implemented resource so it can be passed to a guest.
+///
+/// The returned `ExternRef` holds a [`Wrapped`] like the ones created by the
+/// `~ctor` imports of [`emit`], so guests call it through the regular
+/// `pit/{rid}.{method}` imports and release it through `pit.drop`.
+///
+/// # Arguments
+///
+/// * `ctx` - A mutable store context
+/// * `object` - The host implementation
+///
+/// # Returns
+///
+/// An `ExternRef` referring to the resource.
+pub fn register<U: 'static, E: WasmEngine, T: IntoWrapped<U, E> + ?Sized>(
+    ctx: impl AsContextMut<UserState = U, Engine = E>,
+    object: Arc<T>,
+) -> ExternRef {
+    register_with(ctx, object, None)
+}
+
+/// Registers a host-implemented resource, observing calls with an [`Interceptor`].
+///
+/// Like [`register`], but `interceptor` sees every method call and drop on the
+/// resource. Resources registered without one bypass any [`Policy`](policy::Policy),
+/// so pass the policy here, or use [`Linker::register`](linker::Linker::register).
+///
+/// # Arguments
+///
+/// * `ctx` - A mutable store context
+/// * `object` - The host implementation
+/// * `interceptor` - The interceptor to install, if any
+pub fn register_with<U: 'static, E: WasmEngine, T: IntoWrapped<U, E> + ?Sized>(
+    mut ctx: impl AsContextMut<UserState = U, Engine = E>,
+    object: Arc<T>,
+    interceptor: Option<Arc<dyn Interceptor>>,
+) -> ExternRef {
+    let object = object.into_wrapped();
+    let object = match interceptor {
+        Some(x) => object.intercept(x),
+        None => object,
+    };
+    ExternRef::new(ctx.as_context_mut(), object)
+}
+
 /// Type alias for an Arc-wrapped resource.
 pub type RWrapped<U, E> = ::alloc::sync::Arc<Wrapped<U, E>>;
 
@@ -315,6 +579,10 @@ pub type RWrapped<U, E> = ::alloc::sync::Arc<Wrapped<U, E>>;
 pub extern crate anyhow;
 #[doc(hidden)]
 pub extern crate wasm_runtime_layer;
+#[doc(hidden)]
+pub extern crate pit_core;
+#[doc(hidden)]
+pub extern crate spin;
 
 /// A wrapper that combines a resource with its store reference.
 ///
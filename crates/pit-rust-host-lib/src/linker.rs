@@ -0,0 +1,480 @@
+//! # Multi-module linking
+//!
+//! [`init`](crate::init) and [`emit`](crate::emit) set up the imports of a single module.
+//! A [`Linker`] instead registers each interface once and instantiates any number of
+//! modules against the same `pit.drop` and `pit/{rid}.{method}` imports, so resources
+//! can be passed freely between the instances.
+//!
+//! Every [`Wrapped`] created through a `~ctor` import is bound to the instance
+//! exporting `pit/{rid}/~{ctor}/...`. A module's own exports take precedence; otherwise
+//! a `~ctor` import is resolved against a previously instantiated module exporting that
+//! constructor.
+//!
+//! ```ignore
+//! let mut linker = Linker::new(&mut store);
+//! linker.interface(buffer.into(), &mut store);
+//! let provider = linker.instantiate(&mut store, &provider_module)?;
+//! let consumer = linker.instantiate(&mut store, &consumer_module)?;
+//! ```
+//!
+//! Instances are returned as [`Linked`], which [`Linker::teardown`] takes back when an
+//! instance is discarded.
+
+use crate::{
+    batch::batch_func,
+    ctor_func, drop_func, emit_methods,
+    error::{PitError, Result},
+    intercept::Interceptor,
+    leak::{Leaks, Live},
+    policy::Policy,
+    register_with, IntoWrapped, Wrapped,
+};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::{cell::UnsafeCell, marker::PhantomData};
+use pit_core::Interface;
+use pit_patch_core::names;
+use wasm_runtime_layer::{
+    backend::WasmEngine, AsContext, AsContextMut, Extern, ExternRef, Imports, Instance, Module,
+    Value,
+};
+
+/// Links several PIT modules against a shared set of interfaces.
+///
+/// # Type Parameters
+///
+/// * `U` - The user state type for the store
+/// * `E` - The WebAssembly engine backend
+pub struct Linker<U: 'static, E: WasmEngine> {
+    imports: Imports,
+    interfaces: BTreeMap<String, Arc<Interface>>,
+    ctors: BTreeMap<(String, String), Linked>,
+    next: u64,
+    interceptor: Option<Arc<dyn Interceptor>>,
+    policy: Option<Arc<Policy>>,
+    leaks: Option<Arc<Leaks>>,
+    phantom: PhantomData<fn() -> (U, E)>,
+}
+
+/// An instance created by [`Linker::instantiate`].
+#[derive(Clone)]
+pub struct Linked {
+    /// The instance.
+    pub instance: Instance,
+    id: u64,
+}
+
+impl Linked {
+    /// Returns the number the linker gave this instance, as found in [`Live::instance`].
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+/// The instance a module is being instantiated into.
+///
+/// `~ctor` imports are defined before the instance exists, so they read it from
+/// this slot once [`Linker::instantiate`] has filled it in.
+///
+/// # Safety
+///
+/// Like [`StoreCell`](crate::StoreCell), users must ensure the store the instance
+/// belongs to is only accessed from one thread at a time.
+pub(crate) struct InstanceSlot {
+    pub(crate) wrapped: UnsafeCell<Option<Instance>>,
+}
+unsafe impl Send for InstanceSlot {}
+unsafe impl Sync for InstanceSlot {}
+
+impl<U: 'static, E: WasmEngine> Linker<U, E> {
+    /// Creates a new linker with the `pit.drop` import defined.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A mutable store context
+    pub fn new(ctx: &mut impl AsContextMut<UserState = U, Engine = E>) -> Self {
+        let mut imports = Imports::new();
+        imports.define("pit", "drop", Extern::Func(drop_func(ctx)));
+        Self {
+            imports,
+            interfaces: BTreeMap::new(),
+            ctors: BTreeMap::new(),
+            next: 0,
+            interceptor: None,
+            policy: None,
+            leaks: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Registers an interface, defining its `pit/{rid}.{method}` imports.
+    ///
+    /// Registering the same interface again has no effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid` - The interface definition
+    /// * `ctx` - A mutable store context
+    pub fn interface(
+        &mut self,
+        rid: Arc<Interface>,
+        ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+    ) {
+        let n = rid.rid_str();
+        if self.interfaces.contains_key(&n) {
+            return;
+        }
+        emit_methods(&mut self.imports, &rid, ctx);
+        self.interfaces.insert(n, rid);
+    }
+
+    /// Installs an interceptor for modules instantiated from now on.
+    ///
+    /// It observes their constructor calls, and every method call and drop on the
+    /// resources created by them or by [`Linker::wrap`].
+    pub fn intercept(&mut self, i: Arc<dyn Interceptor>) {
+        self.interceptor = Some(i);
+    }
+
+    /// Restricts modules instantiated from now on with a capability policy.
+    ///
+    /// Their `pit/{rid}` imports are checked when they are instantiated, and every
+    /// crossing is checked before any installed interceptor sees it.
+    pub fn policy(&mut self, p: Arc<Policy>) {
+        self.policy = Some(p);
+    }
+
+    /// Tracks the resources created by modules instantiated from now on.
+    ///
+    /// Leaks are reported by [`Linker::teardown`].
+    pub fn track(&mut self, l: Arc<Leaks>) {
+        self.leaks = Some(l);
+    }
+
+    /// Returns the interceptor for new resources, combining the policy, leak tracker
+    /// and interceptor, in that order.
+    ///
+    /// `provider` is the [`Linked::id`] of the instance implementing the resources, or
+    /// `None` for host-implemented ones.
+    fn interceptor(&self, provider: Option<u64>) -> Option<Arc<dyn Interceptor>> {
+        let mut all: Vec<Arc<dyn Interceptor>> = Vec::new();
+        if let Some(p) = self.policy.clone() {
+            all.push(p);
+        }
+        if let Some(l) = self.leaks.as_ref() {
+            all.push(match provider {
+                Some(id) => l.scoped(id),
+                None => l.clone(),
+            });
+        }
+        if let Some(i) = self.interceptor.clone() {
+            all.push(i);
+        }
+        match all.len() {
+            0 => None,
+            1 => all.pop(),
+            _ => Some(Arc::new(all)),
+        }
+    }
+
+    /// Defines an additional, non-PIT import shared by all modules.
+    pub fn define(&mut self, module: &str, name: &str, value: Extern) {
+        self.imports.define(module, name, value);
+    }
+
+    /// Returns the instance exporting the given constructor, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid` - The resource ID, as returned by `Interface::rid_str`
+    /// * `ctor` - The unique ID of the implementation, without the leading `~`
+    pub fn provider(&self, rid: &str, ctor: &str) -> Option<&Linked> {
+        self.ctors.get(&(rid.to_owned(), ctor.to_owned()))
+    }
+
+    /// Instantiates a module against the registered interfaces.
+    ///
+    /// Every `pit/{rid}.~{ctor}` import is bound to the module itself if it exports
+    /// `pit/{rid}/~{ctor}.drop`, or else to a previously instantiated module exporting it.
+    /// Constructors exported by the new instance become available to later modules,
+    /// replacing those of earlier instances of the same module.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PitError::Denied`] if the policy denies one of the module's imports,
+    /// [`PitError::Unlinked`] if it imports an interface that was not registered, or
+    /// the error instantiation failed with.
+    pub fn instantiate(
+        &mut self,
+        ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+        m: &Module,
+    ) -> Result<Linked> {
+        let slot = Arc::new(InstanceSlot {
+            wrapped: UnsafeCell::new(None),
+        });
+        let id = self.next;
+        self.next += 1;
+        let own_ctors = own_ctors(&*ctx, m);
+        let mut imports = self.imports.clone();
+        let i = m
+            .imports(ctx.as_context().engine())
+            .map(|a| (a.module.to_owned(), a.name.to_owned()))
+            .collect::<Vec<_>>();
+        if let Some(p) = self.policy.as_ref() {
+            for (module, name) in i.iter() {
+                p.check_import(module, name)?;
+            }
+        }
+        for (module, name) in i {
+            let Some(rid) = module.strip_prefix("pit/") else {
+                continue;
+            };
+            let Some(iface) = self.interfaces.get(rid) else {
+                return Err(PitError::Unlinked(format!("interface {rid}")));
+            };
+            if name == names::BATCH_IMPORT_NAME {
+                let slot = slot.clone();
+                let f = batch_func(ctx, iface, move |_| match unsafe { &*slot.wrapped.get() } {
+                    Some(i) => Ok(i.clone()),
+                    None => Err(PitError::Unlinked("caller of a batch".into()).into()),
+                });
+                imports.define(&module, &name, Extern::Func(f));
+                continue;
+            }
+            let Some(ctor) = name.strip_prefix("~") else {
+                continue;
+            };
+            let key = (rid.to_owned(), ctor.to_owned());
+            let f = match self.ctors.get(&key) {
+                Some(l) if !own_ctors.contains(&key) => {
+                    let i = l.instance.clone();
+                    ctor_func(
+                        ctx,
+                        iface.clone(),
+                        ctor.to_owned(),
+                        move |_| Ok(i.clone()),
+                        self.interceptor(Some(l.id)),
+                    )
+                }
+                _ => {
+                    let slot = slot.clone();
+                    let what =
+                        format!("instance behind pit/{rid}.~{ctor} (called during instantiation)");
+                    ctor_func(
+                        ctx,
+                        iface.clone(),
+                        ctor.to_owned(),
+                        move |_| match unsafe { &*slot.wrapped.get() } {
+                            Some(i) => Ok(i.clone()),
+                            None => Err(PitError::Unlinked(what.clone()).into()),
+                        },
+                        self.interceptor(Some(id)),
+                    )
+                }
+            };
+            imports.define(&module, &name, Extern::Func(f));
+        }
+        let instance = Instance::new(&mut *ctx, m, &imports).map_err(PitError::from)?;
+        unsafe {
+            *slot.wrapped.get() = Some(instance.clone());
+        }
+        let linked = Linked { instance, id };
+        for key in own_ctors {
+            self.ctors.insert(key, linked.clone());
+        }
+        Ok(linked)
+    }
+
+    /// Creates a [`Wrapped`] resource backed by the instance exporting `pit/{rid}/~{ctor}/...`.
+    ///
+    /// This is the host-side equivalent of a guest calling its `~ctor` import.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PitError::Unlinked`] if no linked instance exports the constructor.
+    pub fn wrap(
+        &self,
+        rid: Arc<Interface>,
+        ctor: &str,
+        handle: i32,
+        mut ctx: impl AsContextMut<UserState = U, Engine = E>,
+    ) -> Result<Wrapped<U, E>> {
+        let Some(l) = self.provider(&rid.rid_str(), ctor) else {
+            return Err(PitError::Unlinked(format!(
+                "constructor pit/{}/~{ctor}",
+                rid.rid_str()
+            )));
+        };
+        let w = Wrapped::new(
+            alloc::vec![Value::I32(handle)],
+            rid.clone(),
+            ctor.to_owned(),
+            l.instance.clone(),
+            ctx.as_context_mut(),
+        );
+        Ok(match self.interceptor(Some(l.id)) {
+            Some(x) => w.intercept(x),
+            None => w,
+        })
+    }
+
+    /// Registers a host-implemented resource so it can be passed to linked modules.
+    ///
+    /// Like [`register`](crate::register), but the resource is observed by the policy,
+    /// leak tracker and interceptor installed on this linker.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A mutable store context
+    /// * `object` - The host implementation
+    pub fn register<T: IntoWrapped<U, E> + ?Sized>(
+        &self,
+        ctx: impl AsContextMut<UserState = U, Engine = E>,
+        object: Arc<T>,
+    ) -> ExternRef {
+        register_with(ctx, object, self.interceptor(None))
+    }
+
+    /// Unlinks the constructors an instance exports and reports its leaks.
+    ///
+    /// Later modules can no longer import the instance's constructors, unless another
+    /// instance has since replaced them. If a leak tracker was installed with
+    /// [`Linker::track`], the resources the instance implements that are still alive
+    /// are forgotten and returned. Other instances of the same module are unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - The torn down instance
+    pub fn teardown(&mut self, instance: &Linked) -> Vec<Live> {
+        self.ctors.retain(|_, l| l.id != instance.id);
+        match self.leaks.as_ref() {
+            Some(l) => l.teardown_instance(instance.id),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Returns the `(rid, ctor)` pairs a module exports `pit/{rid}/~{ctor}.drop` for.
+fn own_ctors<U: 'static, E: WasmEngine>(
+    ctx: &impl AsContext<UserState = U, Engine = E>,
+    m: &Module,
+) -> Vec<(String, String)> {
+    m.exports(ctx.as_context().engine())
+        .filter_map(|e| {
+            let (rid, ctor) = e.name.strip_prefix("pit/")?.split_once("/~")?;
+            let ctor = ctor.strip_suffix(".drop")?;
+            Some((rid.to_owned(), ctor.to_owned()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{call, iface, module, store};
+
+    const IFACE: &str = "{
+    read8(I32) -> (I32)
+}";
+
+    /// A module implementing the interface as `~a`, adding the handle to the offset,
+    /// whose `make` export creates resources through its own constructor and `free`
+    /// drops them.
+    fn provider(rid: &str) -> String {
+        format!(
+            r#"(module
+    (import "pit" "drop" (func $drop (param externref)))
+    (import "pit/{rid}" "~a" (func $new (param i32) (result externref)))
+    (func (export "make") (param i32) (result externref) local.get 0 call $new)
+    (func (export "free") (param externref) local.get 0 call $drop)
+    (func (export "pit/{rid}/~a/read8") (param i32 i32) (result i32)
+        local.get 0 local.get 1 i32.add)
+    (func (export "pit/{rid}/~a.drop") (param i32))
+)"#
+        )
+    }
+
+    /// A module creating a resource through the provider's constructor and calling it.
+    fn consumer(rid: &str) -> String {
+        format!(
+            r#"(module
+    (import "pit/{rid}" "~a" (func $new (param i32) (result externref)))
+    (import "pit/{rid}" "read8" (func $read8 (param externref i32) (result i32)))
+    (func (export "run") (param i32 i32) (result i32)
+        local.get 0 call $new local.get 1 call $read8)
+)"#
+        )
+    }
+
+    #[test]
+    fn links_constructors_across_instances() {
+        let mut s = store(());
+        let i = iface(IFACE);
+        let rid = i.rid_str();
+        let mut l = Linker::new(&mut s);
+        l.interface(i, &mut s);
+        let pm = module(&s, &provider(&rid));
+        let cm = module(&s, &consumer(&rid));
+        let p = l.instantiate(&mut s, &pm).unwrap();
+        let c = l.instantiate(&mut s, &cm).unwrap();
+        assert_eq!(l.provider(&rid, "a").map(Linked::id), Some(p.id()));
+        assert!(l.provider(&rid, "b").is_none());
+        let r = call(
+            &mut s,
+            &c.instance,
+            "run",
+            &[Value::I32(5), Value::I32(2)],
+            1,
+        )
+        .unwrap();
+        assert!(matches!(r[..], [Value::I32(7)]));
+    }
+
+    #[test]
+    fn rejects_unknown_interfaces() {
+        let mut s = store(());
+        let rid = iface(IFACE).rid_str();
+        let mut l = Linker::new(&mut s);
+        let m = module(&s, &consumer(&rid));
+        assert!(matches!(
+            l.instantiate(&mut s, &m),
+            Err(PitError::Unlinked(_))
+        ));
+    }
+
+    #[test]
+    fn tears_down_one_instance() {
+        let mut s = store(());
+        let i = iface(IFACE);
+        let rid = i.rid_str();
+        let mut l = Linker::new(&mut s);
+        let leaks = Arc::new(Leaks::default());
+        l.track(leaks.clone());
+        l.interface(i, &mut s);
+        let m = module(&s, &provider(&rid));
+        let a = l.instantiate(&mut s, &m).unwrap();
+        let b = l.instantiate(&mut s, &m).unwrap();
+        assert_eq!(l.provider(&rid, "a").map(Linked::id), Some(b.id()));
+
+        // Both instances create a resource with the same handle, and only b drops it.
+        call(&mut s, &a.instance, "make", &[Value::I32(1)], 1).unwrap();
+        let r = call(&mut s, &b.instance, "make", &[Value::I32(1)], 1).unwrap();
+        call(&mut s, &b.instance, "free", &r, 0).unwrap();
+        let live = leaks.snapshot();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].instance, Some(a.id()));
+
+        let leaked = l.teardown(&a);
+        assert_eq!(leaked.len(), 1);
+        assert_eq!(leaked[0].instance, Some(a.id()));
+        assert!(leaks.snapshot().is_empty());
+        assert_eq!(l.provider(&rid, "a").map(Linked::id), Some(b.id()));
+
+        assert!(l.teardown(&b).is_empty());
+        assert!(l.provider(&rid, "a").is_none());
+    }
+}
@@ -0,0 +1,332 @@
+//! # Capability policies
+//!
+//! A [`Policy`] restricts what a guest may do over PIT: which interfaces it may import,
+//! which methods it may call, which constructors it may use, and how many resources of
+//! each interface may be alive at once.
+//!
+//! Installed on a [`Linker`](crate::linker::Linker) with
+//! [`Linker::policy`](crate::linker::Linker::policy), a policy is checked when a
+//! module's imports are defined, so a module importing something it may not use fails
+//! to instantiate, and again on every call, since a policy is also an [`Interceptor`].
+//! Host resources passed to guests are covered if they are created with
+//! [`Linker::register`](crate::linker::Linker::register).
+//!
+//! A policy only sees the calls it is installed on. Without a linker, pass it to
+//! [`emit_with`](crate::emit_with) and [`register_with`](crate::register_with), and
+//! check the module with [`Policy::check_imports`] before instantiating it; resources
+//! from [`emit`](crate::emit) or [`register`](crate::register) are not restricted.
+//!
+//! ```ignore
+//! let mut p = Policy::default();
+//! p.default = Rule::Deny;
+//! p.rids.insert(buffer.rid_str(), Rule::Allow);
+//! p.methods.insert((buffer.rid_str(), "write8".into()), Rule::Deny);
+//! p.quotas.insert(buffer.rid_str(), 16);
+//! linker.policy(Arc::new(p));
+//! ```
+
+use crate::intercept::{Event, Interceptor};
+use crate::PitError;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use pit_patch_core::names::BATCH_IMPORT_NAME;
+use wasm_runtime_layer::{backend::WasmEngine, Engine, Module, Value};
+
+/// Whether something is allowed.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Allowed.
+    #[default]
+    Allow,
+    /// Denied.
+    Deny,
+}
+
+/// A capability policy for PIT imports and calls.
+///
+/// Rules are looked up from most to least specific: a method or constructor rule,
+/// then the rule for its interface, then [`Policy::default`]. Drops are always allowed.
+#[derive(Default)]
+pub struct Policy {
+    /// The rule for anything without a more specific rule.
+    pub default: Rule,
+    /// Rules per interface, keyed by resource ID.
+    pub rids: BTreeMap<String, Rule>,
+    /// Rules per method, keyed by resource ID and method name.
+    pub methods: BTreeMap<(String, String), Rule>,
+    /// Rules per constructor, keyed by resource ID and unique ID (without the `~`).
+    pub ctors: BTreeMap<(String, String), Rule>,
+    /// The maximum number of live guest-implemented resources per interface, keyed by
+    /// resource ID.
+    pub quotas: BTreeMap<String, usize>,
+    live: spin::Mutex<BTreeMap<String, usize>>,
+}
+
+impl Policy {
+    fn rid_rule(&self, rid: &str) -> Rule {
+        self.rids.get(rid).copied().unwrap_or(self.default)
+    }
+
+    /// Returns whether a method may be called.
+    pub fn allows_method(&self, rid: &str, method: &str) -> bool {
+        let r = self
+            .methods
+            .get(&(rid.to_owned(), method.to_owned()))
+            .copied()
+            .unwrap_or_else(|| self.rid_rule(rid));
+        r == Rule::Allow
+    }
+
+    /// Returns whether a constructor may be used.
+    pub fn allows_ctor(&self, rid: &str, ctor: &str) -> bool {
+        let r = self
+            .ctors
+            .get(&(rid.to_owned(), ctor.to_owned()))
+            .copied()
+            .unwrap_or_else(|| self.rid_rule(rid));
+        r == Rule::Allow
+    }
+
+    /// Returns the number of live resources of an interface created under this policy.
+    pub fn live(&self, rid: &str) -> usize {
+        self.live.lock().get(rid).copied().unwrap_or_default()
+    }
+
+    /// Returns whether a module may import the `@batch` function of an interface: when
+    /// the interface, or any of its methods, may be called.
+    ///
+    /// The calls queued in a batch are checked one by one as they run.
+    pub fn allows_batch(&self, rid: &str) -> bool {
+        self.rid_rule(rid) == Rule::Allow
+            || self
+                .methods
+                .iter()
+                .any(|((r, _), x)| r == rid && *x == Rule::Allow)
+    }
+
+    /// Checks whether a module may import `module`.`name`.
+    ///
+    /// Imports outside the `pit/{rid}` namespace are not restricted. The `@batch`
+    /// import is checked with [`Policy::allows_batch`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PitError::Denied`] naming the import if the policy denies it.
+    pub fn check_import(&self, module: &str, name: &str) -> Result<(), PitError> {
+        let Some(rid) = module.strip_prefix("pit/") else {
+            return Ok(());
+        };
+        let allowed = match name.strip_prefix("~") {
+            Some(ctor) => self.allows_ctor(rid, ctor),
+            None if name == BATCH_IMPORT_NAME => self.allows_batch(rid),
+            None => self.allows_method(rid, name),
+        };
+        if !allowed {
+            return Err(PitError::Denied(format!("import {module}.{name}")));
+        }
+        Ok(())
+    }
+
+    /// Checks every import of a module with [`Policy::check_import`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PitError::Denied`] naming the first denied import.
+    pub fn check_imports(
+        &self,
+        engine: &Engine<impl WasmEngine>,
+        m: &Module,
+    ) -> Result<(), PitError> {
+        for i in m.imports(engine) {
+            self.check_import(i.module, i.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Interceptor for Policy {
+    fn before(&self, ev: &Event<'_>) -> anyhow::Result<()> {
+        let rid = ev.rid().rid_str();
+        match ev {
+            Event::Ctor { ctor, .. } => {
+                if !self.allows_ctor(&rid, ctor) {
+                    return Err(PitError::Denied(format!("constructor pit/{rid}.~{ctor}")).into());
+                }
+                // The slot is reserved now, so that constructors called while this one
+                // runs see it, and released in `after` if the constructor fails.
+                let q = self.quotas.get(&rid).copied();
+                let mut live = self.live.lock();
+                let n = live.entry(rid.clone()).or_default();
+                if let Some(q) = q.filter(|q| *n >= *q) {
+                    return Err(PitError::Denied(format!(
+                        "more than {q} live pit/{rid} resources"
+                    ))
+                    .into());
+                }
+                *n += 1;
+            }
+            Event::Call { method, .. } => {
+                if !self.allows_method(&rid, method) {
+                    return Err(PitError::Denied(format!("method pit/{rid}.{method}")).into());
+                }
+            }
+            Event::Drop { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn after(&self, ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+        let release = match ev {
+            // Constructors reserved their slot in `before`.
+            Event::Ctor { .. } => rets.is_err(),
+            // Host-implemented resources were never counted.
+            Event::Drop { ctor: Some(_), .. } => rets.is_ok(),
+            Event::Drop { ctor: None, .. } | Event::Call { .. } => false,
+        };
+        if release {
+            if let Some(n) = self.live.lock().get_mut(&ev.rid().rid_str()) {
+                *n = n.saturating_sub(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pit_core::Interface;
+
+    const IFACE: &str = "{
+    read8(I32) -> (I32);
+    write8(I32,I32) -> ()
+}";
+
+    fn iface() -> Interface {
+        let (_, i) = pit_core::parse_interface(IFACE).unwrap();
+        i
+    }
+
+    fn ctor_of<'a>(rid: &'a Interface, base: &'a [Value]) -> Event<'a> {
+        Event::Ctor {
+            rid,
+            ctor: "a",
+            base,
+        }
+    }
+
+    fn drop_of<'a>(rid: &'a Interface, ctor: Option<&'a str>, base: &'a [Value]) -> Event<'a> {
+        Event::Drop { rid, ctor, base }
+    }
+
+    #[test]
+    fn specific_rules_win() {
+        let rid = iface().rid_str();
+        let mut p = Policy {
+            default: Rule::Deny,
+            ..Default::default()
+        };
+        p.rids.insert(rid.clone(), Rule::Allow);
+        p.methods.insert((rid.clone(), "write8".into()), Rule::Deny);
+        p.ctors.insert((rid.clone(), "b".into()), Rule::Deny);
+        assert!(p.allows_method(&rid, "read8"));
+        assert!(!p.allows_method(&rid, "write8"));
+        assert!(p.allows_ctor(&rid, "a"));
+        assert!(!p.allows_ctor(&rid, "b"));
+        assert!(!p.allows_method("other", "read8"));
+    }
+
+    #[test]
+    fn checks_imports() {
+        let rid = iface().rid_str();
+        let mut p = Policy::default();
+        p.methods.insert((rid.clone(), "write8".into()), Rule::Deny);
+        p.ctors.insert((rid.clone(), "b".into()), Rule::Deny);
+        let m = format!("pit/{rid}");
+        assert!(p.check_import(&m, "read8").is_ok());
+        assert!(p.check_import(&m, "~a").is_ok());
+        assert!(matches!(
+            p.check_import(&m, "write8"),
+            Err(PitError::Denied(_))
+        ));
+        assert!(matches!(p.check_import(&m, "~b"), Err(PitError::Denied(_))));
+        p.default = Rule::Deny;
+        assert!(p.check_import("env", "memory").is_ok());
+    }
+
+    #[test]
+    fn denies_calls() {
+        let i = iface();
+        let mut p = Policy::default();
+        p.methods.insert((i.rid_str(), "write8".into()), Rule::Deny);
+        let call = |method| Event::Call {
+            rid: &i,
+            ctor: Some("a"),
+            base: &[],
+            method,
+            args: &[],
+        };
+        assert!(p.before(&call("read8")).is_ok());
+        assert!(p.before(&call("write8")).is_err());
+        assert!(p.before(&drop_of(&i, Some("a"), &[])).is_ok());
+    }
+
+    #[test]
+    fn enforces_quotas() {
+        let i = iface();
+        let rid = i.rid_str();
+        let mut p = Policy::default();
+        p.quotas.insert(rid.clone(), 1);
+        let base = [Value::I32(1)];
+        p.before(&ctor_of(&i, &base)).unwrap();
+        p.after(&ctor_of(&i, &base), Ok(&[]));
+        assert_eq!(p.live(&rid), 1);
+        assert!(p.before(&ctor_of(&i, &base)).is_err());
+
+        // Dropping a host-implemented resource frees nothing.
+        p.after(&drop_of(&i, None, &[]), Ok(&[]));
+        assert_eq!(p.live(&rid), 1);
+
+        p.after(&drop_of(&i, Some("a"), &base), Ok(&[]));
+        assert_eq!(p.live(&rid), 0);
+        assert!(p.before(&ctor_of(&i, &base)).is_ok());
+
+        // Failed constructors are not counted.
+        p.after(&ctor_of(&i, &base), Err(&anyhow::anyhow!("trap")));
+        assert_eq!(p.live(&rid), 0);
+    }
+
+    #[test]
+    fn reserves_quota_for_nested_constructors() {
+        let i = iface();
+        let rid = i.rid_str();
+        let mut p = Policy::default();
+        p.quotas.insert(rid.clone(), 1);
+        let base = [Value::I32(1)];
+
+        // A constructor called while another one runs sees its slot taken.
+        p.before(&ctor_of(&i, &base)).unwrap();
+        assert_eq!(p.live(&rid), 1);
+        assert!(p.before(&ctor_of(&i, &base)).is_err());
+        p.after(&ctor_of(&i, &base), Ok(&[]));
+        assert_eq!(p.live(&rid), 1);
+    }
+
+    #[test]
+    fn checks_batch_imports() {
+        let rid = iface().rid_str();
+        let m = format!("pit/{rid}");
+        let mut p = Policy::default();
+        p.methods.insert((rid.clone(), "write8".into()), Rule::Deny);
+        assert!(p.check_import(&m, BATCH_IMPORT_NAME).is_ok());
+
+        p.default = Rule::Deny;
+        assert!(matches!(
+            p.check_import(&m, BATCH_IMPORT_NAME),
+            Err(PitError::Denied(_))
+        ));
+        p.methods.insert((rid.clone(), "read8".into()), Rule::Allow);
+        assert!(p.check_import(&m, BATCH_IMPORT_NAME).is_ok());
+    }
+}
@@ -0,0 +1,223 @@
+//! # Dynamic reflection
+//!
+//! Calling [`Wrapped`] resources by method name, for hosts without generated bindings
+//! such as scripting engines and debuggers. Arguments are checked against the
+//! resource's [`Interface`] before the call reaches the guest.
+//!
+//! ```ignore
+//! for (name, sig) in wrapped.methods() {
+//!     println!("{name}{sig}");
+//! }
+//! let size = wrapped.call(&mut store, "size", &[])?;
+//! ```
+
+use crate::{PitError, Wrapped};
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::vec::Vec;
+use pit_core::{Arg, Interface, ResTy, Sig};
+use wasm_runtime_layer::{backend::WasmEngine, AsContext, AsContextMut, Value};
+
+impl<U: 'static, E: WasmEngine> Wrapped<U, E> {
+    /// Returns the methods of this resource's interface, in dispatch order.
+    pub fn methods(&self) -> impl Iterator<Item = (&str, &Sig)> + '_ {
+        self.rid.methods.iter().map(|(a, b)| (a.as_str(), b))
+    }
+
+    /// Returns the signature of a method, if the interface declares it.
+    pub fn signature(&self, name: &str) -> Option<&Sig> {
+        self.rid.methods.get(name)
+    }
+
+    /// Returns whether this resource implements the interface with the given resource ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `rid` - The resource ID, as returned by `Interface::rid_str`
+    pub fn implements(&self, rid: &str) -> bool {
+        self.rid.rid_str() == rid
+    }
+
+    /// Calls a method by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A mutable store context
+    /// * `name` - The method name
+    /// * `args` - The method arguments, without the resource itself
+    ///
+    /// # Returns
+    ///
+    /// The values returned by the method.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PitError::MissingMethod`] if the interface has no such method,
+    /// [`PitError::NullResource`] or [`PitError::TypeMismatch`] if the arguments do not
+    /// match its signature, or the error the call itself failed with.
+    pub fn call(
+        &self,
+        mut ctx: impl AsContextMut<UserState = U, Engine = E>,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Vec<Value>, PitError> {
+        let Some(j) = self.rid.methods.keys().position(|a| a == name) else {
+            return Err(PitError::MissingMethod {
+                rid: self.rid.rid_str(),
+                method: name.to_owned(),
+            });
+        };
+        let sig = &self.rid.methods[name];
+        if sig.params.len() != args.len() {
+            return Err(PitError::TypeMismatch {
+                expected: format!("{} arguments to {name}", sig.params.len()),
+                actual: format!("{}", args.len()),
+            });
+        }
+        for (k, (p, a)) in sig.params.iter().zip(args.iter()).enumerate() {
+            if !check_val(&self.rid, p, a, ctx.as_context()) {
+                return Err(match (p, a) {
+                    (Arg::Resource { .. }, Value::ExternRef(None)) => PitError::NullResource,
+                    _ => PitError::mismatch(format!("{p} for argument {k} of {name}"), a),
+                });
+            }
+        }
+        let f = self.all[j + 1].clone();
+        Ok(f(ctx.as_context_mut(), args.to_vec())?)
+    }
+}
+
+/// Checks that a value matches a PIT argument type.
+///
+/// Resources are checked for nullability and, when they are [`Wrapped`] resources,
+/// against the expected interface.
+///
+/// # Arguments
+///
+/// * `base` - The containing interface (for `this` type resolution)
+/// * `a` - The expected argument type
+/// * `v` - The value to check
+/// * `ctx` - A store context
+pub fn check_val<U: 'static, E: WasmEngine>(
+    base: &Interface,
+    a: &Arg,
+    v: &Value,
+    ctx: impl AsContext<UserState = U, Engine = E>,
+) -> bool {
+    match (a, v) {
+        (Arg::I32, Value::I32(_))
+        | (Arg::I64, Value::I64(_))
+        | (Arg::F32, Value::F32(_))
+        | (Arg::F64, Value::F64(_)) => true,
+        (Arg::Resource { nullable, .. }, Value::ExternRef(None)) => *nullable,
+        (Arg::Resource { ty, .. }, Value::ExternRef(Some(r))) => {
+            let expected = match ty {
+                ResTy::None => return true,
+                ResTy::Of(a) => hex::encode(a),
+                ResTy::This => base.rid_str(),
+                _ => return true,
+            };
+            match r.downcast::<'_, '_, Wrapped<U, E>, U, E>(ctx.as_context()) {
+                Ok(w) => w.implements(&expected),
+                Err(_) => true,
+            }
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{iface, module, store, E};
+    use alloc::string::ToString;
+    use alloc::sync::Arc;
+    use alloc::vec;
+    use wasm_runtime_layer::{ExternRef, Imports, Instance, Store};
+
+    const IFACE: &str = "{
+    read8(I32) -> (I32);
+    pass(Rthis) -> ()
+}";
+
+    /// Instantiates a module implementing the interface as `~a`, and wraps handle 1.
+    fn wrapped(s: &mut Store<(), E>, i: Arc<Interface>) -> Wrapped<(), E> {
+        let rid = i.rid_str();
+        let m = module(
+            s,
+            &format!(
+                r#"(module
+    (func (export "pit/{rid}/~a/read8") (param i32 i32) (result i32)
+        local.get 0 local.get 1 i32.add)
+    (func (export "pit/{rid}/~a/pass") (param i32 externref))
+    (func (export "pit/{rid}/~a.drop") (param i32))
+)"#
+            ),
+        );
+        let instance = Instance::new(&mut *s, &m, &Imports::new()).unwrap();
+        Wrapped::new(
+            vec![Value::I32(1)],
+            i,
+            "a".to_string(),
+            instance,
+            s.as_context_mut(),
+        )
+    }
+
+    #[test]
+    fn calls_methods_by_name() {
+        let mut s = store(());
+        let w = wrapped(&mut s, iface(IFACE));
+        assert_eq!(
+            w.methods().map(|(a, _)| a).collect::<Vec<_>>(),
+            ["pass", "read8"]
+        );
+        let r = w.call(&mut s, "read8", &[Value::I32(2)]).unwrap();
+        assert!(matches!(r[..], [Value::I32(3)]));
+    }
+
+    #[test]
+    fn rejects_mismatched_calls() {
+        let mut s = store(());
+        let w = wrapped(&mut s, iface(IFACE));
+        assert!(matches!(
+            w.call(&mut s, "write8", &[]),
+            Err(PitError::MissingMethod { method, .. }) if method == "write8"
+        ));
+        assert!(matches!(
+            w.call(&mut s, "read8", &[]),
+            Err(PitError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            w.call(&mut s, "read8", &[Value::I64(2)]),
+            Err(PitError::TypeMismatch { actual, .. }) if actual == "i64"
+        ));
+        assert!(matches!(
+            w.call(&mut s, "pass", &[Value::ExternRef(None)]),
+            Err(PitError::NullResource)
+        ));
+    }
+
+    #[test]
+    fn checks_resource_interfaces() {
+        let mut s = store(());
+        let i = iface(IFACE);
+        let other = iface("{ size() -> (I64) }");
+        let this = Arg::Resource {
+            ty: ResTy::This,
+            nullable: true,
+            take: false,
+            ann: vec![],
+        };
+        let same = wrapped(&mut s, i.clone());
+        let same = Value::ExternRef(Some(ExternRef::new(&mut s, same)));
+        let different = wrapped(&mut s, other);
+        let different = Value::ExternRef(Some(ExternRef::new(&mut s, different)));
+        let host = Value::ExternRef(Some(ExternRef::new(&mut s, ())));
+        assert!(check_val(&i, &this, &same, &s));
+        assert!(!check_val(&i, &this, &different, &s));
+        assert!(check_val(&i, &this, &host, &s));
+        assert!(check_val(&i, &this, &Value::ExternRef(None), &s));
+        assert!(!check_val(&i, &Arg::I32, &Value::F32(1.0), &s));
+    }
+}
@@ -0,0 +1,43 @@
+//! Helpers for tests that run modules, on the wasmi backend.
+
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use pit_core::Interface;
+use wasm_runtime_layer::{AsContextMut, Engine, Extern, Instance, Module, Store, Value};
+
+/// The engine tests run on.
+pub(crate) type E = wasmi_runtime_layer::Engine;
+
+/// Creates a store on a new engine.
+pub(crate) fn store<U: 'static>(data: U) -> Store<U, E> {
+    Store::new(&Engine::new(wasmi_runtime_layer::Engine::default()), data)
+}
+
+/// Compiles a module from the WebAssembly text format.
+pub(crate) fn module<U: 'static>(store: &Store<U, E>, wat: &str) -> Module {
+    let bytes = wat::parse_str(wat).unwrap();
+    Module::new(store.engine(), &bytes[..]).unwrap()
+}
+
+/// Parses an interface.
+pub(crate) fn iface(s: &str) -> Arc<Interface> {
+    let (_, i) = pit_core::parse_interface(s).unwrap();
+    Arc::new(i)
+}
+
+/// Calls an exported function, returning its `rets` results.
+pub(crate) fn call<U: 'static>(
+    store: &mut Store<U, E>,
+    i: &Instance,
+    name: &str,
+    args: &[Value],
+    rets: usize,
+) -> anyhow::Result<Vec<Value>> {
+    let Some(Extern::Func(f)) = i.get_export(store.as_context_mut(), name) else {
+        panic!("missing export {name}");
+    };
+    let mut r = vec![Value::I32(0); rets];
+    f.call(store.as_context_mut(), args, &mut r)?;
+    Ok(r)
+}
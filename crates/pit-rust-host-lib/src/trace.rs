@@ -0,0 +1,875 @@
+//! # Recording and replaying PIT traces
+//!
+//! A [`Recorder`] is an [`Interceptor`] that records every constructor, method call
+//! and drop with its arguments, results and the identity of the resource involved.
+//! [`encode`] turns a trace into a compact binary form, and [`decode`] reads it back.
+//!
+//! A [`Replayer`] instantiates a single guest module on its own and drives it from a
+//! trace: calls the guest received are made again, and calls the guest made on other
+//! resources are answered from the trace by stub resources. This reproduces a guest's
+//! behaviour without the rest of the host setup.
+//!
+//! ```ignore
+//! let rec = Arc::new(Recorder::default());
+//! linker.intercept(rec.clone());
+//! // ... run the application ...
+//! std::fs::write("app.pittrace", rec.encode())?;
+//!
+//! // later, in a unit test
+//! let mut r = Replayer::new(decode(&std::fs::read("app.pittrace")?)?);
+//! r.interface(buffer.into());
+//! r.run(&mut store, &guest_module, Imports::new())?;
+//! ```
+//!
+//! ## Format
+//!
+//! A trace starts with the magic bytes `PITT` and a format version byte, followed by
+//! LEB128-encoded records. Strings such as resource IDs are interned: each is written
+//! in full once and referred to by index afterwards.
+
+use crate::intercept::{Event, Interceptor};
+use crate::linker::InstanceSlot;
+use crate::{ctor_func, drop_func, emit_methods, PitError, Wrapped, WrappedFn};
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use core::iter::once;
+use pit_core::{Arg, Interface, ResTy};
+use pit_patch_core::names;
+use wasm_runtime_layer::{
+    backend::WasmEngine, AsContext, AsContextMut, Extern, ExternRef, Imports, Instance, Module,
+    StoreContextMut, Value,
+};
+
+/// The kind of a recorded crossing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// A `pit/{rid}.~{ctor}` constructor call.
+    Ctor,
+    /// A method call.
+    Call,
+    /// A drop.
+    Drop,
+}
+
+/// A recorded value.
+///
+/// Resources are recorded by nullness only; replay substitutes stub resources for them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Val {
+    /// An `i32`.
+    I32(i32),
+    /// An `i64`.
+    I64(i64),
+    /// The bits of an `f32`.
+    F32(u32),
+    /// The bits of an `f64`.
+    F64(u64),
+    /// A null reference.
+    Null,
+    /// A non-null reference.
+    Ref,
+}
+
+impl Val {
+    /// Records a value.
+    pub fn of(v: &Value) -> Self {
+        match v {
+            Value::I32(a) => Val::I32(*a),
+            Value::I64(a) => Val::I64(*a),
+            Value::F32(a) => Val::F32(a.to_bits()),
+            Value::F64(a) => Val::F64(a.to_bits()),
+            Value::ExternRef(None) | Value::FuncRef(None) => Val::Null,
+            Value::ExternRef(Some(_)) | Value::FuncRef(Some(_)) => Val::Ref,
+        }
+    }
+}
+
+/// A recorded crossing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// The kind of crossing.
+    pub kind: Kind,
+    /// The index of the crossing this one happened during, if any.
+    pub parent: Option<usize>,
+    /// The resource ID of the interface involved.
+    pub rid: String,
+    /// The unique ID of the implementation, or `None` for host-implemented resources.
+    pub ctor: Option<String>,
+    /// The values the resource was constructed with.
+    pub base: Vec<Val>,
+    /// The method name, empty for constructors and drops.
+    pub method: String,
+    /// The method arguments, without the resource itself.
+    pub args: Vec<Val>,
+    /// The results, or the error message the crossing failed with.
+    pub rets: Result<Vec<Val>, String>,
+}
+
+#[derive(Default)]
+struct RecState {
+    records: Vec<Record>,
+    stack: Vec<usize>,
+}
+
+/// An interceptor recording every crossing it observes.
+#[derive(Default)]
+pub struct Recorder {
+    state: spin::Mutex<RecState>,
+}
+
+impl Recorder {
+    /// Returns a copy of the records so far.
+    pub fn records(&self) -> Vec<Record> {
+        self.state.lock().records.clone()
+    }
+
+    /// Encodes the records so far, see [`encode`].
+    pub fn encode(&self) -> Vec<u8> {
+        encode(&self.state.lock().records)
+    }
+
+    /// Discards the records so far.
+    pub fn clear(&self) {
+        let mut s = self.state.lock();
+        s.records.clear();
+        s.stack.clear();
+    }
+}
+
+impl Interceptor for Recorder {
+    fn before(&self, ev: &Event<'_>) -> anyhow::Result<()> {
+        let mut s = self.state.lock();
+        let (kind, method, args) = match ev {
+            Event::Ctor { .. } => (Kind::Ctor, "", &[][..]),
+            Event::Call { method, args, .. } => (Kind::Call, *method, *args),
+            Event::Drop { .. } => (Kind::Drop, "", &[][..]),
+        };
+        let r = Record {
+            kind,
+            parent: s.stack.last().copied(),
+            rid: ev.rid().rid_str(),
+            ctor: ev.ctor().map(|a| a.to_owned()),
+            base: ev.base().iter().map(Val::of).collect(),
+            method: method.to_owned(),
+            args: args.iter().map(Val::of).collect(),
+            rets: Ok(vec![]),
+        };
+        let i = s.records.len();
+        s.records.push(r);
+        s.stack.push(i);
+        Ok(())
+    }
+
+    fn after(&self, _ev: &Event<'_>, rets: Result<&[Value], &anyhow::Error>) {
+        let mut s = self.state.lock();
+        let Some(i) = s.stack.pop() else {
+            return;
+        };
+        s.records[i].rets = match rets {
+            Ok(r) => Ok(r.iter().map(Val::of).collect()),
+            Err(e) => Err(alloc::format!("{e}")),
+        };
+    }
+}
+
+const MAGIC: &[u8] = b"PITT";
+const VERSION: u8 = 1;
+
+struct Writer {
+    out: Vec<u8>,
+    strings: BTreeMap<String, u64>,
+}
+
+impl Writer {
+    fn uint(&mut self, mut v: u64) {
+        loop {
+            let b = (v & 0x7f) as u8;
+            v >>= 7;
+            if v == 0 {
+                self.out.push(b);
+                return;
+            }
+            self.out.push(b | 0x80);
+        }
+    }
+    fn int(&mut self, v: i64) {
+        self.uint(((v << 1) ^ (v >> 63)) as u64)
+    }
+    fn str(&mut self, s: &str) {
+        if let Some(i) = self.strings.get(s).copied() {
+            self.uint(i + 1);
+            return;
+        }
+        let i = self.strings.len() as u64;
+        self.strings.insert(s.to_owned(), i);
+        self.uint(0);
+        self.uint(s.len() as u64);
+        self.out.extend_from_slice(s.as_bytes());
+    }
+    fn vals(&mut self, v: &[Val]) {
+        self.uint(v.len() as u64);
+        for v in v {
+            match v {
+                Val::I32(a) => {
+                    self.out.push(0);
+                    self.int(*a as i64)
+                }
+                Val::I64(a) => {
+                    self.out.push(1);
+                    self.int(*a)
+                }
+                Val::F32(a) => {
+                    self.out.push(2);
+                    self.out.extend_from_slice(&a.to_le_bytes())
+                }
+                Val::F64(a) => {
+                    self.out.push(3);
+                    self.out.extend_from_slice(&a.to_le_bytes())
+                }
+                Val::Null => self.out.push(4),
+                Val::Ref => self.out.push(5),
+            }
+        }
+    }
+}
+
+/// Encodes a trace.
+pub fn encode(records: &[Record]) -> Vec<u8> {
+    let mut w = Writer {
+        out: MAGIC.to_vec(),
+        strings: BTreeMap::new(),
+    };
+    w.out.push(VERSION);
+    w.uint(records.len() as u64);
+    for r in records {
+        w.out.push(match r.kind {
+            Kind::Ctor => 0,
+            Kind::Call => 1,
+            Kind::Drop => 2,
+        });
+        w.uint(r.parent.map_or(0, |p| p as u64 + 1));
+        w.str(&r.rid);
+        match r.ctor.as_ref() {
+            None => w.out.push(0),
+            Some(c) => {
+                w.out.push(1);
+                w.str(c)
+            }
+        }
+        w.vals(&r.base);
+        w.str(&r.method);
+        w.vals(&r.args);
+        match r.rets.as_ref() {
+            Ok(v) => {
+                w.out.push(0);
+                w.vals(v)
+            }
+            Err(e) => {
+                w.out.push(1);
+                w.str(e)
+            }
+        }
+    }
+    w.out
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    strings: Vec<String>,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> anyhow::Result<u8> {
+        let Some((b, rest)) = self.data.split_first() else {
+            anyhow::bail!("truncated trace")
+        };
+        self.data = rest;
+        Ok(*b)
+    }
+    fn bytes(&mut self, n: usize) -> anyhow::Result<&'a [u8]> {
+        if self.data.len() < n {
+            anyhow::bail!("truncated trace")
+        }
+        let (a, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(a)
+    }
+    fn uint(&mut self) -> anyhow::Result<u64> {
+        let mut v = 0u64;
+        let mut shift = 0;
+        loop {
+            let b = self.byte()?;
+            if shift >= 64 {
+                anyhow::bail!("invalid integer in trace")
+            }
+            v |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(v);
+            }
+            shift += 7;
+        }
+    }
+    fn int(&mut self) -> anyhow::Result<i64> {
+        let v = self.uint()?;
+        Ok(((v >> 1) as i64) ^ -((v & 1) as i64))
+    }
+    fn str(&mut self) -> anyhow::Result<String> {
+        let i = self.uint()?;
+        if i != 0 {
+            let Some(s) = self.strings.get(i as usize - 1) else {
+                anyhow::bail!("invalid string reference in trace")
+            };
+            return Ok(s.clone());
+        }
+        let n = self.uint()? as usize;
+        let s = String::from_utf8(self.bytes(n)?.to_vec())?;
+        self.strings.push(s.clone());
+        Ok(s)
+    }
+    fn vals(&mut self) -> anyhow::Result<Vec<Val>> {
+        let n = self.uint()?;
+        (0..n)
+            .map(|_| {
+                Ok(match self.byte()? {
+                    0 => Val::I32(self.int()? as i32),
+                    1 => Val::I64(self.int()?),
+                    2 => {
+                        let mut a = [0u8; 4];
+                        a.copy_from_slice(self.bytes(4)?);
+                        Val::F32(u32::from_le_bytes(a))
+                    }
+                    3 => {
+                        let mut a = [0u8; 8];
+                        a.copy_from_slice(self.bytes(8)?);
+                        Val::F64(u64::from_le_bytes(a))
+                    }
+                    4 => Val::Null,
+                    5 => Val::Ref,
+                    _ => anyhow::bail!("invalid value in trace"),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Decodes a trace produced by [`encode`].
+///
+/// # Errors
+///
+/// Returns an error if the data is not a valid trace.
+pub fn decode(data: &[u8]) -> anyhow::Result<Vec<Record>> {
+    let Some(data) = data.strip_prefix(MAGIC) else {
+        anyhow::bail!("not a PIT trace")
+    };
+    let mut r = Reader {
+        data,
+        strings: vec![],
+    };
+    if r.byte()? != VERSION {
+        anyhow::bail!("unsupported PIT trace version")
+    }
+    let n = r.uint()?;
+    (0..n)
+        .map(|_| {
+            let kind = match r.byte()? {
+                0 => Kind::Ctor,
+                1 => Kind::Call,
+                2 => Kind::Drop,
+                _ => anyhow::bail!("invalid record kind in trace"),
+            };
+            let parent = match r.uint()? {
+                0 => None,
+                p => Some(p as usize - 1),
+            };
+            let rid = r.str()?;
+            let ctor = match r.byte()? {
+                0 => None,
+                _ => Some(r.str()?),
+            };
+            let base = r.vals()?;
+            let method = r.str()?;
+            let args = r.vals()?;
+            let rets = match r.byte()? {
+                0 => Ok(r.vals()?),
+                _ => Err(r.str()?),
+            };
+            Ok(Record {
+                kind,
+                parent,
+                rid,
+                ctor,
+                base,
+                method,
+                args,
+                rets,
+            })
+        })
+        .collect()
+}
+
+/// Drives a single guest module from a trace.
+///
+/// Records are split by the resource involved:
+/// - crossings into resources the module exports (its own constructors) are inputs,
+///   and are made again by calling the module's exports
+/// - method calls the module made on any other resource are outputs, and are answered
+///   from the trace, in order, by stub resources
+///
+/// Inputs made by the host are replayed in order; inputs made while the guest was
+/// calling out are replayed from inside the stub answering that call.
+///
+/// Only resources created by the module's own constructors are driven. Every resource
+/// passed to the module, as an argument or a result, is replaced by a stub, even one
+/// the module exported itself, since the trace records references by nullness only.
+pub struct Replayer {
+    records: Arc<Vec<Record>>,
+    interfaces: BTreeMap<String, Arc<Interface>>,
+}
+
+struct ReplayState {
+    records: Arc<Vec<Record>>,
+    interfaces: BTreeMap<String, Arc<Interface>>,
+    outputs: Vec<usize>,
+    inputs: BTreeMap<usize, Vec<usize>>,
+    cursor: spin::Mutex<usize>,
+    slot: Arc<InstanceSlot>,
+}
+
+impl Replayer {
+    /// Creates a replayer for a decoded trace.
+    pub fn new(records: Vec<Record>) -> Self {
+        Self {
+            records: Arc::new(records),
+            interfaces: BTreeMap::new(),
+        }
+    }
+
+    /// Registers an interface used by the guest module.
+    ///
+    /// Every interface the module imports, or receives resources of, must be registered.
+    pub fn interface(&mut self, rid: Arc<Interface>) {
+        self.interfaces.insert(rid.rid_str(), rid);
+    }
+
+    /// Instantiates the module and replays the trace against it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A mutable store context
+    /// * `m` - The guest module
+    /// * `imports` - Any non-PIT imports the module needs
+    ///
+    /// # Returns
+    ///
+    /// The instance, after all inputs have been replayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the guest traps, if a replayed input returns a different
+    /// scalar result than recorded, or if the guest's outputs diverge from the trace.
+    pub fn run<U: 'static, E: WasmEngine>(
+        &self,
+        ctx: &mut impl AsContextMut<UserState = U, Engine = E>,
+        m: &Module,
+        mut imports: Imports,
+    ) -> anyhow::Result<Instance> {
+        let own = m
+            .exports(ctx.as_context().engine())
+            .filter_map(|e| {
+                let (_, ctor) = e.name.strip_prefix("pit/")?.split_once("/~")?;
+                Some(ctor.strip_suffix(".drop")?.to_owned())
+            })
+            .collect::<BTreeSet<_>>();
+        let is_input = |r: &Record| {
+            r.kind != Kind::Ctor && r.ctor.as_ref().is_some_and(|c| own.contains(c))
+        };
+        let outputs = self
+            .records
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| {
+                r.kind == Kind::Call
+                    && !is_input(r)
+                    && r.parent.is_some_and(|p| is_input(&self.records[p]))
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let mut inputs = BTreeMap::<usize, Vec<usize>>::new();
+        for (i, r) in self.records.iter().enumerate() {
+            if let Some(p) = r.parent.filter(|_| is_input(r)) {
+                inputs.entry(p).or_default().push(i);
+            }
+        }
+        let state = Arc::new(ReplayState {
+            records: self.records.clone(),
+            interfaces: self.interfaces.clone(),
+            outputs,
+            inputs,
+            cursor: spin::Mutex::new(0),
+            slot: Arc::new(InstanceSlot {
+                wrapped: UnsafeCell::new(None),
+            }),
+        });
+        imports.define("pit", "drop", Extern::Func(drop_func(ctx)));
+        for i in self.interfaces.values() {
+            emit_methods(&mut imports, i, ctx);
+        }
+        let i = m
+            .imports(ctx.as_context().engine())
+            .map(|a| (a.module.to_owned(), a.name.to_owned()))
+            .collect::<Vec<_>>();
+        for (module, name) in i {
+            let (Some(rid), Some(ctor)) = (module.strip_prefix("pit/"), name.strip_prefix("~"))
+            else {
+                continue;
+            };
+            let Some(iface) = self.interfaces.get(rid) else {
+                anyhow::bail!("interface {rid} is not registered with the replayer")
+            };
+            let slot = state.slot.clone();
+            let f = ctor_func(
+                ctx,
+                iface.clone(),
+                ctor.to_owned(),
+                move |_| match unsafe { &*slot.wrapped.get() } {
+                    Some(i) => Ok(i.clone()),
+                    None => anyhow::bail!("constructor called during instantiation"),
+                },
+                None,
+            );
+            imports.define(&module, &name, Extern::Func(f));
+        }
+        let instance = Instance::new(&mut *ctx, m, &imports)?;
+        unsafe {
+            *state.slot.wrapped.get() = Some(instance.clone());
+        }
+        for (i, r) in self.records.iter().enumerate() {
+            if is_input(r) && r.parent.is_none() {
+                input(&state, i, ctx.as_context_mut())?;
+            }
+        }
+        Ok(instance)
+    }
+}
+
+/// Converts recorded values back into values, creating stubs for resources.
+fn revive<U: 'static, E: WasmEngine>(
+    state: &Arc<ReplayState>,
+    base: &Interface,
+    tys: &[Arg],
+    vals: &[Val],
+    mut ctx: StoreContextMut<'_, U, E>,
+) -> anyhow::Result<Vec<Value>> {
+    tys.iter()
+        .zip(vals.iter())
+        .map(|(t, v)| {
+            Ok(match v {
+                Val::I32(a) => Value::I32(*a),
+                Val::I64(a) => Value::I64(*a),
+                Val::F32(a) => Value::F32(f32::from_bits(*a)),
+                Val::F64(a) => Value::F64(f64::from_bits(*a)),
+                Val::Null => Value::ExternRef(None),
+                Val::Ref => {
+                    let Arg::Resource { ty, .. } = t else {
+                        anyhow::bail!("trace has a reference where the signature does not")
+                    };
+                    let rid = match ty {
+                        ResTy::Of(a) => hex::encode(a),
+                        ResTy::This => base.rid_str(),
+                        _ => return Ok(Value::ExternRef(Some(ExternRef::new(ctx.as_context_mut(), ())))),
+                    };
+                    let Some(iface) = state.interfaces.get(&rid) else {
+                        anyhow::bail!("interface {rid} is not registered with the replayer")
+                    };
+                    Value::ExternRef(Some(ExternRef::new(
+                        ctx.as_context_mut(),
+                        stub::<U, E>(state, iface.clone()),
+                    )))
+                }
+            })
+        })
+        .collect()
+}
+
+/// Replays an input record by calling the guest's export.
+fn input<U: 'static, E: WasmEngine>(
+    state: &Arc<ReplayState>,
+    i: usize,
+    mut ctx: StoreContextMut<'_, U, E>,
+) -> anyhow::Result<()> {
+    let r = &state.records[i];
+    let Some(iface) = state.interfaces.get(&r.rid) else {
+        anyhow::bail!("interface {} is not registered with the replayer", r.rid)
+    };
+    let Some(instance) = (unsafe { &*state.slot.wrapped.get() }).clone() else {
+        anyhow::bail!("replay started before instantiation")
+    };
+    let ctor = r.ctor.as_deref().unwrap_or_default();
+    let base_tys = vec![Arg::I32; r.base.len()];
+    let mut vals = revive(state, iface, &base_tys, &r.base, ctx.as_context_mut())?;
+    let (name, n_rets) = match r.kind {
+        Kind::Drop => (names::drop_export_name(&r.rid, ctor), 0),
+        _ => {
+            let Some(sig) = iface.methods.get(&r.method) else {
+                anyhow::bail!("no method {} in interface {}", r.method, r.rid)
+            };
+            vals.extend(revive(state, iface, &sig.params, &r.args, ctx.as_context_mut())?);
+            (
+                names::method_export_name(&r.rid, ctor, &r.method),
+                sig.rets.len(),
+            )
+        }
+    };
+    let Some(Extern::Func(f)) = instance.get_export(ctx.as_context_mut(), &name) else {
+        return Err(PitError::MissingExport(name).into());
+    };
+    let mut rets = vec![Value::I32(0); n_rets];
+    f.call(ctx.as_context_mut(), &vals, &mut rets)?;
+    if let Ok(expected) = r.rets.as_ref() {
+        for (k, (a, b)) in rets.iter().zip(expected.iter()).enumerate() {
+            let a = Val::of(a);
+            if matches!(b, Val::Ref | Val::Null) {
+                continue;
+            }
+            if a != *b {
+                anyhow::bail!(
+                    "replay diverged: result {k} of record {i} ({}) is {a:?}, recorded {b:?}",
+                    name
+                )
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Creates a stub resource answering method calls from the trace.
+fn stub<U: 'static, E: WasmEngine>(state: &Arc<ReplayState>, rid: Arc<Interface>) -> Wrapped<U, E> {
+    let drop = Arc::new(
+        |_: StoreContextMut<'_, U, E>, _: Vec<Value>| -> anyhow::Result<Vec<Value>> { Ok(vec![]) },
+    ) as WrappedFn<U, E>;
+    let methods = rid.methods.iter().map(|(name, sig)| {
+        let state = state.clone();
+        let rid = rid.clone();
+        let name = name.clone();
+        let sig = sig.clone();
+        Arc::new(
+            move |mut ctx: StoreContextMut<'_, U, E>, _: Vec<Value>| -> anyhow::Result<Vec<Value>> {
+                let i = {
+                    let mut c = state.cursor.lock();
+                    let Some(i) = state.outputs.get(*c).copied() else {
+                        anyhow::bail!(
+                            "replay diverged: guest called pit/{}.{name} after the end of the trace",
+                            rid.rid_str()
+                        )
+                    };
+                    *c += 1;
+                    i
+                };
+                let r = &state.records[i];
+                if r.rid != rid.rid_str() || r.method != name {
+                    anyhow::bail!(
+                        "replay diverged: guest called pit/{}.{name}, recorded pit/{}.{}",
+                        rid.rid_str(),
+                        r.rid,
+                        r.method
+                    )
+                }
+                for j in state.inputs.get(&i).into_iter().flatten().copied() {
+                    input(&state, j, ctx.as_context_mut())?;
+                }
+                match r.rets.as_ref() {
+                    Ok(v) => revive(&state, &rid, &sig.rets, v, ctx),
+                    Err(e) => anyhow::bail!("{e}"),
+                }
+            },
+        ) as WrappedFn<U, E>
+    });
+    Wrapped {
+        rid: rid.clone(),
+        all: once(drop).chain(methods).collect(),
+        ctor: None,
+        base: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{call, iface, module, store};
+    use alloc::format;
+    use alloc::string::ToString;
+
+    fn records() -> Vec<Record> {
+        let rid = "867207405fe87fda620c2d7a5485e8e5e274636a898a166fb674448b4391ffc5".to_owned();
+        let ctor = Some("589c4a05dd51e35d7b0a11dccb5f188299087929ca59c488696fc3f31b314fa5".to_owned());
+        vec![
+            Record {
+                kind: Kind::Ctor,
+                parent: None,
+                rid: rid.clone(),
+                ctor: ctor.clone(),
+                base: vec![Val::I32(3)],
+                method: String::new(),
+                args: vec![],
+                rets: Ok(vec![Val::Ref]),
+            },
+            Record {
+                kind: Kind::Call,
+                parent: None,
+                rid: rid.clone(),
+                ctor: ctor.clone(),
+                base: vec![Val::I32(3)],
+                method: "write8".to_owned(),
+                args: vec![Val::I32(-1), Val::I64(i64::MIN), Val::F32(1.5f32.to_bits())],
+                rets: Ok(vec![]),
+            },
+            Record {
+                kind: Kind::Call,
+                parent: Some(1),
+                rid: rid.clone(),
+                ctor: None,
+                base: vec![],
+                method: "read8".to_owned(),
+                args: vec![Val::F64(f64::NAN.to_bits()), Val::Null],
+                rets: Err("out of range".to_owned()),
+            },
+            Record {
+                kind: Kind::Drop,
+                parent: None,
+                rid,
+                ctor,
+                base: vec![Val::I32(i32::MAX)],
+                method: String::new(),
+                args: vec![],
+                rets: Ok(vec![]),
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        let r = records();
+        assert_eq!(decode(&encode(&r)).unwrap(), r);
+        assert_eq!(decode(&encode(&[])).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn interns_strings() {
+        let r = records();
+        let once = encode(&r[..1]).len();
+        let twice = encode(&[r[0].clone(), r[0].clone()]).len();
+        assert!(twice - once < 64, "resource IDs are written once");
+    }
+
+    #[test]
+    fn rejects_invalid_traces() {
+        let data = encode(&records());
+        assert!(decode(b"nope").is_err());
+        assert!(decode(&data[..data.len() - 1]).is_err());
+        let mut v = data.clone();
+        v[MAGIC.len()] = VERSION + 1;
+        assert!(decode(&v).is_err());
+        let mut v = data;
+        v[MAGIC.len() + 2] = 7;
+        assert!(decode(&v).is_err());
+    }
+
+    /// A guest implementing `run` as `~a`, which adds its handle and one to what
+    /// `get` returns on the source it is passed, and counts its drops.
+    fn guest(source: &str, rid: &str) -> String {
+        format!(
+            r#"(module
+    (import "pit/{source}" "get" (func $get (param externref) (result i32)))
+    (global $drops (mut i32) (i32.const 0))
+    (func (export "pit/{rid}/~a/run") (param i32 externref) (result i32)
+        local.get 1 call $get local.get 0 i32.add i32.const 1 i32.add)
+    (func (export "pit/{rid}/~a.drop") (param i32)
+        global.get $drops i32.const 1 i32.add global.set $drops)
+    (func (export "drops") (result i32) global.get $drops)
+)"#
+        )
+    }
+
+    /// A trace of the host creating a resource, running it once against a source
+    /// returning `got` and dropping it.
+    fn trace(source: &str, rid: &str, got: i32, ran: i32) -> Vec<Record> {
+        let own = |kind, method: &str, args, rets| Record {
+            kind,
+            parent: None,
+            rid: rid.to_owned(),
+            ctor: Some("a".to_owned()),
+            base: vec![Val::I32(1)],
+            method: method.to_owned(),
+            args,
+            rets: Ok(rets),
+        };
+        vec![
+            own(Kind::Ctor, "", vec![], vec![Val::Ref]),
+            own(Kind::Call, "run", vec![Val::Ref], vec![Val::I32(ran)]),
+            Record {
+                kind: Kind::Call,
+                parent: Some(1),
+                rid: source.to_owned(),
+                ctor: None,
+                base: vec![],
+                method: "get".to_owned(),
+                args: vec![],
+                rets: Ok(vec![Val::I32(got)]),
+            },
+            own(Kind::Drop, "", vec![], vec![]),
+        ]
+    }
+
+    fn replayer(records: Vec<Record>, source: &Arc<Interface>, i: &Arc<Interface>) -> Replayer {
+        let mut r = Replayer::new(decode(&encode(&records)).unwrap());
+        r.interface(source.clone());
+        r.interface(i.clone());
+        r
+    }
+
+    #[test]
+    fn replays_a_guest() {
+        let source = iface("{ get() -> (I32) }");
+        let i = iface(&format!("{{ run(R{}) -> (I32) }}", source.rid_str()));
+        let (sr, rid) = (source.rid_str(), i.rid_str());
+        let mut s = store(());
+        let m = module(&s, &guest(&sr, &rid));
+        let r = replayer(trace(&sr, &rid, 40, 42), &source, &i);
+        let instance = r.run(&mut s, &m, Imports::new()).unwrap();
+        let d = call(&mut s, &instance, "drops", &[], 1).unwrap();
+        assert!(matches!(d[..], [Value::I32(1)]));
+    }
+
+    #[test]
+    fn detects_divergence() {
+        let source = iface("{ get() -> (I32) }");
+        let i = iface(&format!("{{ run(R{}) -> (I32) }}", source.rid_str()));
+        let (sr, rid) = (source.rid_str(), i.rid_str());
+        let mut s = store(());
+        let m = module(&s, &guest(&sr, &rid));
+
+        // The guest returns 42 where 41 was recorded.
+        let r = replayer(trace(&sr, &rid, 40, 41), &source, &i);
+        let e = r.run(&mut s, &m, Imports::new()).unwrap_err();
+        assert!(e.to_string().contains("replay diverged"), "{e}");
+
+        // The guest calls get, which the trace has no answer for.
+        let mut records = trace(&sr, &rid, 40, 42);
+        records.remove(2);
+        records[2].parent = None;
+        let r = replayer(records, &source, &i);
+        let e = r.run(&mut s, &m, Imports::new()).unwrap_err();
+        let e = format!("{e:#}");
+        assert!(e.contains("after the end of the trace"), "{e}");
+
+        // Interfaces the guest imports must be registered.
+        let mut r = Replayer::new(trace(&sr, &rid, 40, 42));
+        r.interface(i.clone());
+        assert!(r.run(&mut s, &m, Imports::new()).is_err());
+    }
+}
@@ -0,0 +1,28 @@
+package pc.portal.pit.guest;
+
+import org.teavm.interop.Import;
+
+/**
+ * Converts between values of type T and TPIT handles, where 0 is the null handle.
+ */
+public abstract class Handler<T> {
+    public abstract T fromHandle(int handle);
+
+    public abstract int handleOf(T value);
+
+    /** Raw handles, for untyped resources. */
+    public static final Handler<Integer> INT = new Handler<Integer>() {
+        @Override
+        public Integer fromHandle(int handle) {
+            return handle;
+        }
+
+        @Override
+        public int handleOf(Integer value) {
+            return value == null ? 0 : value;
+        }
+    };
+
+    @Import(name = "drop", module = "tpit")
+    public static native void drop(int handle);
+}
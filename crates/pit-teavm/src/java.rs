@@ -0,0 +1,463 @@
+//! Java code generation.
+//!
+//! Generates plain Java code for TeaVM, with the same structure as the Scala output:
+//! - An interface `R{rid}` with all interface methods, closed when the resource is dropped
+//! - A static `R{rid}.handler(...)` converting between values and TPIT handles
+//! - A nested `Impl` class that wraps a handle and implements the interface through
+//!   `@Import`s, and whose other constructor exports any implementation through the
+//!   `@Export`s next to it
+//!
+//! Interfaces with `[generics=N]` take type parameters `T0`..`T{N-1}`, with one
+//! `Handler` per parameter passed to `handler` and `Impl`. Resources annotated with
+//! `instance` are typed as with Scala, while others, and those referring to generic
+//! parameters of methods, are raw `int` handles. Nullable resources are `null` when
+//! absent.
+//!
+//! TeaVM imports and exports return at most one value, so interfaces with methods
+//! returning more than one are rejected with [`Unsupported`]. The generated code
+//! depends on the `Handler` class returned by [`runtime`].
+
+use itertools::Itertools;
+use pit_core::{Arg, Interface, ResTy, Sig};
+use std::fmt;
+use std::iter::once;
+
+/// An interface Java code cannot be generated for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    /// The method returning more than one value.
+    pub method: String,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} returns more than one value, which TeaVM cannot express",
+            self.method
+        )
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// Generates Java code from a PIT interface definition.
+///
+/// # Arguments
+///
+/// * `i` - The PIT interface to convert
+/// * `pkg` - The Java package name for the generated code
+///
+/// # Returns
+///
+/// A string containing the complete Java source file, to be saved as `R{rid}.java`,
+/// or [`Unsupported`] if a method returns more than one value.
+pub fn emit(i: &Interface, pkg: &str) -> Result<String, Unsupported> {
+    if let Some((a, _)) = i.methods.iter().find(|(_, b)| b.rets.len() > 1) {
+        return Err(Unsupported { method: a.clone() });
+    }
+    let rid = i.rid_str();
+    let generics: u32 = i
+        .ann
+        .iter()
+        .find_map(|a| {
+            if a.name == "generics" {
+                Some(a.value.clone())
+            } else {
+                None
+            }
+        })
+        .map(|a| a.parse().unwrap())
+        .unwrap_or_default();
+    let tp = angle((0..generics).map(|a| format!("T{a}")));
+    let wild = angle((0..generics).map(|_| "?".to_owned()));
+    let diamond = if generics == 0 { "" } else { "<>" };
+    let hparams = (0..generics)
+        .map(|a| format!(", Handler<T{a}> h{a}"))
+        .join("");
+    let hargs = (0..generics).map(|a| format!(", h{a}")).join("");
+    let ctp = if generics == 0 {
+        String::new()
+    } else {
+        format!("{tp} ")
+    };
+    let fields = |indent: &str| {
+        (0..generics)
+            .map(|a| format!("\n{indent}final Handler<T{a}> h{a};"))
+            .join("")
+    };
+    let init = |indent: &str| {
+        (0..generics)
+            .map(|a| format!("\n{indent}this.h{a} = h{a};"))
+            .join("")
+    };
+    let ty = |a: &Arg| jty(a, &rid, generics);
+    let sig = |b: &Sig| {
+        (
+            b.rets.first().map(ty).unwrap_or_else(|| "void".to_owned()),
+            b.params
+                .iter()
+                .enumerate()
+                .map(|(idx, a)| format!("{} p{idx}", ty(a)))
+                .join(", "),
+        )
+    };
+    let ffi_sig = |b: &Sig| {
+        (
+            b.rets
+                .first()
+                .map(|a| ffi_ty(a).to_owned())
+                .unwrap_or_else(|| "void".to_owned()),
+            once("int handle".to_owned())
+                .chain(
+                    b.params
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, a)| format!("{} p{idx}", ffi_ty(a))),
+                )
+                .join(", "),
+        )
+    };
+    let decls = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let (ret, params) = sig(b);
+            format!("\n    {ret} {a}({params});")
+        })
+        .join("");
+    let methods = i
+        .methods
+        .iter()
+        .map(|(a, b)| {
+            let (ret, params) = sig(b);
+            let (fret, fparams) = ffi_sig(b);
+            let r = if b.rets.is_empty() { "" } else { "return " };
+            let call = format!(
+                "import${a}({})",
+                once("handle".to_owned())
+                    .chain(
+                        b.params
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, p)| to_ffi(p, &rid, generics, "", &format!("p{idx}")))
+                    )
+                    .join(", ")
+            );
+            let call = match b.rets.first() {
+                Some(t) => from_ffi(t, &rid, generics, "", &call),
+                None => call,
+            };
+            let ecall = format!(
+                "z.res.{a}({})",
+                b.params
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, p)| from_ffi(p, &rid, generics, "z.", &format!("p{idx}")))
+                    .join(", ")
+            );
+            let ecall = match b.rets.first() {
+                Some(t) => to_ffi(t, &rid, generics, "z.", &ecall),
+                None => ecall,
+            };
+            let eargs = once("all.get(handle)".to_owned())
+                .chain((0..b.params.len()).map(|idx| format!("p{idx}")))
+                .join(", ");
+            let cparams = once(format!("Exported{tp} z"))
+                .chain(
+                    b.params
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, a)| format!("{} p{idx}", ffi_ty(a))),
+                )
+                .join(", ");
+            format!(
+                r#"
+
+        @Import(name = "{a}", module = "tpit/{rid}")
+        private static native {fret} import${a}({fparams});
+
+        @Override
+        public {ret} {a}({params}) {{
+            {r}{call};
+        }}
+
+        @Export(name = "tpit/{rid}/~{pkg}/{a}")
+        public static {fret} export${a}({fparams}) {{
+            {r}call${a}({eargs});
+        }}
+
+        private static {ctp}{fret} call${a}({cparams}) {{
+            {r}{ecall};
+        }}"#
+            )
+        })
+        .join("");
+    Ok(format!(
+        r#"package {pkg};
+
+import java.util.HashMap;
+import java.util.Map;
+import org.teavm.interop.Export;
+import org.teavm.interop.Import;
+import pc.portal.pit.guest.Handler;
+
+public interface R{rid}{tp} extends AutoCloseable {{{decls}
+
+    @Override
+    void close();
+
+    static {ctp}Handler<R{rid}{tp}> handler({}) {{
+        return new Handler<R{rid}{tp}>() {{
+            @Override
+            public R{rid}{tp} fromHandle(int handle) {{
+                return handle == 0 ? null : new Impl{diamond}(handle{hargs});
+            }}
+
+            @Override
+            public int handleOf(R{rid}{tp} value) {{
+                return value == null ? 0 : Impl.of(value{hargs}).handle;
+            }}
+        }};
+    }}
+
+    final class Impl{tp} implements R{rid}{tp} {{
+        public final int handle;{}
+
+        public Impl(int handle{hparams}) {{
+            this.handle = handle;{}
+        }}
+
+        public Impl(R{rid}{tp} res{hparams}) {{
+            this(export(new Exported{diamond}(res{hargs})){hargs});
+        }}
+
+        @SuppressWarnings("unchecked")
+        public static {ctp}Impl{tp} of(R{rid}{tp} value{hparams}) {{
+            return value instanceof Impl ? (Impl{tp}) value : new Impl{diamond}(value{hargs});
+        }}
+
+        @Override
+        public void close() {{
+            Handler.drop(handle);
+        }}
+
+        private static final class Exported{tp} {{
+            final R{rid}{tp} res;{}
+
+            Exported(R{rid}{tp} res{hparams}) {{
+                this.res = res;{}
+            }}
+        }}
+
+        private static final Map<Integer, Exported{wild}> all = new HashMap<>();
+
+        @Import(name = "~{pkg}", module = "tpit/{rid}")
+        private static native int ctor(int index);
+
+        private static int export(Exported{wild} e) {{
+            int x = 0;
+            while (all.containsKey(x)) {{
+                x++;
+            }}
+            all.put(x, e);
+            return ctor(x);
+        }}
+
+        @Export(name = "tpit/{rid}/~{pkg}.drop")
+        public static void export$drop(int handle) {{
+            Exported{wild} z = all.remove(handle);
+            if (z != null) {{
+                z.res.close();
+            }}
+        }}{methods}
+    }}
+}}
+"#,
+        hparams.trim_start_matches(", "),
+        fields("        private "),
+        init("            "),
+        fields("            "),
+        init("                "),
+    ))
+}
+
+/// Renders Java type arguments, or nothing if there are none.
+fn angle(a: impl Iterator<Item = String>) -> String {
+    let a = a.join(", ");
+    if a.is_empty() {
+        a
+    } else {
+        format!("<{a}>")
+    }
+}
+
+/// Returns the Java type of a PIT argument at the WebAssembly boundary.
+pub fn ffi_ty(a: &Arg) -> &'static str {
+    match a {
+        Arg::I32 => "int",
+        Arg::I64 => "long",
+        Arg::F32 => "float",
+        Arg::F64 => "double",
+        Arg::Resource { .. } => "int",
+        _ => todo!(),
+    }
+}
+
+/// Returns the high-level Java type of a PIT argument.
+pub fn jty(a: &Arg, rid: &str, generics: u32) -> String {
+    match res(a, rid, generics, "") {
+        Some((t, _)) => t,
+        None => ffi_ty(a).to_owned(),
+    }
+}
+
+/// Resolves a typed resource to its Java type and an expression for its `Handler`,
+/// whose handler parameters are prefixed with `prefix`. Returns `None` for anything
+/// passed as is.
+fn res(a: &Arg, rid: &str, generics: u32, prefix: &str) -> Option<(String, String)> {
+    let Arg::Resource { ty, ann, .. } = a else {
+        return None;
+    };
+    let instances: Vec<_> = ann
+        .iter()
+        .find_map(|a| {
+            if a.name == "instance" {
+                Some(a.value.as_str())
+            } else {
+                None
+            }
+        })
+        .into_iter()
+        .flat_map(|a| a.split(","))
+        .collect();
+    let is2 = instances
+        .iter()
+        .map(|s| instance(s, rid, generics, prefix))
+        .collect::<Option<Vec<_>>>()?;
+    let of = |r: &str, args: Vec<(String, String)>| {
+        let (t, h): (Vec<_>, Vec<_>) = args.into_iter().unzip();
+        (
+            format!("R{r}{}", angle(t.into_iter())),
+            format!("R{r}.handler({})", h.join(", ")),
+        )
+    };
+    match ty {
+        ResTy::Of(x) => Some(of(&hex::encode(x), is2)),
+        ResTy::This if instances.is_empty() => Some(of(
+            rid,
+            (0..generics)
+                .map(|a| (format!("T{a}"), format!("{prefix}h{a}")))
+                .collect(),
+        )),
+        ResTy::This => Some(of(rid, is2)),
+        ResTy::None if is2.len() == 1 => is2.into_iter().next(),
+        _ => None,
+    }
+}
+
+/// Resolves an instance specification, as in [`do_instances`], to a Java type and a
+/// `Handler` expression.
+fn instance(s: &str, rid: &str, generics: u32, prefix: &str) -> Option<(String, String)> {
+    let apply = |r: &str, n: u32, stack: &mut Vec<(String, String)>| {
+        let (t, h): (Vec<_>, Vec<_>) = (0..n).filter_map(|_| stack.pop()).unzip();
+        stack.push((
+            format!("R{r}{}", angle(t.into_iter())),
+            format!("R{r}.handler({})", h.join(", ")),
+        ));
+    };
+    let mut stack = vec![];
+    for s in s.split(";") {
+        if let Ok(g) = s.parse::<u32>() {
+            if g >= generics {
+                return None;
+            }
+            stack.push((format!("T{g}"), format!("{prefix}h{g}")));
+        }
+        if let Some(s) = s.strip_prefix("R") {
+            let (r, n) = s.split_once("N")?;
+            apply(r, n.parse().ok()?, &mut stack);
+        }
+        if s == "any" {
+            stack.push(("Integer".to_owned(), "Handler.INT".to_owned()));
+        }
+        if s == "this" {
+            apply(rid, generics, &mut stack);
+        }
+    }
+    stack.pop()
+}
+
+/// Converts a high-level Java value to its representation at the WebAssembly boundary.
+fn to_ffi(a: &Arg, rid: &str, generics: u32, prefix: &str, v: &str) -> String {
+    match res(a, rid, generics, prefix) {
+        Some((_, h)) => format!("{h}.handleOf({v})"),
+        None => v.to_owned(),
+    }
+}
+
+/// Converts a value at the WebAssembly boundary to its high-level Java representation.
+fn from_ffi(a: &Arg, rid: &str, generics: u32, prefix: &str, v: &str) -> String {
+    match res(a, rid, generics, prefix) {
+        Some((_, h)) => format!("{h}.fromHandle({v})"),
+        None => v.to_owned(),
+    }
+}
+
+/// Returns the Java runtime the generated code depends on, as `(path, contents)` pairs
+/// relative to the source root.
+///
+/// The runtime provides `pc.portal.pit.guest.Handler`, which converts between values
+/// and TPIT handles and imports `tpit.drop`.
+pub fn runtime() -> Vec<(&'static str, &'static str)> {
+    vec![("pc/portal/pit/guest/Handler.java", include_str!("Handler.java"))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pit_golden::{golden, iface, other, single_result_iface};
+    use std::process::Command;
+
+    #[test]
+    fn java_interface() {
+        golden!(
+            "Interface.java",
+            &emit(&single_result_iface(), "pc.portal.pit.guest").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_multiple_results() {
+        assert_eq!(
+            emit(&iface(), "pc.portal.pit.guest"),
+            Err(Unsupported {
+                method: "seek".to_owned()
+            })
+        );
+    }
+
+    /// Compiles the interfaces and the runtime with javac, against `tests/stubs`,
+    /// which stands in for the TeaVM annotations.
+    #[test]
+    fn compiles_with_javac() {
+        let dir = pit_golden::scratch("java");
+        let mut srcs = vec![];
+        for i in [single_result_iface(), other()] {
+            let src = format!("pc/portal/pit/guest/R{}.java", i.rid_str());
+            let java = emit(&i, "pc.portal.pit.guest").unwrap();
+            pit_golden::write_all(&dir, [(src.as_str(), java.as_str())]);
+            srcs.push(src);
+        }
+        pit_golden::write_all(&dir, runtime());
+        let stubs = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/stubs");
+        pit_golden::run(
+            Command::new("javac")
+                .current_dir(&dir)
+                .args(["-d", "classes", "-sourcepath"])
+                .arg(&stubs)
+                .args(srcs)
+                .args(runtime().into_iter().map(|(name, _)| name)),
+        );
+    }
+}
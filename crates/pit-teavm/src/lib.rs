@@ -27,6 +27,13 @@
 //!
 //! PIT interfaces can have generic parameters specified via the `[generics=N]`
 //! annotation, where N is the number of type parameters.
+//!
+//! ## Java
+//!
+//! The [`java`] module generates the same bindings as plain Java, for TeaVM projects
+//! built with Maven or Gradle.
+
+pub mod java;
 
 use itertools::Itertools;
 use pit_core::{Arg, Interface, ResTy, Sig};
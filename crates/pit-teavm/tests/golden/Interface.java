@@ -0,0 +1,151 @@
+package pc.portal.pit.guest;
+
+import java.util.HashMap;
+import java.util.Map;
+import org.teavm.interop.Export;
+import org.teavm.interop.Import;
+import pc.portal.pit.guest.Handler;
+
+public interface R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 extends AutoCloseable {
+    void give(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd p0);
+    Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd read(int p0);
+    R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 split();
+    void write(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd p0);
+
+    @Override
+    void close();
+
+    static Handler<R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526> handler() {
+        return new Handler<R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526>() {
+            @Override
+            public R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 fromHandle(int handle) {
+                return handle == 0 ? null : new Impl(handle);
+            }
+
+            @Override
+            public int handleOf(R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 value) {
+                return value == null ? 0 : Impl.of(value).handle;
+            }
+        };
+    }
+
+    final class Impl implements R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 {
+        public final int handle;
+
+        public Impl(int handle) {
+            this.handle = handle;
+        }
+
+        public Impl(R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 res) {
+            this(export(new Exported(res)));
+        }
+
+        @SuppressWarnings("unchecked")
+        public static Impl of(R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 value) {
+            return value instanceof Impl ? (Impl) value : new Impl(value);
+        }
+
+        @Override
+        public void close() {
+            Handler.drop(handle);
+        }
+
+        private static final class Exported {
+            final R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 res;
+
+            Exported(R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 res) {
+                this.res = res;
+            }
+        }
+
+        private static final Map<Integer, Exported> all = new HashMap<>();
+
+        @Import(name = "~pc.portal.pit.guest", module = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526")
+        private static native int ctor(int index);
+
+        private static int export(Exported e) {
+            int x = 0;
+            while (all.containsKey(x)) {
+                x++;
+            }
+            all.put(x, e);
+            return ctor(x);
+        }
+
+        @Export(name = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~pc.portal.pit.guest.drop")
+        public static void export$drop(int handle) {
+            Exported z = all.remove(handle);
+            if (z != null) {
+                z.res.close();
+            }
+        }
+
+        @Import(name = "give", module = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526")
+        private static native void import$give(int handle, int p0);
+
+        @Override
+        public void give(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd p0) {
+            import$give(handle, Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.handler().handleOf(p0));
+        }
+
+        @Export(name = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~pc.portal.pit.guest/give")
+        public static void export$give(int handle, int p0) {
+            call$give(all.get(handle), p0);
+        }
+
+        private static void call$give(Exported z, int p0) {
+            z.res.give(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.handler().fromHandle(p0));
+        }
+
+        @Import(name = "read", module = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526")
+        private static native int import$read(int handle, int p0);
+
+        @Override
+        public Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd read(int p0) {
+            return Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.handler().fromHandle(import$read(handle, p0));
+        }
+
+        @Export(name = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~pc.portal.pit.guest/read")
+        public static int export$read(int handle, int p0) {
+            return call$read(all.get(handle), p0);
+        }
+
+        private static int call$read(Exported z, int p0) {
+            return Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.handler().handleOf(z.res.read(p0));
+        }
+
+        @Import(name = "split", module = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526")
+        private static native int import$split(int handle);
+
+        @Override
+        public R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526 split() {
+            return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526.handler().fromHandle(import$split(handle));
+        }
+
+        @Export(name = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~pc.portal.pit.guest/split")
+        public static int export$split(int handle) {
+            return call$split(all.get(handle));
+        }
+
+        private static int call$split(Exported z) {
+            return R2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526.handler().handleOf(z.res.split());
+        }
+
+        @Import(name = "write", module = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526")
+        private static native void import$write(int handle, int p0);
+
+        @Override
+        public void write(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd p0) {
+            import$write(handle, Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.handler().handleOf(p0));
+        }
+
+        @Export(name = "tpit/2ff51a154900690f3f0df5e2f3beb4af398f6e20c135a1dad7981b5f75e04526/~pc.portal.pit.guest/write")
+        public static void export$write(int handle, int p0) {
+            call$write(all.get(handle), p0);
+        }
+
+        private static void call$write(Exported z, int p0) {
+            z.res.write(Re4e63a7f199fcbae0aab3b72493933895145f71724b8a86a173837ec9cc090cd.handler().fromHandle(p0));
+        }
+    }
+}
@@ -0,0 +1,15 @@
+package org.teavm.interop;
+
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+
+/** Stand-in for TeaVM's annotation, for checking generated code with javac. */
+@Retention(RetentionPolicy.CLASS)
+@Target(ElementType.METHOD)
+public @interface Import {
+    String module() default "";
+
+    String name();
+}
@@ -22,6 +22,9 @@
 //! When the handle is dropped, it calls the TPIT drop function to release the
 //! underlying resource.
 //!
+//! Resources returned without ownership transfer are [`Lent`] handles, which borrow
+//! the resource but own their table slot, and release it with `tpit.void`.
+//!
 //! ## Usage
 //!
 //! This crate is typically used as a dependency for generated PIT guest code.
@@ -29,7 +32,7 @@
 //!
 //! ## Safety
 //!
-//! The [`Tpit::new`] and [`Tpit::summon`] functions are unsafe because they create
+//! The [`Tpit::new`], [`Tpit::summon`] and [`Lent::new`] functions are unsafe because they create
 //! handles from raw integers without verifying that the handle is valid.
 //!
 //! ## no_std
@@ -40,8 +43,9 @@
 #![no_std]
 use core::{
     marker::PhantomData,
-    mem::forget,
+    mem::{forget, ManuallyDrop},
     num::NonZeroU32,
+    ops::{Deref, DerefMut},
     sync::{
         atomic::{AtomicUsize, Ordering},
         // Arc,
@@ -146,3 +150,72 @@ impl<D> Tpit<D> {
         unsafe { core::mem::transmute(a) }
     }
 }
+
+/// A tablified PIT resource handle lent by its owner.
+///
+/// Methods returning resources without ownership transfer return `Lent` handles. The
+/// owner keeps the resource alive while it is lent; the borrower may call its methods
+/// through [`Deref`] and [`DerefMut`], but must not drop or replace the underlying
+/// [`Tpit`].
+///
+/// A handle received from another module owns a table slot of its own, which is
+/// released with `tpit.void`, without dropping the resource, when the `Lent` is
+/// dropped. A handle lent with [`Lent::of`] borrows the owner's slot.
+pub struct Lent<'a, D> {
+    tpit: ManuallyDrop<Tpit<D>>,
+    slot: bool,
+    phantom: PhantomData<&'a mut Tpit<D>>,
+}
+impl<D> Drop for Lent<'_, D> {
+    fn drop(&mut self) {
+        #[link(wasm_import_module = "tpit")]
+        extern "C" {
+            fn void(a: u32);
+        }
+        let p = self.tpit.ptr();
+        if self.slot && p != 0 {
+            unsafe {
+                void(p);
+            }
+        }
+    }
+}
+impl<D> Lent<'static, D> {
+    /// Creates a `Lent` handle from a raw integer pointer owning its table slot, but
+    /// not the resource.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is a valid handle obtained from the TPIT
+    /// runtime for a borrowed resource, and that the resource outlives the handle.
+    ///
+    /// A `ptr` value of 0 is treated as a null reference.
+    pub unsafe fn new(ptr: u32) -> Self {
+        Self {
+            tpit: ManuallyDrop::new(unsafe { Tpit::new(ptr) }),
+            slot: true,
+            phantom: PhantomData,
+        }
+    }
+}
+impl<'a, D> Lent<'a, D> {
+    /// Lends an owned handle, for as long as it is borrowed.
+    pub fn of(a: &'a mut Tpit<D>) -> Self {
+        Self {
+            tpit: ManuallyDrop::new(unsafe { Tpit::new(a.ptr()) }),
+            slot: false,
+            phantom: PhantomData,
+        }
+    }
+}
+impl<D> Deref for Lent<'_, D> {
+    type Target = Tpit<D>;
+    fn deref(&self) -> &Tpit<D> {
+        &self.tpit
+    }
+}
+impl<D> DerefMut for Lent<'_, D> {
+    fn deref_mut(&mut self) -> &mut Tpit<D> {
+        &mut self.tpit
+    }
+}